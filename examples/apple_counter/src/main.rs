@@ -1,6 +1,7 @@
 use gui::gui_widget::button::ButtonHandler;
 use gui::Updateable;
 use gui::{type_registry, ToComponent};
+use gui::{MenuHandler, PointerInfo, ShortcutHandler};
 
 #[derive(ToComponent, Default)]
 struct AppleCounter {
@@ -11,6 +12,10 @@ impl ButtonHandler<gen::AddApple> for AppleCounter {
     fn on_press(&mut self) {
         *self.apple_count.invalidate() += 1;
     }
+
+    fn on_long_press(&mut self, _pointer: PointerInfo) {
+        *self.apple_count.invalidate() += 10;
+    }
 }
 
 impl ButtonHandler<gen::ResetApple> for AppleCounter {
@@ -19,6 +24,24 @@ impl ButtonHandler<gen::ResetApple> for AppleCounter {
     }
 }
 
+impl ShortcutHandler<gen::ResetApple> for AppleCounter {
+    fn on_shortcut(&mut self) {
+        *self.apple_count.invalidate() = 0;
+    }
+}
+
+impl MenuHandler<gen::AddApple> for AppleCounter {
+    fn on_select(&mut self) {
+        *self.apple_count.invalidate() += 1;
+    }
+}
+
+impl MenuHandler<gen::ResetApple> for AppleCounter {
+    fn on_select(&mut self) {
+        *self.apple_count.invalidate() = 0;
+    }
+}
+
 type_registry!();
 
 fn main() {
@@ -60,4 +83,39 @@ mod test {
         }
         assert_screenshot!(harness, "ten_apples")
     }
+
+    #[test]
+    fn long_press_adds_ten() {
+        let mut harness = TestHarness::new(AppleCounter::default(), (800.0, 400.0));
+        let increment = harness.get_id("AddApple").unwrap();
+        harness.simulate_long_press(Some(increment));
+        assert_eq!(*harness.get_component().apple_count.get_value(), 10);
+        harness.simulate_pointer_down_up(PointerButton::Secondary, Some(increment));
+        assert_eq!(*harness.get_component().apple_count.get_value(), 10);
+    }
+
+    #[test]
+    fn shortcut_resets() {
+        let mut harness = TestHarness::new(AppleCounter::default(), (800.0, 400.0));
+        let increment = harness.get_id("AddApple").unwrap();
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(increment));
+        harness.simulate_shortcut("R");
+        assert_eq!(*harness.get_component().apple_count.get_value(), 1);
+        harness.simulate_shortcut("Mod+R");
+        assert_eq!(*harness.get_component().apple_count.get_value(), 0);
+    }
+
+    #[test]
+    fn menus_change_count() {
+        let mut harness = TestHarness::new(AppleCounter::default(), (800.0, 400.0));
+        harness.select_menu_item(&["Apples", "Add an apple"]);
+        assert_eq!(*harness.get_component().apple_count.get_value(), 1);
+
+        let count = harness.get_id("AppleCount").unwrap();
+        harness.simulate_pointer_down_up(PointerButton::Secondary, Some(count));
+        assert!(harness.get_popup().is_some());
+        harness.select_popup_item("Reset");
+        assert!(harness.get_popup().is_none());
+        assert_eq!(*harness.get_component().apple_count.get_value(), 0);
+    }
 }
@@ -0,0 +1,27 @@
+use gui::gui_widget::button::ButtonHandler;
+use gui::{Dialog, ToComponent};
+
+#[derive(ToComponent, Default)]
+pub struct ConfirmReset {
+    confirmed: Option<bool>,
+}
+
+impl ButtonHandler<gen::ConfirmBtn> for ConfirmReset {
+    fn on_press(&mut self) {
+        self.confirmed = Some(true);
+    }
+}
+
+impl ButtonHandler<gen::CancelBtn> for ConfirmReset {
+    fn on_press(&mut self) {
+        self.confirmed = Some(false);
+    }
+}
+
+impl Dialog for ConfirmReset {
+    type Result = bool;
+
+    fn take_result(&mut self) -> Option<bool> {
+        self.confirmed.take()
+    }
+}
@@ -1,20 +1,15 @@
+use crate::confirm_reset::ConfirmReset;
+use gui::gui_core::OnMessage;
 use gui::gui_widget::button::ButtonHandler;
-use gui::{type_registry, ToComponent, Update, Updateable};
+use gui::{type_registry, App, MenuHandler, ToComponent, Updateable};
+
+mod confirm_reset;
 
 #[derive(ToComponent, Default)]
 struct Counter {
     count: Updateable<u32>,
 }
 
-impl Update<gen::disabled_decrement> for Counter {
-    fn is_updated(&self) -> bool {
-        self.count.is_updated()
-    }
-    fn value(&self) -> bool {
-        self.count.value() == 0
-    }
-}
-
 impl ButtonHandler<gen::IncrementBtn> for Counter {
     fn on_press(&mut self) {
         *self.count.invalidate() += 1;
@@ -27,6 +22,47 @@ impl ButtonHandler<gen::DecrementBtn> for Counter {
     }
 }
 
+enum Message {
+    SetCount(u32),
+    /// The result of the dialog asking to reset the count.
+    Reset(bool),
+}
+
+impl OnMessage for Counter {
+    type Message = Message;
+
+    fn on_message(&mut self, message: Message) {
+        match message {
+            Message::SetCount(count) => *self.count.invalidate() = count,
+            Message::Reset(true) => *self.count.invalidate() = 0,
+            Message::Reset(false) => {}
+        }
+    }
+}
+
+impl MenuHandler<gen::NewWindow> for Counter {
+    fn on_select(&mut self) {
+        match gui::open_window(Counter::default(), App::new()) {
+            Ok(window) => window.send(Message::SetCount(*self.count.get_value())),
+            Err(error) => eprintln!("Could not open a window: {error}"),
+        }
+    }
+}
+
+impl MenuHandler<gen::CopyToMain> for Counter {
+    fn on_select(&mut self) {
+        if let Some(main) = gui::main_window::<Counter>() {
+            main.send(Message::SetCount(*self.count.get_value()));
+        }
+    }
+}
+
+impl MenuHandler<gen::Reset> for Counter {
+    fn on_select(&mut self) {
+        gui::show_dialog::<Counter, _>(ConfirmReset::default(), Message::Reset);
+    }
+}
+
 type_registry!();
 
 fn main() {
@@ -35,7 +71,7 @@ fn main() {
 
 #[cfg(test)]
 mod test {
-    use crate::Counter;
+    use crate::{Counter, Message};
     use gui::{assert_screenshot, PointerButton, TestHarness};
 
     #[test]
@@ -75,4 +111,72 @@ mod test {
         assert_screenshot!(harness, "count_not_incremented");
         assert_eq!(*harness.get_component().count.get_value(), 1);
     }
+
+    #[test]
+    fn new_window_copies_count() {
+        let mut harness = TestHarness::new(Counter::default(), (500.0, 500.0));
+        let incr_btn = harness.get_id("IncrementBtn").unwrap();
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(incr_btn));
+        harness.select_menu_item(&["Window", "New window"]);
+
+        let mut window = TestHarness::<Counter>::from_opened_window((500.0, 500.0)).unwrap();
+        assert_eq!(*window.get_component().count.get_value(), 1);
+        assert!(TestHarness::<Counter>::from_opened_window((500.0, 500.0)).is_none());
+
+        // The opened window can send its count back to the main window.
+        let incr_btn = window.get_id("IncrementBtn").unwrap();
+        window.simulate_pointer_down_up(PointerButton::Primary, Some(incr_btn));
+        window.select_menu_item(&["Window", "Copy to main window"]);
+        harness.receive_messages();
+        assert_eq!(*harness.get_component().count.get_value(), 2);
+    }
+
+    #[test]
+    fn messages_wait_for_the_window() {
+        let window = gui::open_window(Counter::default(), gui::App::new()).unwrap();
+        window.send(Message::SetCount(3));
+        let mut window = TestHarness::<Counter>::from_opened_window((500.0, 500.0)).unwrap();
+        window.receive_messages();
+        assert_eq!(*window.get_component().count.get_value(), 3);
+    }
+
+    #[test]
+    fn hover_shows_tooltip() {
+        let mut harness = TestHarness::new(Counter::default(), (500.0, 500.0));
+        let incr_btn = harness.get_id("IncrementBtn").unwrap();
+        harness.simulate_hover(incr_btn);
+        assert_eq!(harness.get_tooltip(), Some("Adds one to the count"));
+
+        // Pressing the button hides the tooltip.
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(incr_btn));
+        assert_eq!(harness.get_tooltip(), None);
+        let decr_btn = harness.get_id("DecrementBtn").unwrap();
+        harness.simulate_hover(decr_btn);
+        assert_eq!(harness.get_tooltip(), None);
+        harness.simulate_hover(incr_btn);
+        assert_eq!(harness.get_tooltip(), Some("Adds one to the count"));
+    }
+
+    #[test]
+    fn reset_asks_for_confirmation() {
+        let mut harness = TestHarness::new(Counter::default(), (500.0, 500.0));
+        let incr_btn = harness.get_id("IncrementBtn").unwrap();
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(incr_btn));
+        harness.select_menu_item(&["Window", "Reset"]);
+        assert_eq!(harness.overlay_count(), 1);
+
+        // The dialog blocks the counter until it is closed.
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(incr_btn));
+        assert_eq!(*harness.get_component().count.get_value(), 1);
+        let cancel = harness.get_id("CancelBtn").unwrap();
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(cancel));
+        assert_eq!(harness.overlay_count(), 0);
+        assert_eq!(*harness.get_component().count.get_value(), 1);
+
+        harness.select_menu_item(&["Window", "Reset"]);
+        let confirm = harness.get_id("ConfirmBtn").unwrap();
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(confirm));
+        assert_eq!(harness.overlay_count(), 0);
+        assert_eq!(*harness.get_component().count.get_value(), 0);
+    }
 }
@@ -0,0 +1,3 @@
+fn main() {
+    gui_build::build("gui.yaml");
+}
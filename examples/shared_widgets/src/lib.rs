@@ -0,0 +1,39 @@
+//! A library of components exported for the layout files of other crates.
+use gui::gui_core::OnMessage;
+use gui::gui_widget::button::ButtonHandler;
+use gui::{type_registry, Signal, ToComponent};
+
+/// Counts the presses of its button.
+#[derive(ToComponent, Default)]
+pub struct Tally {
+    presses: Signal<usize>,
+}
+
+impl Tally {
+    /// Counts into `presses`, which can be shared with the component holding the tally.
+    pub fn new(presses: Signal<usize>) -> Self {
+        Self { presses }
+    }
+}
+
+pub enum Message {
+    Reset,
+}
+
+impl OnMessage for Tally {
+    type Message = Message;
+
+    fn on_message(&mut self, message: Message) {
+        match message {
+            Message::Reset => self.presses.set_value(0),
+        }
+    }
+}
+
+impl ButtonHandler<gen::AddBtn> for Tally {
+    fn on_press(&mut self) {
+        self.presses.update(|presses| *presses += 1);
+    }
+}
+
+type_registry!(export);
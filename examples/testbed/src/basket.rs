@@ -0,0 +1,52 @@
+use gui::gui_widget::button::ButtonHandler;
+use gui::{Computed, Signal, ToComponent};
+
+/// Apples that can be eaten until the basket is empty, which is computed from the apples.
+#[derive(ToComponent)]
+pub struct Basket {
+    apples: Signal<u32>,
+    empty: Computed<bool>,
+}
+
+impl Basket {
+    pub fn new(apples: Signal<u32>) -> Self {
+        let empty = Computed::new({
+            let apples = apples.clone();
+            move || apples.value() == 0
+        });
+        Self { apples, empty }
+    }
+}
+
+impl ButtonHandler<gen::EatBtn> for Basket {
+    fn on_press(&mut self) {
+        self.apples.update(|apples| *apples -= 1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Basket;
+    use gui::{PointerButton, Signal, TestHarness};
+
+    #[test]
+    fn computed_follows_signal() {
+        let apples = Signal::new(0);
+        let mut harness = TestHarness::new(Basket::new(apples.clone()), (300.0, 200.0));
+        let eat = harness.get_id("EatBtn").unwrap();
+        // The button is disabled while the basket is empty.
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(eat));
+        assert_eq!(apples.value(), 0);
+
+        // Updating the signal outside the component updates the computed variable.
+        apples.update(|apples| *apples = 2);
+        harness.receive_messages();
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(eat));
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(eat));
+        assert_eq!(apples.value(), 0);
+
+        // Eating the last apple disables the button again.
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(eat));
+        assert_eq!(apples.value(), 0);
+    }
+}
@@ -0,0 +1,93 @@
+use gui::gui_core::vello::kurbo::Vec2;
+use gui::gui_widget::clickable::ClickableHandler;
+use gui::{PointerInfo, ToComponent};
+
+/// Records the gestures made on a clickable area.
+#[derive(ToComponent, Default)]
+pub struct ClickLog {
+    pub gestures: Vec<String>,
+}
+
+impl ClickableHandler<gen::Area> for ClickLog {
+    fn on_press(&mut self) {
+        self.gestures.push("press".into());
+    }
+
+    fn on_hover_enter(&mut self, _pointer: PointerInfo) {
+        self.gestures.push("hover enter".into());
+    }
+
+    fn on_hover_leave(&mut self, _pointer: PointerInfo) {
+        self.gestures.push("hover leave".into());
+    }
+
+    fn on_secondary_press(&mut self, _pointer: PointerInfo) {
+        self.gestures.push("secondary press".into());
+    }
+
+    fn on_double_click(&mut self, _pointer: PointerInfo) {
+        self.gestures.push("double click".into());
+    }
+
+    fn on_long_press(&mut self, _pointer: PointerInfo) {
+        self.gestures.push("long press".into());
+    }
+
+    fn on_drag_start(&mut self, _pointer: PointerInfo) {
+        self.gestures.push("drag start".into());
+    }
+
+    fn on_drag(&mut self, _pointer: PointerInfo, offset: Vec2) {
+        self.gestures.push(format!("drag {}", offset.x));
+    }
+
+    fn on_drag_end(&mut self, _pointer: PointerInfo) {
+        self.gestures.push("drag end".into());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ClickLog;
+    use gui::gui_core::Point;
+    use gui::{PointerButton, TestHarness};
+
+    fn take_gestures(harness: &mut TestHarness<ClickLog>) -> Vec<String> {
+        std::mem::take(&mut harness.get_component().gestures)
+    }
+
+    #[test]
+    fn gestures() {
+        let mut harness = TestHarness::new(ClickLog::default(), (300.0, 200.0));
+        let area = harness.get_id("Area").unwrap();
+
+        harness.simulate_pointer_move(area.0, area.1, None);
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(area));
+        assert_eq!(take_gestures(&mut harness), ["hover enter", "press"]);
+
+        // The second press of a double click is still a press.
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(area));
+        assert_eq!(take_gestures(&mut harness), ["double click", "press"]);
+
+        harness.simulate_long_press(Some(area));
+        assert_eq!(take_gestures(&mut harness), ["long press"]);
+
+        harness.simulate_pointer_down_up(PointerButton::Secondary, Some(area));
+        assert_eq!(take_gestures(&mut harness), ["secondary press"]);
+
+        // Moving the pointer while pressed drags instead of pressing. The drag starts away from
+        // the last press, so that it is not a double click.
+        let centre = harness.get_local_rect(area.0, area.1).center();
+        harness.simulate_pointer_move(area.0, area.1, Some(centre - (20.0, 0.0)));
+        harness.simulate_pointer_down(PointerButton::Primary, None);
+        harness.simulate_pointer_move(area.0, area.1, Some(centre + (20.0, 0.0)));
+        harness.simulate_pointer_up(PointerButton::Primary, None);
+        assert_eq!(
+            take_gestures(&mut harness),
+            ["drag start", "drag 40", "drag end"]
+        );
+
+        harness.simulate_pointer_move(area.0, area.1, Some(Point::new(-10.0, -10.0)));
+        assert_eq!(take_gestures(&mut harness), ["hover leave"]);
+    }
+}
@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use gui::gui_widget::file_picker::FilePickerHandler;
+use gui::ToComponent;
+
+/// Stores the files chosen in a file dialog.
+#[derive(ToComponent, Default)]
+pub struct FileLog {
+    pub opened: Vec<PathBuf>,
+    pub cancelled: bool,
+}
+
+impl FilePickerHandler<gen::Open> for FileLog {
+    fn on_files_chosen(&mut self, paths: Vec<PathBuf>) {
+        self.opened = paths;
+    }
+
+    fn on_cancel(&mut self) {
+        self.cancelled = true;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FileLog;
+    use gui::{PointerButton, TestHarness};
+    use std::path::PathBuf;
+
+    #[test]
+    fn choose_files() {
+        let mut harness = TestHarness::new(FileLog::default(), (300.0, 200.0));
+        let open = harness.get_id("Open").unwrap();
+
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(open));
+        harness.choose_files(["notes.txt", "todo.txt"]);
+        let component = harness.get_component();
+        assert_eq!(
+            component.opened,
+            [PathBuf::from("notes.txt"), PathBuf::from("todo.txt")]
+        );
+        assert!(!component.cancelled);
+
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(open));
+        harness.choose_files(Vec::<PathBuf>::new());
+        assert!(harness.get_component().cancelled);
+    }
+}
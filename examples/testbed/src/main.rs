@@ -1,23 +1,88 @@
 use crate::traffic_light::TrafficLight;
 use gui::gui_widget::button::ButtonHandler;
+use gui::gui_widget::select::SelectHandler;
 use gui::CompHolder;
-use gui::{type_registry, ToComponent};
+use gui::{type_registry, ToComponent, Updateable};
 
+mod basket;
+mod click_log;
+mod file_log;
+mod notes;
+mod reorder_list;
+mod scoreboard;
+mod shelf;
+mod size_picker;
 mod traffic_light;
 
 #[derive(ToComponent, Default)]
 struct Holder {
     light: CompHolder<TrafficLight>,
+    colour: Updateable<usize>,
 }
 
 impl ButtonHandler<gen::Button> for Holder {
     fn on_press(&mut self) {
+        *self.colour.invalidate() = (self.colour.value() + 1) % 4;
         self.light.send_message(traffic_light::Message::Next);
     }
 }
 
+impl SelectHandler<gen::ColourSelect> for Holder {
+    fn on_select(&mut self, index: usize) {
+        self.light.send_message(traffic_light::Message::Set(index));
+    }
+}
+
 type_registry!();
 
 fn main() {
     gui::run(Holder::default())
 }
+
+#[cfg(test)]
+mod test {
+    use crate::Holder;
+    use gui::{PointerButton, TestHarness};
+
+    #[test]
+    fn select_colour() {
+        let mut harness = TestHarness::new(Holder::default(), (500.0, 500.0));
+        let select = harness.get_id("ColourSelect").unwrap();
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(select));
+        assert_eq!(
+            harness.get_option_list(),
+            Some(&["Red", "Red and yellow", "Green", "Yellow"].map(String::from)[..])
+        );
+        harness.select_option("Green");
+        assert!(harness.get_option_list().is_none());
+        assert_eq!(harness.get_component().colour.value(), 2);
+
+        // Pressing the button moves the selection on with the lights.
+        let button = harness.get_id("Button").unwrap();
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(button));
+        assert_eq!(harness.get_component().colour.value(), 3);
+    }
+
+    #[test]
+    fn select_colour_with_keys() {
+        let mut harness = TestHarness::new(Holder::default(), (500.0, 500.0));
+        let select = harness.get_id("ColourSelect").unwrap();
+        // The arrow keys change the option of the widget under the pointer.
+        harness.simulate_pointer_move(select.0, select.1, None);
+        harness.simulate_shortcut("Down");
+        assert_eq!(harness.get_component().colour.value(), 1);
+
+        harness.simulate_shortcut("Enter");
+        assert!(harness.get_option_list().is_some());
+        harness.simulate_shortcut("Down");
+        harness.simulate_shortcut("Down");
+        harness.simulate_shortcut("Enter");
+        assert!(harness.get_option_list().is_none());
+        assert_eq!(harness.get_component().colour.value(), 3);
+
+        harness.simulate_shortcut("Enter");
+        harness.simulate_shortcut("Esc");
+        assert!(harness.get_option_list().is_none());
+        assert_eq!(harness.get_component().colour.value(), 3);
+    }
+}
@@ -0,0 +1,44 @@
+use gui::ToComponent;
+
+/// Two lines of selectable text.
+#[derive(ToComponent, Default)]
+pub struct Notes {}
+
+#[cfg(test)]
+mod test {
+    use super::Notes;
+    use gui::gui_core::glazier::{KbKey, KeyEvent, Modifiers};
+    use gui::gui_core::Point;
+    use gui::{PointerButton, TestHarness};
+
+    /// Selects all the text of a widget by dragging over it, then copies it.
+    fn select_and_copy(harness: &mut TestHarness<Notes>, name: &str) -> Option<String> {
+        let (runtime_id, widget_id) = harness.get_id(name).unwrap();
+        let rect = harness.get_local_rect(runtime_id, widget_id);
+        let y = rect.center().y;
+        harness.simulate_pointer_move(runtime_id, widget_id, Some(Point::new(0.5, y)));
+        harness.simulate_pointer_down(PointerButton::Primary, None);
+        harness.simulate_pointer_move(runtime_id, widget_id, Some(Point::new(rect.x1 - 0.5, y)));
+        harness.simulate_pointer_up(PointerButton::Primary, None);
+        harness.simulate_key_down(&KeyEvent {
+            key: KbKey::Character("c".into()),
+            mods: Modifiers::CONTROL,
+            ..Default::default()
+        });
+        harness.get_clipboard()
+    }
+
+    #[test]
+    fn copy_selection() {
+        let mut harness = TestHarness::new(Notes::default(), (300.0, 200.0));
+        assert_eq!(
+            select_and_copy(&mut harness, "Body").as_deref(),
+            Some("Milk and eggs")
+        );
+        // Selecting other text clears the previous selection, so only the new one is copied.
+        assert_eq!(
+            select_and_copy(&mut harness, "Title").as_deref(),
+            Some("Shopping list")
+        );
+    }
+}
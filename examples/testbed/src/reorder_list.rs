@@ -0,0 +1,92 @@
+use gui::gui_core::Point;
+use gui::gui_widget::clickable::ClickableHandler;
+use gui::{DragPayload, ToComponent, Updateable};
+
+/// A list of three rows that can be reordered by dragging a row onto another row.
+#[derive(ToComponent)]
+pub struct ReorderList {
+    first: Updateable<String>,
+    second: Updateable<String>,
+    third: Updateable<String>,
+}
+
+/// The payload of a dragged row.
+struct Row(usize);
+
+impl ReorderList {
+    pub fn new(items: [&str; 3]) -> Self {
+        let [first, second, third] = items.map(|item| Updateable::new(item.to_string()));
+        Self {
+            first,
+            second,
+            third,
+        }
+    }
+
+    pub fn items(&self) -> [String; 3] {
+        [self.first.value(), self.second.value(), self.third.value()]
+    }
+
+    /// Moves the row at `from` to `to`, moving the rows in between.
+    fn move_row(&mut self, from: usize, to: usize) {
+        let mut items = self.items().to_vec();
+        let item = items.remove(from);
+        items.insert(to, item);
+        let rows = [&mut self.first, &mut self.second, &mut self.third];
+        for (row, item) in rows.into_iter().zip(items) {
+            row.set_value(item);
+        }
+    }
+}
+
+macro_rules! row_handler {
+    ($row:ident, $index:literal) => {
+        impl ClickableHandler<gen::$row> for ReorderList {
+            fn drag_payload(&mut self) -> Option<DragPayload> {
+                Some(DragPayload::new(Row($index)))
+            }
+
+            fn on_drop(&mut self, payload: &DragPayload, _pos: Point) {
+                if let Some(Row(from)) = payload.get::<Row>() {
+                    self.move_row(*from, $index);
+                }
+            }
+        }
+    };
+}
+
+row_handler!(Row0, 0);
+row_handler!(Row1, 1);
+row_handler!(Row2, 2);
+
+#[cfg(test)]
+mod test {
+    use super::ReorderList;
+    use gui::TestHarness;
+
+    #[test]
+    fn drag_rows() {
+        let list = ReorderList::new(["Apples", "Bananas", "Cherries"]);
+        let mut harness = TestHarness::new(list, (300.0, 300.0));
+        let rows = ["Row0", "Row1", "Row2"].map(|row| harness.get_id(row).unwrap());
+
+        harness.simulate_drag(rows[0], rows[2]);
+        assert_eq!(
+            harness.get_component().items(),
+            ["Bananas", "Cherries", "Apples"]
+        );
+
+        harness.simulate_drag(rows[2], rows[1]);
+        assert_eq!(
+            harness.get_component().items(),
+            ["Bananas", "Apples", "Cherries"]
+        );
+
+        // Dropping a row onto itself leaves the list unchanged.
+        harness.simulate_drag(rows[0], rows[0]);
+        assert_eq!(
+            harness.get_component().items(),
+            ["Bananas", "Apples", "Cherries"]
+        );
+    }
+}
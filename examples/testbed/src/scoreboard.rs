@@ -0,0 +1,32 @@
+use gui::{CompHolder, ToComponent};
+use shared_widgets::Tally;
+
+/// Holds a tally, which is defined and exported by the `shared_widgets` crate.
+#[derive(ToComponent)]
+pub struct Scoreboard {
+    tally: CompHolder<Tally>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::Scoreboard;
+    use gui::{CompHolder, PointerButton, Signal, TestHarness};
+    use shared_widgets::{Message, Tally};
+
+    #[test]
+    fn holds_exported_component() {
+        let presses = Signal::new(0);
+        let scoreboard = Scoreboard {
+            tally: CompHolder::new(Tally::new(presses.clone())),
+        };
+        let mut harness = TestHarness::new(scoreboard, (300.0, 200.0));
+        let add = harness.get_id("AddBtn").unwrap();
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(add));
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(add));
+        assert_eq!(presses.value(), 2);
+
+        harness.get_component().tally.send_message(Message::Reset);
+        harness.receive_messages();
+        assert_eq!(presses.value(), 0);
+    }
+}
@@ -0,0 +1,83 @@
+use gui::gui_core::OnMessage;
+use gui::gui_widget::button::ButtonHandler;
+use gui::{CompHolder, Dialog, Signal, ToComponent};
+
+/// Holds an item, which shows a dialog itself rather than through the shelf.
+#[derive(ToComponent)]
+pub struct Shelf {
+    item: CompHolder<ShelfItem>,
+}
+
+#[derive(ToComponent)]
+pub struct ShelfItem {
+    removed: Signal<bool>,
+}
+
+impl ButtonHandler<gen::RemoveBtn> for ShelfItem {
+    fn on_press(&mut self) {
+        gui::show_dialog::<ShelfItem, _>(ConfirmRemove::default(), |remove| remove);
+    }
+}
+
+impl OnMessage for ShelfItem {
+    type Message = bool;
+
+    fn on_message(&mut self, remove: bool) {
+        self.removed.update(|removed| *removed = remove);
+    }
+}
+
+#[derive(ToComponent, Default)]
+pub struct ConfirmRemove {
+    remove: Option<bool>,
+}
+
+impl ButtonHandler<gen::YesBtn> for ConfirmRemove {
+    fn on_press(&mut self) {
+        self.remove = Some(true);
+    }
+}
+
+impl ButtonHandler<gen::NoBtn> for ConfirmRemove {
+    fn on_press(&mut self) {
+        self.remove = Some(false);
+    }
+}
+
+impl Dialog for ConfirmRemove {
+    type Result = bool;
+
+    fn take_result(&mut self) -> Option<bool> {
+        self.remove.take()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Shelf, ShelfItem};
+    use gui::{CompHolder, PointerButton, Signal, TestHarness};
+
+    #[test]
+    fn nested_component_receives_result() {
+        let removed = Signal::new(false);
+        let shelf = Shelf {
+            item: CompHolder::new(ShelfItem {
+                removed: removed.clone(),
+            }),
+        };
+        let mut harness = TestHarness::new(shelf, (300.0, 200.0));
+        let remove = harness.get_id("RemoveBtn").unwrap();
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(remove));
+        assert_eq!(harness.overlay_count(), 1);
+
+        let no = harness.get_id("NoBtn").unwrap();
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(no));
+        assert_eq!(harness.overlay_count(), 0);
+        assert!(!removed.with(|removed| *removed));
+
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(remove));
+        let yes = harness.get_id("YesBtn").unwrap();
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(yes));
+        assert!(removed.with(|removed| *removed));
+    }
+}
@@ -0,0 +1,45 @@
+use gui::gui_widget::select::SelectHandler;
+use gui::{ToComponent, Updateable};
+
+/// Picks one of a list of sizes that can be changed while a size is selected.
+#[derive(ToComponent)]
+pub struct SizePicker {
+    pub sizes: Updateable<Vec<String>>,
+    pub size: Updateable<usize>,
+}
+
+impl SelectHandler<gen::SizeSelect> for SizePicker {}
+
+impl SizePicker {
+    pub fn new(sizes: &[&str], size: usize) -> Self {
+        Self {
+            sizes: Updateable::new(sizes.iter().map(|s| s.to_string()).collect()),
+            size: Updateable::new(size),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SizePicker;
+    use gui::{PointerButton, TestHarness};
+
+    #[test]
+    fn options_shrink_below_selection() {
+        let sizes = ["XS", "S", "M", "L", "XL", "XXL"];
+        let mut harness = TestHarness::new(SizePicker::new(&sizes, 5), (300.0, 300.0));
+        harness
+            .get_component()
+            .sizes
+            .set_value(sizes[..3].iter().map(|s| s.to_string()).collect());
+        harness.receive_messages();
+        // The bound index is limited to the last of the remaining options.
+        assert_eq!(harness.get_component().size.value(), 2);
+
+        let select = harness.get_id("SizeSelect").unwrap();
+        harness.simulate_pointer_down_up(PointerButton::Primary, Some(select));
+        assert_eq!(harness.get_option_list().map(<[String]>::len), Some(3));
+        harness.select_option("XS");
+        assert_eq!(harness.get_component().size.value(), 0);
+    }
+}
@@ -8,6 +8,8 @@ pub struct TrafficLight {
 
 pub enum Message {
     Next,
+    /// Shows the colours at an index in the order the lights change.
+    Set(usize),
 }
 
 impl OnMessage for TrafficLight {
@@ -25,6 +27,11 @@ impl OnMessage for TrafficLight {
                 };
                 *self.state.invalidate() = next;
             }
+            Message::Set(index) => {
+                use gen::State;
+                let states = [State::Red, State::RedYellow, State::Green, State::Yellow];
+                *self.state.invalidate() = states[index % states.len()];
+            }
         }
     }
 }
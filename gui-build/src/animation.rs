@@ -0,0 +1,214 @@
+use crate::widget::Widget;
+use anyhow::bail;
+use gui_core::animation::is_animatable;
+use gui_core::parse::var::Name;
+use gui_core::parse::{StateDeclaration, TransitionDeclaration};
+use gui_core::widget::WidgetID;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use std::collections::BTreeMap;
+
+/// The transition used by a property when switching to each state.
+type StateTransitions<'a> = Vec<(&'a Name, &'a TransitionDeclaration)>;
+
+/// The transitions declared by the states of a component, grouped by the widget and property
+/// they animate.
+pub struct Transitions<'a> {
+    states: &'a [StateDeclaration],
+    properties: BTreeMap<(&'a str, &'a str), StateTransitions<'a>>,
+}
+
+impl<'a> Transitions<'a> {
+    pub fn new(states: &'a [StateDeclaration], widget_tree: &Widget) -> anyhow::Result<Self> {
+        let mut properties: BTreeMap<_, StateTransitions> = BTreeMap::new();
+        for state in states {
+            for transition in &state.transitions {
+                let widget_name = transition.name.as_str();
+                let Some(widget) = widget_tree
+                    .iter()
+                    .find(|w| w.widget_declaration.name.as_deref() == Some(widget_name))
+                else {
+                    bail!(
+                        "Could not find widget {widget_name} to animate in state {}",
+                        state.name
+                    );
+                };
+
+                for property in &transition.properties {
+                    if !widget.static_properties().any(|p| p == property.as_str()) {
+                        bail!("Property {property} of widget {widget_name} is not a static so cannot be animated");
+                    }
+                    let static_type = widget
+                        .widget_declaration
+                        .widget
+                        .get_static_types()
+                        .into_iter()
+                        .find(|(p, _)| *p == property.as_str())
+                        .map(|(_, t)| t);
+                    if let Some(static_type) = static_type.filter(|t| !is_animatable(t)) {
+                        let type_name = static_type.rsplit("::").next().unwrap_or(static_type);
+                        bail!("Property {property} of widget {widget_name} has type {type_name}, which cannot be animated (only Colour and f32)");
+                    }
+                    let state_transitions = properties
+                        .entry((widget_name, property.as_str()))
+                        .or_default();
+                    if state_transitions.iter().any(|(s, _)| *s == &state.name) {
+                        bail!("Property {property} of widget {widget_name} has multiple transitions in state {}", state.name);
+                    }
+                    state_transitions.push((&state.name, transition));
+                }
+            }
+        }
+        Ok(Self { states, properties })
+    }
+
+    /// No transitions, used for widgets that cannot be animated.
+    pub fn none() -> Self {
+        Self {
+            states: &[],
+            properties: BTreeMap::new(),
+        }
+    }
+
+    /// The transitions of the properties of a given widget.
+    pub fn for_widget(&self, widget: &Widget) -> Option<WidgetTransitions> {
+        let name = widget.widget_declaration.name.as_deref()?;
+        let properties = self
+            .properties
+            .iter()
+            .filter(|((w, _), _)| *w == name)
+            .map(|((_, p), t)| (*p, t))
+            .collect::<Vec<_>>();
+        (!properties.is_empty()).then_some(WidgetTransitions {
+            id: widget.id,
+            all_states: self.states.len(),
+            properties,
+        })
+    }
+
+    /// Generates the code that moves each running animation on to the next frame.
+    pub fn gen_frames(
+        &self,
+        widget_tree: &Widget,
+        widget_stmts: &[(WidgetID, TokenStream)],
+    ) -> TokenStream {
+        let mut stream = TokenStream::new();
+        let widget_ident = Ident::new("widget", Span::call_site());
+        let value_ident = Ident::new("value", Span::call_site());
+        let handle_ident = Ident::new("handle_ref", Span::call_site());
+
+        for widget in widget_tree.iter() {
+            let Some(transitions) = self.for_widget(widget) else {
+                continue;
+            };
+            let Some((_, widget_stmt)) = widget_stmts.iter().find(|(id, _)| *id == widget.id)
+            else {
+                continue;
+            };
+            let id = widget.id.id();
+            for (property, _) in transitions.properties {
+                let mut on_property_update = TokenStream::new();
+                widget.widget_declaration.widget.on_property_update(
+                    property,
+                    &widget_ident,
+                    &value_ident,
+                    &handle_ident,
+                    &mut on_property_update,
+                );
+                stream.extend(quote! {
+                    if let Some(value) = self.animations.next_frame((#id, #property), handle_ref) {
+                        let widget = &mut #widget_stmt;
+                        #on_property_update
+                    }
+                });
+            }
+        }
+        stream
+    }
+}
+
+/// The transitions of the properties of a single widget.
+pub struct WidgetTransitions<'a> {
+    id: WidgetID,
+    all_states: usize,
+    properties: Vec<(&'a str, &'a StateTransitions<'a>)>,
+}
+
+impl WidgetTransitions<'_> {
+    /// Generates the code to start animating `property` towards the current `value`, replacing
+    /// `value` with the value the property should be set to now.
+    pub fn gen_animate(&self, property: &str) -> Option<TokenStream> {
+        let (property, state_transitions) = self.properties.iter().find(|(p, _)| *p == property)?;
+        let id = self.id.id();
+        let arms = state_transitions.iter().map(|(state, transition)| {
+            let state = format_ident!("{}", state.as_str());
+            let duration = transition.duration;
+            let easing = transition.easing;
+            quote!(State::#state => Some(::gui::gui_core::animation::Transition::new(#duration, #easing)),)
+        });
+        let fallback = (state_transitions.len() < self.all_states).then(|| quote!(_ => None,));
+        Some(quote! {
+            let value = self.animations.animate_to(
+                (#id, #property),
+                value,
+                match self.state {
+                    #(#arms)*
+                    #fallback
+                },
+                handle_ref,
+            );
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Transitions;
+    use crate::widget::Widget;
+    use gui_core::parse::ComponentDeclaration;
+
+    fn gen_animate(widget: &str, property: &str) -> anyhow::Result<Option<String>> {
+        let declaration: ComponentDeclaration = serde_yaml::from_str(&format!(
+            r#"
+name: Light
+states:
+  - name: On
+    transitions:
+      - name: {widget}
+        properties: {property}
+        duration: 200
+    overrides:
+      - name: Label
+        widget: Text
+        properties:
+          size: 20
+  - name: Off
+    overrides: []
+child:
+  name: Label
+  widget: Text
+  properties:
+    text: Light
+    selectable: true"#
+        ))?;
+        let widget_tree = Widget::new(&declaration)?;
+        let transitions = Transitions::new(&declaration.states[..], &widget_tree)?;
+        Ok(transitions
+            .for_widget(&widget_tree)
+            .and_then(|t| t.gen_animate("size"))
+            .map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn test_transitions() {
+        let animate = gen_animate("Label", "size").unwrap().unwrap();
+        assert!(animate.contains("self . animations . animate_to"));
+        assert!(animate.contains("State :: On =>"));
+        assert!(animate.contains("_ => None"));
+
+        assert!(gen_animate("Missing", "size").is_err());
+        assert!(gen_animate("Label", "text").is_err());
+        let error = gen_animate("Label", "selectable").unwrap_err().to_string();
+        assert!(error.contains("has type bool, which cannot be animated"));
+    }
+}
@@ -1,17 +1,33 @@
+use crate::animation::Transitions;
 use crate::component_var::ComponentVars;
+use crate::expr;
 use crate::fluent;
 use crate::fluent::FluentIdent;
+use crate::hot_reload;
+use crate::menu::Menus;
+use crate::shortcut::Shortcuts;
+use crate::tooltip::Tooltips;
 use crate::widget::Widget;
+use crate::window::Window;
 use anyhow::{bail, Context};
+use gui_core::parse::var::Name;
 use gui_core::parse::{ComponentDeclaration, StateDeclaration};
 use itertools::Itertools;
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote};
+use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 use std::str::FromStr;
 
-pub fn create_component(out_dir: &Path, component: &ComponentDeclaration) -> anyhow::Result<()> {
+/// Generates the code for a component. If `layout_path` is given, the generated code will also
+/// support hot reloading the layout file at the path.
+pub fn create_component(
+    out_dir: &Path,
+    component: &ComponentDeclaration,
+    layout_path: Option<&Path>,
+) -> anyhow::Result<()> {
+    let hot_reload_component = layout_path.map(|_| component.name.as_str());
     let component_holder = format_ident!("{}Holder", *component.name);
     let component_name = format_ident!("{}", *component.name);
 
@@ -23,18 +39,42 @@ pub fn create_component(out_dir: &Path, component: &ComponentDeclaration) -> any
 
     let widget_tree = Widget::new(component)?;
 
+    for (name, _) in widget_tree.iter().flat_map(|w| &w.bindings) {
+        match normal_variables.iter().find(|v| v.name == *name) {
+            None => bail!("Could not find variable {name} to bind"),
+            Some(v) if v.expr.is_some() => {
+                bail!("Variable {name} is calculated from an expression so cannot be bound")
+            }
+            Some(_) => {}
+        }
+    }
+
     let component_vars = ComponentVars::new(&component.variables[..], &widget_tree)?;
 
     let mut widget_set = TokenStream::new();
     widget_tree.gen_widget_set(&mut widget_set);
 
+    let menus = Menus::new(component, &widget_tree).context("Invalid menus")?;
+    let window = Window::new(component).context("Invalid window")?;
+    let tooltips = Tooltips::new(component, &widget_tree).context("Invalid tooltips")?;
+
     let mut fluents = vec![];
     widget_tree.push_fluents(&mut fluents);
-
-    let bundle_func = (!fluents.is_empty()).then(|| fluent::gen_bundle_function(&component.name));
-
-    create_bundle(out_dir, &component.name, &fluents[..])
-        .context("Failed to create fluent bundle")?;
+    menus.push_fluents(&mut fluents);
+    window.push_fluents(&mut fluents);
+    tooltips.push_fluents(&mut fluents);
+    let mut item_fluents = vec![];
+    widget_tree.push_item_fluents(&mut item_fluents);
+
+    let bundle_func = (!fluents.is_empty() || !item_fluents.is_empty())
+        .then(|| fluent::gen_bundle_function(&component.name, hot_reload_component.is_some()));
+
+    create_bundle(
+        out_dir,
+        &component.name,
+        &[&fluents[..], &item_fluents[..]].concat(),
+    )
+    .context("Failed to create fluent bundle")?;
 
     let if_update: TokenStream = normal_variables
         .iter()
@@ -53,8 +93,16 @@ pub fn create_component(out_dir: &Path, component: &ComponentDeclaration) -> any
         .map(|fluent| &fluent.property_ident)
         .collect();
 
+    let transitions = Transitions::new(&component.states[..], &widget_tree)
+        .context("Failed to create transitions")?;
+
     let mut statics_update: TokenStream = TokenStream::new();
-    widget_tree.gen_statics(None, &mut statics_update);
+    widget_tree.gen_statics(
+        None,
+        hot_reload_component,
+        &transitions,
+        &mut statics_update,
+    );
 
     let mut prop_update: TokenStream = TokenStream::new();
     widget_tree.gen_fluent_update(None, &mut prop_update);
@@ -75,14 +123,32 @@ pub fn create_component(out_dir: &Path, component: &ComponentDeclaration) -> any
         })
         .collect();
 
+    let expr_updates = expr::gen_expr_updates(&normal_variables)
+        .context("Failed to create expression variables")?;
+
     let var_names = normal_variables
         .iter()
         .map(|n| Ident::new(&n.name, Span::call_site()));
 
-    let state_declaration = create_state(component.states.as_slice())?;
+    let state_declaration =
+        create_state(component.states.as_slice(), hot_reload_component.is_some())?;
 
     let mut struct_handlers = TokenStream::new();
-    widget_tree.gen_handler_structs(&mut struct_handlers)?;
+    widget_tree.gen_handler_structs(&component.states[..], &mut struct_handlers)?;
+    let shortcuts = Shortcuts::new(&component.shortcuts[..]).context("Invalid shortcuts")?;
+    // Shortcuts and menu items can share handler names with widgets and each other.
+    let mut declared_handlers = widget_tree.handler_names();
+    struct_handlers.extend(gen_handler_structs(
+        shortcuts.handlers().chain(menus.handlers()),
+        &mut declared_handlers,
+    ));
+    let shortcut = shortcuts.gen_shortcut();
+    let menu_functions = menus.gen_menu_functions();
+    let window_config = window.gen_window_config();
+    let menu_bar = menus.gen_menu_bar();
+    let context_menu_arms = menus.gen_context_menu_arms();
+    let tooltip_arms = tooltips.gen_tooltip_arms();
+    let menu_command_arms = menus.gen_menu_command_arms();
     let comp_var_structs = component_vars.gen_comp_var_structs();
     let multi_comp = component_vars.gen_multi_comp();
 
@@ -98,6 +164,62 @@ pub fn create_component(out_dir: &Path, component: &ComponentDeclaration) -> any
         quote!(#id => {#widget_get.event(event, handle_ref);})
     });
 
+    let animation_frames = transitions.gen_frames(&widget_tree, &id_to_widgets[..]);
+    let animations_type = (!animation_frames.is_empty())
+        .then(|| quote! {animations: ::gui::gui_core::animation::Animations,});
+    let animations_init =
+        (!animation_frames.is_empty()).then(|| quote! {animations: Default::default(),});
+
+    let item_event_arms = id_to_widgets
+        .iter()
+        .filter_map(|(widget_id, widget_get)| {
+            let widget = widget_tree.iter().find(|w| w.id == *widget_id)?;
+            Some(widget.for_each.as_ref()?.gen_item_event(widget, widget_get))
+        })
+        .collect_vec();
+    let has_items = !item_event_arms.is_empty();
+    let items_type = has_items.then(|| {
+        quote! {for_each_items: ::std::collections::HashMap<RuntimeID, WidgetID>,}
+    });
+    let items_init = has_items.then(|| quote! {for_each_items: Default::default(),});
+    // The widgets of each item of a ForEach use their own runtime ID, with the ForEach as the
+    // parent of the template.
+    let item_parent = has_items.then(|| {
+        quote! {
+            if let Some(for_each_id) = self.for_each_items.get(&runtime_id) {
+                return match self.comp_struct.get_parent(widget_id) {
+                    Some(parent) if parent == *for_each_id => Some((self.runtime_id, parent)),
+                    parent => parent.map(|parent| (runtime_id, parent)),
+                };
+            }
+        }
+    });
+    let not_item = has_items.then(|| quote! {&& !self.for_each_items.contains_key(&runtime_id)});
+    let item_event = has_items.then(|| {
+        quote! {
+            if let Some(for_each_id) = self.for_each_items.get(&runtime_id).copied() {
+                match for_each_id.id() {
+                    #(#item_event_arms)*
+                    _ => {},
+                }
+            } else
+        }
+    });
+
+    let gen_binding_updates = |on_change: TokenStream| -> TokenStream {
+        id_to_widgets
+            .iter()
+            .filter_map(|(widget_id, widget_get)| {
+                let widget = widget_tree.iter().find(|w| w.id == *widget_id)?;
+                Some(widget.gen_binding_updates(widget_get, &on_change))
+            })
+            .collect()
+    };
+    let binding_updates = gen_binding_updates(quote!());
+    // Widgets can also change bound values when they are updated, such as a selected index that
+    // is limited to fewer options. These are stored and shown by the other widgets straight away.
+    let update_rebinds = gen_binding_updates(quote!(rebound = true;));
+
     let mut parent_ids = vec![];
     widget_tree.get_parent_ids(&mut parent_ids);
     let parent_match_arms = parent_ids.iter().map(|(parent, children)| {
@@ -127,6 +249,19 @@ pub fn create_component(out_dir: &Path, component: &ComponentDeclaration) -> any
         .as_ref()
         .map(|_| quote! {state: Default::default(),});
 
+    let hot_reload_type = layout_path.map(|_| quote! {hot_reload_generation: u64,});
+    let hot_reload_init = layout_path.map(|_| quote! {hot_reload_generation: 0,});
+    let check_hot_reload = layout_path.map(|path| {
+        let path = path.display().to_string();
+        let name = component.name.as_str();
+        let structure = hot_reload::structure(component, &widget_tree);
+        quote! {
+            if ::gui::hot_reload::needs_update(#path, #name, #structure, &mut self.hot_reload_generation) {
+                force_update = true;
+            }
+        }
+    });
+
     let gen_module = quote! {
         #[allow(clippy::suspicious_else_formatting)]
         #[allow(clippy::collapsible_if)]
@@ -135,9 +270,10 @@ pub fn create_component(out_dir: &Path, component: &ComponentDeclaration) -> any
         mod gen {
             use super::__private_CompStruct as CompStruct;
             use std::any::Any;
+            use gui::gui_core::glazier::KeyEvent;
             use gui::gui_core::vello::SceneBuilder;
             use gui::gui_core::widget::{RuntimeID, Widget, WidgetID, RenderHandle, ResizeHandle, EventHandle, UpdateHandle, WidgetEvent, Handle};
-            use gui::gui_core::{Component, ComponentHolder, ComponentTypeInfo, LayoutConstraints, MultiComponent, Size, ToComponent, ToHandler, Update, Variable};
+            use gui::gui_core::{Bind, Component, ComponentHolder, ComponentTypeInfo, LayoutConstraints, MultiComponent, Size, ToComponent, ToHandler, Update, Variable};
 
             #state_declaration
 
@@ -145,6 +281,8 @@ pub fn create_component(out_dir: &Path, component: &ComponentDeclaration) -> any
 
             #struct_vars
 
+            #expr_updates
+
             #struct_handlers
 
             #comp_var_structs
@@ -157,12 +295,18 @@ pub fn create_component(out_dir: &Path, component: &ComponentDeclaration) -> any
 
             #bundle_func
 
+            #menu_functions
+
             #[allow(non_snake_case)]
             pub struct #component_holder {
                 comp_struct: CompStruct,
                 runtime_id: RuntimeID,
                 widget: #widget_type,
                 #state_type
+                #hot_reload_type
+                #animations_type
+                #items_type
+                theme_generation: u64,
                 multi_comp: MultiComponentHolder,
                 #( #fluent_arg_idents: FluentArgs<'static> ),*
             }
@@ -179,6 +323,10 @@ pub fn create_component(out_dir: &Path, component: &ComponentDeclaration) -> any
                         multi_comp: MultiComponentHolder::new(&mut self, runtime_id),
                         comp_struct: self,
                         #state_init
+                        #hot_reload_init
+                        #animations_init
+                        #items_init
+                        theme_generation: 0,
                         #( #fluent_arg_idents: FluentArgs::new() ),*
                     }
                 }
@@ -232,14 +380,25 @@ pub fn create_component(out_dir: &Path, component: &ComponentDeclaration) -> any
                     let mut update_handle = UpdateHandle::new(handle, self.runtime_id);
                     let handle_ref = &mut update_handle;
                     #( let mut #fluent_properties = false; )*
+                    if ::gui::gui_core::theme::is_updated(&mut self.theme_generation) {
+                        force_update = true;
+                    }
+                    #check_hot_reload
                     #check_state
                     if force_update {
                         #statics_update
                     }
+                    #animation_frames
                     #if_update
                     #prop_update
                     #( <CompStruct as Update<#var_names>>::reset(&mut self.comp_struct); )*
-                    update_handle.unwrap() || need_multi_comp_resize
+                    let resize = update_handle.unwrap() || need_multi_comp_resize;
+                    let mut rebound = false;
+                    #update_rebinds
+                    if rebound {
+                        return self.update_vars(false, handle) || resize;
+                    }
+                    resize
                 }
 
                 fn resize(
@@ -256,9 +415,11 @@ pub fn create_component(out_dir: &Path, component: &ComponentDeclaration) -> any
                     event: WidgetEvent,
                     handle: &mut Handle,
                 ) -> bool {
+                    let _running = self.runtime_id.enter();
                     let mut event_handle = EventHandle::new(handle, self.runtime_id, &mut self.comp_struct, &mut self.multi_comp);
                     self.widget.event(event, &mut event_handle);
                     let (mut resize, events) = event_handle.unwrap();
+                    #binding_updates
                     for (runtime_id, widget_id, e) in events {
                         if self.event(runtime_id, widget_id, e, handle) {
                             resize = true;
@@ -272,6 +433,7 @@ pub fn create_component(out_dir: &Path, component: &ComponentDeclaration) -> any
                     runtime_id: RuntimeID,
                     widget_id: WidgetID,
                 ) -> Option<(RuntimeID, WidgetID)> {
+                    #item_parent
                     if runtime_id != self.runtime_id {
                         self.multi_comp.get_parent(runtime_id, widget_id)
                     } else {
@@ -298,16 +460,20 @@ pub fn create_component(out_dir: &Path, component: &ComponentDeclaration) -> any
                     event: WidgetEvent,
                     handle: &mut Handle,
                 ) -> bool {
-                    if runtime_id != self.runtime_id {
+                    if runtime_id != self.runtime_id #not_item {
                         return self.multi_comp.event(runtime_id, widget_id, event, handle);
                     }
+                    let _running = self.runtime_id.enter();
                     let mut event_handle = EventHandle::new(handle, self.runtime_id, &mut self.comp_struct, &mut self.multi_comp);
                     let handle_ref = &mut event_handle;
-                    match widget_id.id() {
-                        #(#event_match_arms)*
-                        _ => {},
+                    #item_event {
+                        match widget_id.id() {
+                            #(#event_match_arms)*
+                            _ => {},
+                        }
                     }
                     let (mut resize, events) = event_handle.unwrap();
+                    #binding_updates
                     for (runtime_id, widget_id, e) in events {
                         if self.event(runtime_id, widget_id, e, handle) {
                             resize = true;
@@ -315,6 +481,67 @@ pub fn create_component(out_dir: &Path, component: &ComponentDeclaration) -> any
                     }
                     resize
                 }
+
+                fn shortcut(&mut self, event: &KeyEvent) -> bool {
+                    #shortcut
+                }
+
+                fn window_config(&self) -> ::gui::gui_core::window::WindowConfig {
+                    #window_config
+                }
+
+                fn menu_bar(&self) -> Option<::gui::gui_core::menu::Menu> {
+                    #menu_bar
+                }
+
+                fn context_menu(
+                    &self,
+                    runtime_id: RuntimeID,
+                    widget_id: WidgetID,
+                ) -> Option<::gui::gui_core::menu::Menu> {
+                    if runtime_id != self.runtime_id #not_item {
+                        return self.multi_comp.context_menu(runtime_id, widget_id);
+                    }
+                    match widget_id.id() {
+                        #context_menu_arms
+                        _ => None,
+                    }
+                }
+
+                fn tooltip(&self, runtime_id: RuntimeID, widget_id: WidgetID) -> Option<String> {
+                    if runtime_id != self.runtime_id #not_item {
+                        return self.multi_comp.tooltip(runtime_id, widget_id);
+                    }
+                    match widget_id.id() {
+                        #tooltip_arms
+                        _ => None,
+                    }
+                }
+
+                fn menu_command(&mut self, runtime_id: RuntimeID, id: u32) -> bool {
+                    if runtime_id != self.runtime_id {
+                        return self.multi_comp.menu_command(runtime_id, id);
+                    }
+                    let _running = self.runtime_id.enter();
+                    match id {
+                        #menu_command_arms
+                        _ => {}
+                    }
+                    true
+                }
+
+                fn with_comp_struct(
+                    &mut self,
+                    runtime_id: RuntimeID,
+                    f: &mut dyn FnMut(&mut dyn Any),
+                ) -> bool {
+                    if runtime_id != self.runtime_id {
+                        return self.multi_comp.with_comp_struct(runtime_id, f);
+                    }
+                    f(&mut self.comp_struct);
+                    true
+                }
+
                 fn id(&self) -> RuntimeID {
                     self.runtime_id
                 }
@@ -325,6 +552,26 @@ pub fn create_component(out_dir: &Path, component: &ComponentDeclaration) -> any
     write_file(&rs_path, gen_module)
 }
 
+/// Generates the handler structs of `names` which have not already been declared.
+pub(crate) fn gen_handler_structs<'a>(
+    names: impl Iterator<Item = &'a Name>,
+    declared: &mut HashSet<String>,
+) -> TokenStream {
+    names
+        .filter(|name| declared.insert(name.to_string()))
+        .map(|name| {
+            let name = format_ident!("{}", name.as_str());
+            quote! {
+                pub(crate) struct #name;
+
+                impl ToHandler for #name {
+                    type BaseHandler = CompStruct;
+                }
+            }
+        })
+        .collect()
+}
+
 #[cfg(not(feature = "pretty"))]
 fn write_file(path: &Path, stream: TokenStream) -> anyhow::Result<()> {
     fs::write(path, format!("{}", stream))?;
@@ -348,15 +595,23 @@ fn create_bundle(
     fluents: &[FluentIdent],
 ) -> anyhow::Result<()> {
     let ftl_path = out_dir.join(format!("{component_name}.ftl"));
+    fs::write(ftl_path, bundle_string(fluents))?;
+    Ok(())
+}
+
+/// Creates the fluent resource holding each message in `fluents`.
+pub fn bundle_string(fluents: &[FluentIdent]) -> String {
     let mut bundle = String::new();
     for fluent in fluents {
         bundle = bundle + &format!("{} = {}\n", fluent.name, fluent.fluent.text);
     }
-    fs::write(ftl_path, bundle)?;
-    Ok(())
+    bundle
 }
 
-fn create_state(states: &[StateDeclaration]) -> anyhow::Result<Option<TokenStream>> {
+fn create_state(
+    states: &[StateDeclaration],
+    hot_reload: bool,
+) -> anyhow::Result<Option<TokenStream>> {
     if states.is_empty() {
         return Ok(None);
     }
@@ -366,7 +621,24 @@ fn create_state(states: &[StateDeclaration]) -> anyhow::Result<Option<TokenStrea
         bail!("Cannot have a singular state but found state {name}.");
     }
 
-    let names = states.iter().map(|s| format_ident!("{}", s.name.as_str()));
+    let names = states
+        .iter()
+        .map(|s| format_ident!("{}", s.name.as_str()))
+        .collect_vec();
+
+    // Hot reloaded statics are stored using the name of the state they are applied in.
+    let state_names = hot_reload.then(|| {
+        let name_strings = states.iter().map(|s| s.name.as_str());
+        quote! {
+            impl State {
+                pub(crate) fn name(&self) -> &'static str {
+                    match self {
+                        #(State::#names => #name_strings),*
+                    }
+                }
+            }
+        }
+    });
 
     Ok(Some(quote! {
         #[allow(non_camel_case_types)]
@@ -381,5 +653,7 @@ fn create_state(states: &[StateDeclaration]) -> anyhow::Result<Option<TokenStrea
         impl Variable for state {
             type VarType = State;
         }
+
+        #state_names
     }))
 }
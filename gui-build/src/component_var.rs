@@ -19,8 +19,14 @@ struct ComponentVar {
 
 impl ComponentVar {
     pub fn new(comp: &ComponentVariableDeclaration, id: WidgetID) -> Self {
-        let comp_name_ident = format_ident!("{}", *comp.component);
-        let type_stream = quote!(<crate::__gui_private::#comp_name_ident as ComponentTypeInfo>);
+        let comp_name_ident = format_ident!("{}", *comp.component.name);
+        let type_stream = match &comp.component.crate_name {
+            None => quote!(<crate::__gui_private::#comp_name_ident as ComponentTypeInfo>),
+            Some(crate_name) => {
+                let crate_ident = format_ident!("{}", **crate_name);
+                quote!(<::#crate_ident::__gui_private::#comp_name_ident as ComponentTypeInfo>)
+            }
+        };
         let holder_ident = format_ident!("{}_holder", *comp.name);
         let name_ident = format_ident!("{}", *comp.name);
         Self {
@@ -88,6 +94,13 @@ impl ComponentVars {
         let get_parent = self.gen_try_all_options(quote!(get_parent(runtime_id, widget_id)));
         let get_id = self.gen_try_all_options(quote!(get_id(name)));
         let get_parent_runtime = self.gen_get_parent_runtime();
+        let shortcut = self.gen_shortcut();
+        let context_menu = self.gen_try_all_options(quote!(context_menu(runtime_id, widget_id)));
+        let tooltip = self.gen_try_all_options(quote!(tooltip(runtime_id, widget_id)));
+        let menu_command =
+            self.gen_for_each_comp(quote!(menu_command(runtime_id, id)), quote!(false));
+        let with_comp_struct =
+            self.gen_for_each_comp(quote!(with_comp_struct(runtime_id, f)), quote!(false));
 
         quote! {
             pub struct MultiComponentHolder {
@@ -174,6 +187,29 @@ impl ComponentVars {
                 fn get_id(&self, name: &str) -> Option<(RuntimeID, WidgetID)> {
                     #get_id
                 }
+                fn shortcut(&mut self, event: &KeyEvent) -> bool {
+                    #shortcut
+                }
+                fn context_menu(
+                    &self,
+                    runtime_id: RuntimeID,
+                    widget_id: WidgetID,
+                ) -> Option<::gui::gui_core::menu::Menu> {
+                    #context_menu
+                }
+                fn tooltip(&self, runtime_id: RuntimeID, widget_id: WidgetID) -> Option<String> {
+                    #tooltip
+                }
+                fn menu_command(&mut self, runtime_id: RuntimeID, id: u32) -> bool {
+                    #menu_command
+                }
+                fn with_comp_struct(
+                    &mut self,
+                    runtime_id: RuntimeID,
+                    f: &mut dyn FnMut(&mut dyn Any),
+                ) -> bool {
+                    #with_comp_struct
+                }
             }
         }
     }
@@ -234,6 +270,19 @@ impl ComponentVars {
         }
     }
 
+    /// Stops at the first component that runs a shortcut, so only one handler runs.
+    fn gen_shortcut(&self) -> TokenStream {
+        let component_idents = self.0.iter().map(|c| &c.holder_ident);
+        quote! {
+            #(
+                if self.#component_idents.shortcut(event) {
+                    return true;
+                }
+            )*
+            false
+        }
+    }
+
     fn gen_for_each_comp(&self, stream: TokenStream, default: TokenStream) -> TokenStream {
         if self.0.is_empty() {
             return default;
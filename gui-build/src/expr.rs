@@ -0,0 +1,189 @@
+use anyhow::{anyhow, bail, Context};
+use gui_core::parse::NormalVariableDeclaration;
+use itertools::Itertools;
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use quote::quote;
+use std::str::FromStr;
+
+/// A variable whose value is calculated from an expression using other variables.
+struct ExprVariable<'a> {
+    declaration: &'a NormalVariableDeclaration,
+    expr: TokenStream,
+    /// The variables used by the expression, in the order they are declared.
+    dependencies: Vec<&'a NormalVariableDeclaration>,
+}
+
+impl<'a> ExprVariable<'a> {
+    fn new(
+        declaration: &'a NormalVariableDeclaration,
+        expr: &str,
+        variables: &[&'a NormalVariableDeclaration],
+    ) -> anyhow::Result<Self> {
+        let expr = TokenStream::from_str(expr)
+            .map_err(|e| anyhow!("{e}"))
+            .with_context(|| format!("Failed to parse expression of {}", declaration.name))?;
+        let mut idents = vec![];
+        push_idents(expr.clone(), &mut idents);
+        let dependencies = variables
+            .iter()
+            .filter(|v| idents.iter().any(|i| *i == v.name.as_str()))
+            .copied()
+            .collect_vec();
+        Ok(Self {
+            declaration,
+            expr,
+            dependencies,
+        })
+    }
+
+    fn depends_on(&self, name: &str) -> bool {
+        self.dependencies.iter().any(|d| d.name.as_str() == name)
+    }
+}
+
+fn push_idents(stream: TokenStream, idents: &mut Vec<Ident>) {
+    for tree in stream {
+        match tree {
+            TokenTree::Ident(ident) => idents.push(ident),
+            TokenTree::Group(group) => push_idents(group.stream(), idents),
+            TokenTree::Punct(_) | TokenTree::Literal(_) => {}
+        }
+    }
+}
+
+/// Generates the `Update` implementations of the variables declared with an `expr`. A variable is
+/// updated when any of the variables used by its expression are updated.
+pub fn gen_expr_updates(variables: &[&NormalVariableDeclaration]) -> anyhow::Result<TokenStream> {
+    let exprs = variables
+        .iter()
+        .filter_map(|v| Some(ExprVariable::new(v, v.expr.as_deref()?, variables)))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    if exprs.is_empty() {
+        return Ok(TokenStream::new());
+    }
+    check_cycles(&exprs)?;
+
+    let (functions, impls): (Vec<_>, Vec<_>) = exprs
+        .iter()
+        .map(|e| {
+            let name = Ident::new(&e.declaration.name, Span::call_site());
+            let expr = &e.expr;
+            let args = e
+                .dependencies
+                .iter()
+                .map(|d| Ident::new(&d.name, Span::call_site()))
+                .collect_vec();
+            // The expression is in its own module, as a variable would otherwise be matched
+            // against the struct of the same name.
+            let function = quote! {
+                pub(super) fn #name(#(#args: <super::#args as Variable>::VarType),*) -> <super::#name as Variable>::VarType {
+                    #expr
+                }
+            };
+            let is_updated = if args.is_empty() {
+                quote!(false)
+            } else {
+                quote!(#(<CompStruct as Update<#args>>::is_updated(self))||*)
+            };
+            let update = quote! {
+                #[automatically_derived]
+                impl Update<#name> for CompStruct {
+                    fn is_updated(&self) -> bool {
+                        #is_updated
+                    }
+                    fn value(&self) -> <#name as Variable>::VarType {
+                        __expr::#name(#(<CompStruct as Update<#args>>::value(self)),*)
+                    }
+                }
+            };
+            (function, update)
+        })
+        .unzip();
+
+    Ok(quote! {
+        #[allow(non_snake_case)]
+        mod __expr {
+            use super::Variable;
+            #(#functions)*
+        }
+
+        #(#impls)*
+    })
+}
+
+/// Expressions cannot depend on themselves, which would recurse forever when getting the value.
+fn check_cycles(exprs: &[ExprVariable]) -> anyhow::Result<()> {
+    fn visit<'a>(
+        expr: &'a ExprVariable,
+        exprs: &'a [ExprVariable],
+        path: &mut Vec<&'a str>,
+    ) -> anyhow::Result<()> {
+        let name = expr.declaration.name.as_str();
+        if path.contains(&name) {
+            bail!(
+                "Expression of {name} depends on itself through {}",
+                path.iter().format(" -> ")
+            );
+        }
+        path.push(name);
+        for dependency in exprs
+            .iter()
+            .filter(|e| expr.depends_on(&e.declaration.name))
+        {
+            visit(dependency, exprs, path)?;
+        }
+        path.pop();
+        Ok(())
+    }
+
+    for expr in exprs {
+        visit(expr, exprs, &mut vec![])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::gen_expr_updates;
+    use gui_core::parse::NormalVariableDeclaration;
+
+    fn variables(yaml: &str) -> Vec<NormalVariableDeclaration> {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn expr_dependencies() {
+        let variables = variables(
+            r#"
+- name: count
+  type: u32
+- name: other
+  type: u32
+- name: disabled_decrement
+  type: bool
+  expr: "count == 0""#,
+        );
+        let variables = variables.iter().collect::<Vec<_>>();
+        let updates = gen_expr_updates(&variables).unwrap().to_string();
+        assert!(updates.contains(
+            "pub (super) fn disabled_decrement (count : < super :: count as Variable > :: VarType)"
+        ));
+        assert!(updates.contains("< CompStruct as Update < count > > :: is_updated (self)"));
+        assert!(!updates.contains("Update < other >"));
+    }
+
+    #[test]
+    fn expr_cycle() {
+        let variables = variables(
+            r#"
+- name: a
+  type: u32
+  expr: b + 1
+- name: b
+  type: u32
+  expr: a + 1"#,
+        );
+        let variables = variables.iter().collect::<Vec<_>>();
+        assert!(gen_expr_updates(&variables).is_err());
+    }
+}
@@ -48,8 +48,15 @@ impl FluentIdent {
     }
 }
 
-pub fn gen_bundle_function(component_name: &str) -> TokenStream {
+pub fn gen_bundle_function(component_name: &str, hot_reload: bool) -> TokenStream {
     let ftl_location = format!("/{component_name}.ftl");
+    let hot_reload_message = hot_reload.then(|| {
+        quote! {
+            if let Some(message) = ::gui::hot_reload::get_message(#component_name, message, args) {
+                return Cow::Owned(message);
+            }
+        }
+    });
     quote! {
         use gui::{FluentBundle, FluentArgs, FluentResource};
         use std::borrow::Cow;
@@ -58,6 +65,8 @@ pub fn gen_bundle_function(component_name: &str) -> TokenStream {
             use std::sync::OnceLock;
             use gui::langid;
 
+            #hot_reload_message
+
             static BUNDLE: OnceLock<FluentBundle<FluentResource>> = OnceLock::new();
             const FTL_STRING: &str = include_str!(concat!(env!("OUT_DIR"), #ftl_location));
             let mut errors = vec![];
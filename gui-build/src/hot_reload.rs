@@ -0,0 +1,376 @@
+use crate::component;
+use crate::tooltip::Tooltips;
+use crate::widget::Widget;
+use anyhow::Context;
+use gui_core::parse::{
+    ComponentDeclaration, GUIDeclaration, MenuItemDeclaration, StateDeclaration,
+};
+use gui_core::widget::WidgetID;
+use itertools::Itertools;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::any::Any;
+use std::env;
+use std::fmt::Write;
+use std::fs::File;
+use std::path::Path;
+
+/// A static value of a widget in a hot reloaded layout file.
+pub struct StaticValue {
+    pub widget_id: u32,
+    /// The state overriding the value or `None` if it is the value used by all other states.
+    pub state: Option<String>,
+    pub property: &'static str,
+    pub value: Box<dyn Any>,
+}
+
+/// The parts of a component that can be re-applied to a running application.
+pub struct ReloadedComponent {
+    pub name: String,
+    /// Summary of everything that requires a recompilation if it is changed.
+    pub structure: String,
+    pub statics: Vec<StaticValue>,
+    /// The fluent resource holding all the messages used by the component.
+    pub ftl: String,
+}
+
+/// Whether the crate being built has enabled its `hot-reload` feature.
+pub(crate) fn is_enabled() -> bool {
+    env::var_os("CARGO_FEATURE_HOT_RELOAD").is_some()
+}
+
+/// Parses the layout file at the given `path` at runtime, returning the values of each component
+/// that can be updated without recompiling.
+pub fn reload(path: &Path) -> anyhow::Result<Vec<ReloadedComponent>> {
+    let file = File::open(path).context("Failed to open GUI configuration file")?;
+    let mut ser: GUIDeclaration =
+        serde_yaml::from_reader(file).context("Failed to parse file GUI configuration file")?;
+
+    crate::combine_styles(&mut ser).context("Failed to combine styles")?;
+
+    ser.components
+        .iter()
+        .map(|component| {
+            reload_component(component)
+                .with_context(|| format!("Failed to reload component {}", component.name.as_str()))
+        })
+        .collect()
+}
+
+fn reload_component(component: &ComponentDeclaration) -> anyhow::Result<ReloadedComponent> {
+    let widget_tree = Widget::new(component)?;
+    let mut fluents = vec![];
+    widget_tree.push_fluents(&mut fluents);
+    widget_tree.push_item_fluents(&mut fluents);
+    Tooltips::new(component, &widget_tree)?.push_fluents(&mut fluents);
+
+    Ok(ReloadedComponent {
+        name: component.name.to_string(),
+        structure: structure(component, &widget_tree),
+        statics: widget_tree
+            .iter()
+            .flat_map(|w| static_values(w, &component.states[..]))
+            .collect(),
+        ftl: component::bundle_string(&fluents[..]),
+    })
+}
+
+fn static_values(widget: &Widget, states: &[StateDeclaration]) -> Vec<StaticValue> {
+    let builder = widget.widget_declaration.widget.as_ref();
+    let widget_id = widget.id.id();
+    let mut result = builder
+        .get_static_values()
+        .into_iter()
+        .map(|(property, value)| StaticValue {
+            widget_id,
+            state: None,
+            property,
+            value,
+        })
+        .collect_vec();
+
+    if let Some(name) = &widget.widget_declaration.name {
+        for state in states {
+            for state_override in state.overrides.iter().filter(|o| &o.name == name) {
+                let mut new_widget = state_override.widget.clone();
+                new_widget.combine(builder);
+                result.extend(new_widget.get_static_values().into_iter().map(
+                    |(property, value)| StaticValue {
+                        widget_id,
+                        state: Some(state.name.to_string()),
+                        property,
+                        value,
+                    },
+                ));
+            }
+        }
+    }
+    result
+}
+
+/// The theme tokens used by the statics of a widget in every state.
+fn theme_tokens(widget: &Widget, states: &[StateDeclaration]) -> String {
+    let builder = widget.widget_declaration.widget.as_ref();
+    let mut tokens = builder
+        .get_theme_tokens()
+        .into_iter()
+        .map(|(property, token)| format!("{property}: {token}"))
+        .collect_vec();
+    if let Some(name) = &widget.widget_declaration.name {
+        for state in states {
+            for state_override in state.overrides.iter().filter(|o| &o.name == name) {
+                let mut new_widget = state_override.widget.clone();
+                new_widget.combine(builder);
+                tokens.extend(
+                    new_widget
+                        .get_theme_tokens()
+                        .into_iter()
+                        .map(|(property, token)| format!("{} {property}: {token}", state.name)),
+                );
+            }
+        }
+    }
+    tokens.join(", ")
+}
+
+/// Creates a summary of the parts of a component that generated code depends on, excluding the
+/// values of statics and the text of fluents. Each line describes a different part so that the
+/// first differing line can be used to explain why a change could not be hot reloaded.
+pub(crate) fn structure(component: &ComponentDeclaration, widget_tree: &Widget) -> String {
+    let mut result = String::new();
+    for variable in &component.variables {
+        let _ = writeln!(result, "variable {variable:?}");
+    }
+    let states = component
+        .states
+        .iter()
+        .map(|s| s.name.as_str())
+        .format(", ");
+    let _ = writeln!(result, "states [{states}]");
+    for state in &component.states {
+        let _ = writeln!(
+            result,
+            "state {} transitions {:?}",
+            state.name, state.transitions
+        );
+    }
+    // The window is only configured when it is created.
+    let _ = writeln!(result, "window {:?}", component.window);
+    // Menus are built when opened, so only the text of their items can be reloaded.
+    let menu_bar = component.menu_bar.iter().format(", ");
+    let _ = writeln!(result, "menu bar [{menu_bar}]");
+    for menu in &component.menus {
+        let _ = writeln!(
+            result,
+            "menu {} items {}",
+            menu.name,
+            menu_structure(&menu.items)
+        );
+    }
+    for shortcut in &component.shortcuts {
+        let _ = writeln!(
+            result,
+            "shortcut {} runs {}",
+            shortcut.keys, shortcut.handler
+        );
+    }
+
+    for widget in widget_tree.iter() {
+        let _ = writeln!(
+            result,
+            "widget {} of type {} named {:?} with handler {:?}, action {:?}, context menu {:?}, tooltip {:?}, visible {:?}, components {:?}, variables {:?} and bindings [{}]",
+            widget.id.id(),
+            widget.widget_type_name,
+            widget.widget_declaration.name.as_deref(),
+            widget.handler.as_ref().map(ToString::to_string),
+            widget.widget_declaration.on_press,
+            widget.widget_declaration.context_menu.as_deref(),
+            widget.widget_declaration.tooltip.is_some(),
+            widget.visible,
+            widget.components.0,
+            widget.fallback.variables.0,
+            widget.bindings.iter().map(|(name, _)| name).format(", "),
+        );
+        let statics = widget.fallback.statics.0.iter().map(|s| s.0).format(", ");
+        let fluents = widget
+            .fallback
+            .fluents
+            .0
+            .iter()
+            .chain(&widget.fallback.list_fluents)
+            .map(|f| format!("{}({:?})", f.name, f.fluent.vars))
+            .format(", ");
+        let _ = writeln!(
+            result,
+            "widget {} statics [{statics}] and fluents [{fluents}]",
+            widget.id.id()
+        );
+        let _ = writeln!(
+            result,
+            "widget {} theme tokens [{}]",
+            widget.id.id(),
+            theme_tokens(widget, &component.states[..])
+        );
+        // Statics of templates are not hot reloaded, so the whole template is part of the
+        // structure.
+        if let Some(for_each) = &widget.for_each {
+            let _ = writeln!(
+                result,
+                "widget {} for each {:?}",
+                widget.id.id(),
+                for_each.declaration
+            );
+        }
+
+        for state in &widget.state_overrides {
+            let shared = &widget.shared_overrides;
+            let statics = shared
+                .statics
+                .0
+                .iter()
+                .chain(&state.statics.0)
+                .map(|s| s.0)
+                .sorted()
+                .format(", ");
+            let fluents = shared
+                .fluents
+                .0
+                .iter()
+                .chain(&state.fluents.0)
+                .chain(&state.state_fluent_overrides.0)
+                .chain(&state.state_list_fluents)
+                .map(|f| format!("{}({:?})", f.name, f.fluent.vars))
+                .sorted()
+                .format(", ");
+            let variables = shared
+                .variables
+                .0
+                .iter()
+                .chain(&state.variables.0)
+                .sorted_by_key(|v| v.0)
+                .format(", ");
+            let _ = writeln!(
+                result,
+                "widget {} in state {} statics [{statics}], fluents [{fluents}] and variables [{variables:?}]",
+                widget.id.id(),
+                state.state_name,
+            );
+        }
+    }
+
+    let mut parent_ids = vec![];
+    widget_tree.get_parent_ids(&mut parent_ids);
+    for (parent, children) in parent_ids {
+        let children = children.iter().map(WidgetID::id).format(", ");
+        let _ = writeln!(result, "widget {} children [{children}]", parent.id());
+    }
+    result
+}
+
+/// The handlers and submenus of menu items.
+fn menu_structure(items: &[MenuItemDeclaration]) -> String {
+    let items = items.iter().map(|item| match item {
+        MenuItemDeclaration::Item { handler, .. } => handler.to_string(),
+        MenuItemDeclaration::Submenu { items, .. } => menu_structure(items),
+        MenuItemDeclaration::Separator => "separator".to_string(),
+    });
+    format!("[{}]", items.format(", "))
+}
+
+/// Information needed by generated code to look up the hot reloaded value of a static.
+pub(crate) struct StaticLookup<'a> {
+    pub component: &'a str,
+    pub id: WidgetID,
+    /// Whether the static is only applied in certain states.
+    pub in_state: bool,
+}
+
+impl StaticLookup<'_> {
+    pub fn gen_value(&self, property: &str, value: &TokenStream) -> TokenStream {
+        let component = self.component;
+        let id = self.id.id();
+        let state = if self.in_state {
+            quote!(Some(self.state.name()))
+        } else {
+            quote!(None)
+        };
+        quote!(::gui::hot_reload::static_value(#component, #id, #state, #property, #value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::structure;
+    use crate::widget::Widget;
+    use gui_core::parse::GUIDeclaration;
+
+    fn get_structure(yaml: &str) -> String {
+        let decl: GUIDeclaration = serde_yaml::from_str(yaml).unwrap();
+        let component = &decl.components[0];
+        structure(component, &Widget::new(component).unwrap())
+    }
+
+    #[test]
+    fn structure_ignores_values() {
+        let original = get_structure(
+            r#"
+components:
+  - name: Counter
+    variables:
+      - name: count
+        type: u32
+    child:
+      widget: Text
+      properties:
+        text: "Count: { $count }"
+        size: 12"#,
+        );
+        let changed_values = get_structure(
+            r#"
+components:
+  - name: Counter
+    variables:
+      - name: count
+        type: u32
+    child:
+      widget: Text
+      properties:
+        text: "Total: { $count }"
+        size: 20"#,
+        );
+        let changed_structure = get_structure(
+            r#"
+components:
+  - name: Counter
+    variables:
+      - name: count
+        type: u32
+    child:
+      widget: Text
+      properties:
+        text: "Total"
+        size:
+          variable: count"#,
+        );
+        assert_eq!(original, changed_values);
+        assert_ne!(original, changed_structure);
+    }
+
+    #[test]
+    fn structure_includes_theme_tokens() {
+        let text = |size: &str| {
+            get_structure(&format!(
+                r#"
+components:
+  - name: Heading
+    child:
+      widget: Text
+      properties:
+        text: "Title"
+        size: {size}"#
+            ))
+        };
+        assert_ne!(text("12"), text("{theme: heading}"));
+        assert_ne!(text("{theme: heading}"), text("{theme: large}"));
+    }
+}
@@ -1,11 +1,19 @@
+mod animation;
 mod component;
 mod component_var;
+mod expr;
 mod fluent;
+pub mod hot_reload;
+mod menu;
+mod shortcut;
 mod tokenstream;
+mod tooltip;
 mod widget;
+mod window;
 
 use anyhow::{anyhow, bail, Context};
-use gui_core::parse::{GUIDeclaration, WidgetDeclaration};
+use gui_core::parse::var::Name;
+use gui_core::parse::{GUIDeclaration, StyleDeclaration, WidgetDeclaration};
 use gui_core::widget::{AsAny, WidgetBuilder};
 use itertools::Itertools;
 use std::any::TypeId;
@@ -19,6 +27,10 @@ extern crate gui_widget;
 /// Entry point of the `gui-build` crate. Use this to compile the layout file at the given `path`
 /// into code. This function must be run in a `build.rs` file. To provide better error diagnostics
 /// this function will exit if an error is encountered.
+///
+/// If the crate being built has a `hot-reload` feature enabled (which should also enable the
+/// `hot-reload` feature of `gui`), the generated code will pick up changes made to the layout file
+/// while the application is running.
 pub fn build<P: AsRef<Path>>(path: P) {
     if let Err(e) = build_path(path.as_ref()) {
         println!("{e:#}");
@@ -36,8 +48,13 @@ fn build_path(path: &Path) -> anyhow::Result<()> {
 
     combine_styles(&mut ser).context("Failed to combine styles")?;
 
+    let layout_path = hot_reload::is_enabled()
+        .then(|| path.canonicalize())
+        .transpose()
+        .context("Failed to find the GUI configuration file to hot reload")?;
+
     for component in ser.components.iter_mut() {
-        component::create_component(out_dir, component)
+        component::create_component(out_dir, component, layout_path.as_deref())
             .with_context(|| format!("Failed to create component {}", component.name.as_str()))?;
     }
 
@@ -48,44 +65,113 @@ fn build_path(path: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Applies styles to each widget. Properties are taken, in order of precedence, from the widget
+/// itself, then its style classes (later classes taking precedence) and finally the unnamed style
+/// for the widget's type. State overrides are combined with the styled widget later on.
 fn combine_styles(static_gui: &mut GUIDeclaration) -> anyhow::Result<()> {
-    let mut styles: HashMap<TypeId, usize> = HashMap::new();
-
-    for (t, i) in static_gui
-        .styles
-        .iter()
-        .enumerate()
-        .map(|(i, s)| (s.as_any().type_id(), i))
-    {
-        if styles.insert(t, i).is_some() {
-            bail!(
-                "Found multiple styles for widget {}",
-                static_gui.styles[i].name()
-            )
+    let mut styles: HashMap<TypeId, &dyn WidgetBuilder> = HashMap::new();
+    let mut classes: HashMap<&Name, &StyleDeclaration> = HashMap::new();
+
+    for style in &static_gui.styles {
+        match &style.name {
+            None => {
+                let t = style.widget.as_ref().as_any().type_id();
+                if styles.insert(t, style.widget.as_ref()).is_some() {
+                    bail!("Found multiple styles for widget {}", style.widget.name())
+                }
+            }
+            Some(name) => {
+                if classes.insert(name, style).is_some() {
+                    bail!("Found multiple style classes named {name}")
+                }
+            }
         }
     }
 
+    let mut resolved_classes = HashMap::new();
+    for &name in classes.keys() {
+        resolve_class(name, &classes, &mut resolved_classes, &mut vec![])?;
+    }
+
     for c in &mut static_gui.components {
-        combine_style(&mut c.child, &styles, &static_gui.styles[..])
+        combine_style(&mut c.child, &styles, &resolved_classes)?;
+    }
+
+    Ok(())
+}
+
+/// Combines a style class with the classes it extends.
+fn resolve_class<'a>(
+    name: &'a Name,
+    classes: &HashMap<&'a Name, &'a StyleDeclaration>,
+    resolved_classes: &mut HashMap<&'a Name, Box<dyn WidgetBuilder>>,
+    visiting: &mut Vec<&'a Name>,
+) -> anyhow::Result<()> {
+    if resolved_classes.contains_key(name) {
+        return Ok(());
+    }
+    if visiting.contains(&name) {
+        bail!(
+            "Style class {name} extends itself: {} -> {name}",
+            visiting.iter().format(" -> ")
+        )
+    }
+    let class = classes
+        .get(name)
+        .ok_or_else(|| anyhow!("Could not find style class {name}"))?;
+
+    visiting.push(name);
+    let mut widget = class.widget.clone();
+    for parent in class.extends.iter().rev() {
+        resolve_class(parent, classes, resolved_classes, visiting)?;
+        let parent_widget = resolved_classes[parent].as_ref();
+        if parent_widget.as_any().type_id() != widget.as_ref().as_any().type_id() {
+            bail!(
+                "Style class {name} for widget {} cannot extend style class {parent} for widget {}",
+                widget.name(),
+                parent_widget.name()
+            )
+        }
+        widget.combine(parent_widget);
     }
+    visiting.pop();
 
+    resolved_classes.insert(name, widget);
     Ok(())
 }
 
 fn combine_style(
     widget: &mut WidgetDeclaration,
-    style_map: &HashMap<TypeId, usize>,
-    styles: &[Box<dyn WidgetBuilder>],
-) {
-    if let Some(i) = style_map.get(&(widget.widget.as_any().type_id())) {
-        widget.widget.combine(styles[*i].as_ref())
+    styles: &HashMap<TypeId, &dyn WidgetBuilder>,
+    classes: &HashMap<&Name, Box<dyn WidgetBuilder>>,
+) -> anyhow::Result<()> {
+    let widget_type = widget.widget.as_ref().as_any().type_id();
+
+    for class_name in widget.style.iter().rev() {
+        let class = classes
+            .get(class_name)
+            .ok_or_else(|| anyhow!("Could not find style class {class_name}"))?;
+        if class.as_ref().as_any().type_id() != widget_type {
+            bail!(
+                "Style class {class_name} is for widget {} but was used on widget {}",
+                class.name(),
+                widget.widget.name()
+            )
+        }
+        widget.widget.combine(class.as_ref());
+    }
+
+    if let Some(style) = styles.get(&widget_type) {
+        widget.widget.combine(*style)
     }
 
     for mut widgets in widget.widget.get_widgets().into_iter().flatten() {
         for child in widgets.iter_mut() {
-            combine_style(child, style_map, styles);
+            combine_style(child, styles, classes)?;
         }
     }
+
+    Ok(())
 }
 
 fn add_info_to_env(static_gui: &GUIDeclaration) {
@@ -96,7 +182,9 @@ fn add_info_to_env(static_gui: &GUIDeclaration) {
         let normal_variables = component
             .variables
             .iter()
-            .filter_map(|v| v.get_normal().map(|n| n.name.as_str()))
+            .filter_map(|v| v.get_normal())
+            .filter(|n| n.expr.is_none())
+            .map(|n| n.name.as_str())
             .chain(state_name)
             .format(",");
         let component_variables = component
@@ -104,6 +192,9 @@ fn add_info_to_env(static_gui: &GUIDeclaration) {
             .iter()
             .filter_map(|v| v.get_component().map(|c| c.name.as_str()))
             .format(",");
+        let mut bindings = vec![];
+        push_bindings(&component.child, &mut bindings);
+        let bindings = bindings.iter().unique().format(",");
         println!(
             "cargo:rustc-env=GUI_COMPONENT_{}_VAR={normal_variables}",
             component.name
@@ -112,5 +203,161 @@ fn add_info_to_env(static_gui: &GUIDeclaration) {
             "cargo:rustc-env=GUI_COMPONENT_{}_COMPONENT={component_variables}",
             component.name
         );
+        println!(
+            "cargo:rustc-env=GUI_COMPONENT_{}_BIND={bindings}",
+            component.name
+        );
+    }
+}
+
+/// Pushes the variables bound to the properties of `widget` and its children.
+fn push_bindings(widget: &WidgetDeclaration, acc: &mut Vec<Name>) {
+    acc.extend(
+        widget
+            .widget
+            .get_bindings()
+            .into_iter()
+            .map(|(_, name, _)| name),
+    );
+    for (_, children) in widget.widget.widgets().into_iter().flatten() {
+        for child in children.iter() {
+            push_bindings(child, acc);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::combine_styles;
+    use gui_core::parse::GUIDeclaration;
+    use quote::ToTokens;
+
+    fn get_sizes(yaml: &str) -> anyhow::Result<Vec<String>> {
+        let mut decl: GUIDeclaration = serde_yaml::from_str(yaml).unwrap();
+        combine_styles(&mut decl)?;
+        Ok(decl
+            .components
+            .iter()
+            .map(|c| {
+                c.child
+                    .widget
+                    .get_statics()
+                    .into_iter()
+                    .find(|(p, _)| *p == "size")
+                    .unwrap()
+                    .1
+                    .to_string()
+            })
+            .collect())
+    }
+
+    fn size(value: f32) -> String {
+        value.to_token_stream().to_string()
+    }
+
+    #[test]
+    fn style_class_precedence() {
+        let sizes = get_sizes(
+            r##"
+styles:
+  - widget: Text
+    properties:
+      size: 10
+  - name: large
+    widget: Text
+    properties:
+      size: 20
+  - name: huge
+    extends: large
+    widget: Text
+    properties:
+      colour: "#FF0000"
+  - name: small
+    widget: Text
+    properties:
+      size: 5
+components:
+  - name: Default
+    child:
+      widget: Text
+  - name: Extended
+    child:
+      widget: Text
+      style: huge
+  - name: Local
+    child:
+      widget: Text
+      style: large
+      properties:
+        size: 30
+  - name: Multiple
+    child:
+      widget: Text
+      style: [large, small]"##,
+        )
+        .unwrap();
+        assert_eq!(sizes, vec![size(10.0), size(20.0), size(30.0), size(5.0)]);
+    }
+
+    #[test]
+    fn style_class_errors() {
+        let recursive = get_sizes(
+            r#"
+styles:
+  - name: a
+    extends: b
+    widget: Text
+  - name: b
+    extends: a
+    widget: Text
+components:
+  - name: Recursive
+    child:
+      widget: Text
+      style: a"#,
+        );
+        assert!(recursive.is_err());
+
+        let wrong_widget = get_sizes(
+            r#"
+styles:
+  - name: a
+    widget: Button
+components:
+  - name: WrongWidget
+    child:
+      widget: Text
+      style: a"#,
+        );
+        assert!(wrong_widget.is_err());
+
+        let missing = get_sizes(
+            r#"
+components:
+  - name: Missing
+    child:
+      widget: Text
+      style: a"#,
+        );
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn theme_statics() {
+        let sizes = get_sizes(
+            r#"
+components:
+  - name: Themed
+    child:
+      widget: Text
+      properties:
+        size:
+          theme: heading"#,
+        )
+        .unwrap();
+        assert_eq!(
+            sizes,
+            vec![quote::quote!(::gui::gui_core::theme::get_or_default("heading")).to_string()]
+        );
     }
 }
@@ -0,0 +1,357 @@
+use crate::fluent::FluentIdent;
+use crate::widget::Widget;
+use anyhow::{bail, Context};
+use gui_core::parse::fluent::Fluent;
+use gui_core::parse::var::Name;
+use gui_core::parse::{ComponentDeclaration, MenuDeclaration, MenuItemDeclaration};
+use itertools::Itertools;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+/// The menus declared by a component, used in the menu bar of the window or as the context menus
+/// of its widgets.
+pub struct Menus<'a> {
+    component: &'a ComponentDeclaration,
+    /// The handlers of the menu items, where the index of a handler is its id.
+    handlers: Vec<&'a Name>,
+    /// The widgets with a context menu, and the name of the menu.
+    context_menus: Vec<(u32, &'a Name)>,
+}
+
+impl<'a> Menus<'a> {
+    pub fn new(component: &'a ComponentDeclaration, widget_tree: &Widget) -> anyhow::Result<Self> {
+        let menus = &component.menus;
+        if let Some(menu) = menus.iter().duplicates_by(|m| &m.name).next() {
+            bail!("Menu {} is declared multiple times", menu.name);
+        }
+        let find_menu = |name: &Name| menus.iter().find(|m| &m.name == name);
+
+        for name in &component.menu_bar {
+            match find_menu(name) {
+                None => bail!("Could not find menu {name} of the menu bar"),
+                Some(menu) if menu.text.is_none() => {
+                    bail!("Menu {name} needs a text to be shown in the menu bar")
+                }
+                Some(_) => {}
+            }
+        }
+
+        let mut context_menus = vec![];
+        push_context_menus(widget_tree, &mut context_menus);
+        if let Some((_, name)) = context_menus
+            .iter()
+            .find(|(_, name)| find_menu(name).is_none())
+        {
+            bail!("Could not find context menu {name}");
+        }
+
+        let mut handlers = vec![];
+        for menu in menus {
+            check_text(menu.text.as_ref())
+                .and_then(|_| push_handlers(&menu.items, &mut handlers))
+                .with_context(|| format!("Invalid menu {}", menu.name))?;
+        }
+
+        Ok(Self {
+            component,
+            handlers: handlers.into_iter().unique().collect(),
+            context_menus,
+        })
+    }
+
+    pub fn handlers(&self) -> impl Iterator<Item = &'a Name> + '_ {
+        self.handlers.iter().copied()
+    }
+
+    /// Pushes the text of the menus, which is translated like the fluent properties of widgets.
+    pub fn push_fluents(&self, container: &mut Vec<FluentIdent>) {
+        for menu in &self.component.menus {
+            if let Some(text) = &menu.text {
+                container.push(self.fluent_ident(menu, None, text));
+            }
+            let mut index = 0;
+            self.push_item_fluents(menu, &menu.items, &mut index, container);
+        }
+    }
+
+    fn push_item_fluents(
+        &self,
+        menu: &MenuDeclaration,
+        items: &[MenuItemDeclaration],
+        index: &mut usize,
+        container: &mut Vec<FluentIdent>,
+    ) {
+        for item in items {
+            match item {
+                MenuItemDeclaration::Item { text, .. } => {
+                    container.push(self.fluent_ident(menu, Some(*index), text));
+                    *index += 1;
+                }
+                MenuItemDeclaration::Submenu { text, items } => {
+                    container.push(self.fluent_ident(menu, Some(*index), text));
+                    *index += 1;
+                    self.push_item_fluents(menu, items, index, container);
+                }
+                MenuItemDeclaration::Separator => {}
+            }
+        }
+    }
+
+    fn fluent_ident(
+        &self,
+        menu: &MenuDeclaration,
+        index: Option<usize>,
+        text: &Fluent,
+    ) -> FluentIdent {
+        let component = self.component.name.as_str();
+        let name = match index {
+            None => format!("{component}-menu-{}", menu.name),
+            Some(index) => format!("{component}-menu-{}-{index}", menu.name),
+        };
+        FluentIdent {
+            property: "text",
+            ident: format_ident!("{}", name.replace('-', "_")),
+            name,
+            fluent: text.clone(),
+            property_ident: format_ident!("text"),
+        }
+    }
+
+    /// Generates a function for each menu which builds it for the component with `runtime_id`.
+    pub fn gen_menu_functions(&self) -> TokenStream {
+        self.component
+            .menus
+            .iter()
+            .map(|menu| {
+                let function = format_ident!("menu_{}", menu.name.as_str());
+                let mut index = 0;
+                let menu_stream = self.gen_menu(menu, &menu.items, &mut index);
+                quote! {
+                    #[allow(non_snake_case, unused_variables)]
+                    fn #function(runtime_id: RuntimeID) -> ::gui::gui_core::menu::Menu {
+                        #menu_stream
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn gen_menu(
+        &self,
+        menu: &MenuDeclaration,
+        items: &[MenuItemDeclaration],
+        index: &mut usize,
+    ) -> TokenStream {
+        let mut item_streams = vec![];
+        for item in items {
+            let text_name = format!("{}-menu-{}-{index}", self.component.name, menu.name);
+            let text = quote!(get_bundle_message(#text_name, None).into_owned());
+            item_streams.push(match item {
+                MenuItemDeclaration::Item { handler, .. } => {
+                    let id = self.handler_id(handler);
+                    *index += 1;
+                    quote! {
+                        ::gui::gui_core::menu::MenuItem::Item { runtime_id, id: #id, text: #text }
+                    }
+                }
+                MenuItemDeclaration::Submenu { items, .. } => {
+                    *index += 1;
+                    let submenu = self.gen_menu(menu, items, index);
+                    quote! {
+                        ::gui::gui_core::menu::MenuItem::Submenu { text: #text, menu: #submenu }
+                    }
+                }
+                MenuItemDeclaration::Separator => {
+                    quote!(::gui::gui_core::menu::MenuItem::Separator)
+                }
+            });
+        }
+        quote! {
+            ::gui::gui_core::menu::Menu {
+                items: vec![#(#item_streams),*],
+            }
+        }
+    }
+
+    fn handler_id(&self, handler: &Name) -> u32 {
+        self.handlers
+            .iter()
+            .position(|h| *h == handler)
+            .expect("handlers of all items are stored") as u32
+    }
+
+    /// Generates the body of `Component::menu_bar`.
+    pub fn gen_menu_bar(&self) -> TokenStream {
+        if self.component.menu_bar.is_empty() {
+            return quote!(None);
+        }
+        let component = self.component.name.as_str();
+        let menus = self.component.menu_bar.iter().map(|name| {
+            let text_name = format!("{component}-menu-{name}");
+            let function = format_ident!("menu_{}", name.as_str());
+            quote! {
+                ::gui::gui_core::menu::MenuItem::Submenu {
+                    text: get_bundle_message(#text_name, None).into_owned(),
+                    menu: #function(self.runtime_id),
+                }
+            }
+        });
+        quote! {
+            Some(::gui::gui_core::menu::Menu {
+                items: vec![#(#menus),*],
+            })
+        }
+    }
+
+    /// Generates the match arms of `Component::context_menu` for the widgets of the component.
+    pub fn gen_context_menu_arms(&self) -> TokenStream {
+        self.context_menus
+            .iter()
+            .map(|(id, name)| {
+                let function = format_ident!("menu_{}", name.as_str());
+                quote!(#id => Some(#function(self.runtime_id)),)
+            })
+            .collect()
+    }
+
+    /// Generates the match arms of `Component::menu_command` which run the handlers.
+    pub fn gen_menu_command_arms(&self) -> TokenStream {
+        self.handlers
+            .iter()
+            .enumerate()
+            .map(|(id, handler)| {
+                let id = id as u32;
+                let handler = format_ident!("{}", handler.as_str());
+                quote! {
+                    #id => <CompStruct as ::gui::gui_core::MenuHandler<#handler>>::on_select(&mut self.comp_struct),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Pushes the widgets with a context menu, including the widgets created for each item of a list.
+fn push_context_menus<'a>(widget_tree: &Widget<'a>, context_menus: &mut Vec<(u32, &'a Name)>) {
+    for widget in widget_tree.iter() {
+        if let Some(name) = &widget.widget_declaration.context_menu {
+            context_menus.push((widget.id.id(), name));
+        }
+        if let Some(for_each) = &widget.for_each {
+            push_context_menus(&for_each.template, context_menus);
+        }
+    }
+}
+
+/// Menus are built when they are opened, so their text cannot use variables.
+fn check_text(text: Option<&Fluent>) -> anyhow::Result<()> {
+    match text {
+        Some(text) if !text.vars.is_empty() => {
+            bail!("Menu text {} cannot use variables", text.text.trim())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn push_handlers<'a>(
+    items: &'a [MenuItemDeclaration],
+    handlers: &mut Vec<&'a Name>,
+) -> anyhow::Result<()> {
+    for item in items {
+        match item {
+            MenuItemDeclaration::Item { handler, text } => {
+                check_text(Some(text))?;
+                handlers.push(handler);
+            }
+            MenuItemDeclaration::Submenu { text, items } => {
+                check_text(Some(text))?;
+                push_handlers(items, handlers)?;
+            }
+            MenuItemDeclaration::Separator => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::menu::Menus;
+    use crate::widget::Widget;
+    use gui_core::parse::ComponentDeclaration;
+
+    fn component(menu_bar: &str, context_menu: &str, text: &str) -> ComponentDeclaration {
+        serde_yaml::from_str(&format!(
+            r#"
+name: Editor
+menuBar: [{menu_bar}]
+menus:
+  - name: File
+    text: File
+    items:
+      - handler: Open
+        text: {text}
+      - separator
+      - text: Recent
+        items:
+          - handler: Open
+            text: Last file
+  - name: Edit
+    items:
+      - handler: Copy
+        text: Copy
+child:
+  name: Title
+  widget: Text
+  contextMenu: {context_menu}
+  properties:
+    text: Untitled"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_menus() {
+        let declaration = component("File", "Edit", "Open");
+        let widget = Widget::new(&declaration).unwrap();
+        let menus = Menus::new(&declaration, &widget).unwrap();
+        assert_eq!(
+            menus.handlers().map(|h| h.as_str()).collect::<Vec<_>>(),
+            ["Open", "Copy"]
+        );
+
+        let mut fluents = vec![];
+        menus.push_fluents(&mut fluents);
+        let names = fluents.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "Editor-menu-File",
+                "Editor-menu-File-0",
+                "Editor-menu-File-1",
+                "Editor-menu-File-2",
+                "Editor-menu-Edit-0"
+            ]
+        );
+
+        // Both items of the file menu run the Open handler.
+        let functions = menus.gen_menu_functions().to_string();
+        assert_eq!(functions.matches("id : 0u32").count(), 2);
+        assert!(functions.contains("\"Editor-menu-File-2\""));
+        assert!(menus
+            .gen_menu_command_arms()
+            .to_string()
+            .contains("MenuHandler < Copy >"));
+        assert!(menus
+            .gen_context_menu_arms()
+            .to_string()
+            .contains("Some (menu_Edit (self . runtime_id))"));
+
+        // Only menus with a text can be in the menu bar.
+        let declaration = component("Edit", "Edit", "Open");
+        assert!(Menus::new(&declaration, &widget).is_err());
+        let declaration = component("File", "Missing", "Open");
+        let widget = Widget::new(&declaration).unwrap();
+        assert!(Menus::new(&declaration, &widget).is_err());
+        let declaration = component("File", "Edit", "Open { $file }");
+        assert!(Menus::new(&declaration, &widget).is_err());
+    }
+}
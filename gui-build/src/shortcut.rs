@@ -0,0 +1,100 @@
+use anyhow::bail;
+use gui_core::parse::var::Name;
+use gui_core::parse::ShortcutDeclaration;
+use itertools::Itertools;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+/// The shortcuts declared by a component, which run a `ShortcutHandler` of the component when
+/// their key chord is pressed.
+pub struct Shortcuts<'a>(&'a [ShortcutDeclaration]);
+
+impl<'a> Shortcuts<'a> {
+    pub fn new(shortcuts: &'a [ShortcutDeclaration]) -> anyhow::Result<Self> {
+        // Chords are compared as they are matched, so `Mod+S` is the same as `Ctrl+S`.
+        if let Some((first, second)) = shortcuts
+            .iter()
+            .tuple_combinations()
+            .find(|(a, b)| a.keys.overlaps(&b.keys))
+        {
+            bail!(
+                "Shortcut {} is declared multiple times, as {} is the same key chord",
+                first.keys,
+                second.keys
+            );
+        }
+        Ok(Self(shortcuts))
+    }
+
+    pub fn handlers(&self) -> impl Iterator<Item = &'a Name> {
+        let shortcuts = self.0;
+        shortcuts.iter().map(|s| &s.handler)
+    }
+
+    /// Generates the body of `Component::shortcut`, which tries the held components first.
+    pub fn gen_shortcut(&self) -> TokenStream {
+        let keys = self.0.iter().map(|s| &s.keys);
+        let handlers = self
+            .0
+            .iter()
+            .map(|s| format_ident!("{}", s.handler.as_str()));
+        quote! {
+            if self.multi_comp.shortcut(event) {
+                return true;
+            }
+            let _running = self.runtime_id.enter();
+            #(
+                if (#keys).matches(event) {
+                    <CompStruct as ::gui::gui_core::ShortcutHandler<#handlers>>::on_shortcut(&mut self.comp_struct);
+                    return true;
+                }
+            )*
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::component::gen_handler_structs;
+    use crate::shortcut::Shortcuts;
+    use crate::widget::Widget;
+    use gui_core::parse::ComponentDeclaration;
+
+    #[test]
+    fn test_shortcuts() {
+        let declaration: ComponentDeclaration = serde_yaml::from_str(
+            r#"
+name: Editor
+shortcuts:
+  - keys: Ctrl+S
+    handler: Save
+  - keys: Mod+Shift+S
+    handler: Save
+  - keys: Ctrl+Z
+    handler: Undo
+child:
+  name: Undo
+  widget: Button"#,
+        )
+        .unwrap();
+        let widget = Widget::new(&declaration).unwrap();
+        let shortcuts = Shortcuts::new(&declaration.shortcuts).unwrap();
+
+        // The button already declares the Undo handler struct.
+        let mut declared = widget.handler_names();
+        let structs = gen_handler_structs(shortcuts.handlers(), &mut declared).to_string();
+        assert_eq!(structs.matches("pub (crate) struct").count(), 1);
+        assert!(structs.contains("struct Save"));
+
+        let shortcut = shortcuts.gen_shortcut().to_string();
+        assert_eq!(shortcut.matches("ShortcutHandler < Save >").count(), 2);
+        assert!(shortcut.contains("ShortcutHandler < Undo >"));
+
+        let mut duplicate = declaration.shortcuts.clone();
+        duplicate[1].keys = "Ctrl+S".parse().unwrap();
+        assert!(Shortcuts::new(&duplicate).is_err());
+        duplicate[1].keys = "Mod+S".parse().unwrap();
+        assert!(Shortcuts::new(&duplicate).is_err());
+    }
+}
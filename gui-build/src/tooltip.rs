@@ -0,0 +1,121 @@
+use crate::fluent::FluentIdent;
+use crate::widget::Widget;
+use anyhow::bail;
+use gui_core::parse::fluent::Fluent;
+use gui_core::parse::ComponentDeclaration;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+/// The tooltips of the widgets of a component, which are shown when the pointer rests over them.
+pub struct Tooltips<'a> {
+    component: &'a ComponentDeclaration,
+    /// The widgets with a tooltip, and the text of the tooltip.
+    tooltips: Vec<(u32, &'a Fluent)>,
+}
+
+impl<'a> Tooltips<'a> {
+    pub fn new(
+        component: &'a ComponentDeclaration,
+        widget_tree: &Widget<'a>,
+    ) -> anyhow::Result<Self> {
+        let mut tooltips = vec![];
+        push_tooltips(widget_tree, &mut tooltips);
+        // Tooltips are formatted when they are shown, so their text cannot use variables.
+        if let Some((_, text)) = tooltips.iter().find(|(_, text)| !text.vars.is_empty()) {
+            bail!("Tooltip {} cannot use variables", text.text.trim());
+        }
+        Ok(Self {
+            component,
+            tooltips,
+        })
+    }
+
+    fn fluent_name(&self, id: u32) -> String {
+        format!("{}-tooltip-{id}", self.component.name)
+    }
+
+    /// Pushes the text of the tooltips, which is translated like the fluent properties of widgets.
+    pub fn push_fluents(&self, container: &mut Vec<FluentIdent>) {
+        for (id, text) in &self.tooltips {
+            let name = self.fluent_name(*id);
+            container.push(FluentIdent {
+                property: "tooltip",
+                ident: format_ident!("{}", name.replace('-', "_")),
+                name,
+                fluent: (*text).clone(),
+                property_ident: format_ident!("tooltip"),
+            });
+        }
+    }
+
+    /// Generates the match arms of `Component::tooltip` for the widgets of the component.
+    pub fn gen_tooltip_arms(&self) -> TokenStream {
+        self.tooltips
+            .iter()
+            .map(|(id, _)| {
+                let name = self.fluent_name(*id);
+                quote!(#id => Some(get_bundle_message(#name, None).into_owned()),)
+            })
+            .collect()
+    }
+}
+
+/// Pushes the widgets with a tooltip, including the widgets created for each item of a list.
+fn push_tooltips<'a>(widget_tree: &Widget<'a>, tooltips: &mut Vec<(u32, &'a Fluent)>) {
+    for widget in widget_tree.iter() {
+        if let Some(text) = &widget.widget_declaration.tooltip {
+            tooltips.push((widget.id.id(), text));
+        }
+        if let Some(for_each) = &widget.for_each {
+            push_tooltips(&for_each.template, tooltips);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::tooltip::Tooltips;
+    use crate::widget::Widget;
+    use gui_core::parse::ComponentDeclaration;
+
+    fn component(tooltip: &str) -> ComponentDeclaration {
+        serde_yaml::from_str(&format!(
+            r#"
+name: Editor
+child:
+  widget: VStack
+  properties:
+    children:
+      - name: Title
+        widget: Text
+        properties:
+          text: Untitled
+      - name: Save
+        widget: Text
+        tooltip: {tooltip}
+        properties:
+          text: Save"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_tooltips() {
+        let declaration = component("Saves the file");
+        let widget = Widget::new(&declaration).unwrap();
+        let tooltips = Tooltips::new(&declaration, &widget).unwrap();
+
+        let mut fluents = vec![];
+        tooltips.push_fluents(&mut fluents);
+        let names = fluents.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["Editor-tooltip-2"]);
+        assert!(tooltips
+            .gen_tooltip_arms()
+            .to_string()
+            .contains("2u32 => Some (get_bundle_message (\"Editor-tooltip-2\" , None)"));
+
+        let declaration = component("Saves { $file }");
+        let widget = Widget::new(&declaration).unwrap();
+        assert!(Tooltips::new(&declaration, &widget).is_err());
+    }
+}
@@ -1,21 +1,29 @@
 mod common;
+mod for_each;
 mod iter;
 mod overridden_widget;
 mod widget_set;
 
+use crate::animation::Transitions;
 use crate::fluent::FluentIdent;
+use crate::hot_reload::StaticLookup;
 use crate::widget::common::{Components, Fluents, Statics, Variables};
 use crate::widget::overridden_widget::WidgetProperties;
-use anyhow::anyhow;
+use anyhow::{anyhow, bail, Context};
+use for_each::ForEachTemplate;
+use gui_core::parse::var::{Name, Var};
 use gui_core::parse::{
-    ComponentDeclaration, NormalVariableDeclaration, StateDeclaration, WidgetDeclaration,
+    Action, ComponentDeclaration, NormalVariableDeclaration, StateDeclaration, WidgetDeclaration,
 };
-use gui_core::widget::WidgetID;
+use gui_core::theme::{Theme, ThemeValue};
+use gui_core::widget::{WidgetBuilder, WidgetID};
+use gui_core::Colour;
 use iter::WidgetIter;
 use itertools::Itertools;
 use overridden_widget::OverriddenWidget;
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote};
+use std::collections::HashSet;
 use std::sync::atomic::{AtomicU32, Ordering};
 use widget_set::WidgetSet;
 
@@ -33,6 +41,12 @@ pub struct Widget<'a> {
     /// The name of the handler if this Widget requires one.
     pub handler: Option<Ident>,
     pub components: Components,
+    /// The `visible` layout property, wrapping the widget so that it can be hidden.
+    pub visible: Option<&'a Var<bool>>,
+    /// The template of a `ForEach` widget.
+    pub for_each: Option<ForEachTemplate<'a>>,
+    /// The variables bound to properties, and the function taking a value changed by the widget.
+    pub bindings: Vec<(Name, TokenStream)>,
     pub id: WidgetID,
 }
 
@@ -71,8 +85,41 @@ impl<'a> Widget<'a> {
             widget_declaration.name.as_deref(),
             widget_type_name,
         );
+        let fluent_widget_name = widget_declaration
+            .name
+            .as_deref()
+            .unwrap_or(widget_type_name);
+        let mut statics = Statics::new(widget);
+        let list_fluents = statics.translate_lists(widget, |property| {
+            format!("{component_name}-{fluent_widget_name}-{property}")
+        })?;
+
+        let visible = widget_declaration
+            .layout_properties
+            .as_ref()
+            .and_then(|l| l.visible.as_ref());
+        match visible {
+            Some(Var::Theme(_)) => bail!("The visible layout property can not use a theme token"),
+            Some(Var::Binding(_)) => bail!("The visible layout property can not be bound"),
+            _ => {}
+        }
+        validate_widget(widget, widget_declaration.name.as_deref())?;
+        let bindings = widget
+            .get_bindings()
+            .into_iter()
+            .map(|(property, name, take)| {
+                let take = take.ok_or_else(|| {
+                    anyhow!("Property {property} of widget {widget_type_name} can not be bound")
+                })?;
+                Ok((name, take))
+            })
+            .collect::<anyhow::Result<_>>()?;
 
         let id = WidgetID::next(component_id);
+        let for_each = widget
+            .for_each()
+            .map(|f| ForEachTemplate::new(component_name, f, states, component_id))
+            .transpose()?;
         let mut state_overrides =
             OverriddenWidget::new(component_name, widget_declaration, states)?;
         let shared_overrides = WidgetProperties::remove_common_properties(&mut state_overrides[..]);
@@ -89,10 +136,14 @@ impl<'a> Widget<'a> {
             handler,
             state_overrides,
             fallback: WidgetProperties {
-                statics: Statics::new(widget),
+                statics,
                 fluents,
+                list_fluents,
                 variables: Variables(widget.get_vars()),
             },
+            visible,
+            for_each,
+            bindings,
             id,
             shared_overrides,
         })
@@ -100,20 +151,29 @@ impl<'a> Widget<'a> {
 
     pub fn gen_widget_type(&self) -> TokenStream {
         let mut stream = TokenStream::new();
-        let child_type = self.child_widgets.as_ref().map(|s| s.gen_widget_type());
+        let child_type = match &self.for_each {
+            Some(f) => Some(f.template.gen_widget_type()),
+            None => self.child_widgets.as_ref().map(|s| s.gen_widget_type()),
+        };
         self.widget_declaration.widget.widget_type(
             self.handler.as_ref(),
             &format_ident!("CompStruct"),
             child_type.as_ref(),
             &mut stream,
         );
-        stream
+        if self.visible.is_some() {
+            quote!(::gui::gui_widget::Visible<#stream>)
+        } else {
+            stream
+        }
     }
 
     pub fn push_fluents(&'a self, container: &mut Vec<FluentIdent>) {
         container.extend_from_slice(&self.fallback.fluents.0[..]);
+        container.extend_from_slice(&self.fallback.list_fluents[..]);
         for ow in &self.state_overrides {
             container.extend_from_slice(&ow.state_fluent_overrides.0[..]);
+            container.extend_from_slice(&ow.state_list_fluents[..]);
         }
         for (_, child) in self
             .child_widgets
@@ -125,12 +185,72 @@ impl<'a> Widget<'a> {
         }
     }
 
+    /// Pushes the fluents of the widgets created for each item of a list, which are formatted
+    /// using the item rather than the variables of the component.
+    pub fn push_item_fluents(&'a self, container: &mut Vec<FluentIdent>) {
+        for for_each in self.iter().filter_map(|w| w.for_each.as_ref()) {
+            for_each.template.push_fluents(container);
+        }
+    }
+
+    /// Generates the code to store the values of bound properties that were changed by the widget
+    /// in the component, running `on_change` after each value is stored.
+    pub fn gen_binding_updates(
+        &self,
+        widget_stmt: &TokenStream,
+        on_change: &TokenStream,
+    ) -> TokenStream {
+        self.bindings
+            .iter()
+            .map(|(name, take)| {
+                let name = Ident::new(name, Span::call_site());
+                quote! {
+                    if let Some(value) = #widget_stmt.#take() {
+                        <CompStruct as Bind<#name>>::set_value(&mut self.comp_struct, value);
+                        #on_change
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Generates the code to set the fluents of an item of a list, using the `index` and `item`
+    /// of the item as the fluent arguments.
+    fn gen_item_fluents(&self, widget_stmt: &TokenStream, stream: &mut TokenStream) {
+        self.fallback.fluents.gen_item_fluents(
+            &*self.widget_declaration.widget,
+            widget_stmt,
+            stream,
+        );
+
+        if let Some(ws) = &self.child_widgets {
+            for (get_stmt, w) in ws.gen_widget_gets(widget_stmt) {
+                w.gen_item_fluents(&get_stmt, stream)
+            }
+        }
+    }
+
     fn gen_var_update2(
         &self,
         var: &NormalVariableDeclaration,
         widget_stmt: &TokenStream,
         stream: &mut TokenStream,
     ) {
+        if let Some(for_each) = &self.for_each {
+            if for_each.declaration.items == var.name {
+                stream.extend(for_each.gen_items_update(self, widget_stmt));
+            }
+        }
+
+        if let Some(Var::Variable(name)) = self.visible {
+            if name == &var.name {
+                stream.extend(quote! {
+                    let widget = #widget_stmt;
+                    widget.set_visible(value, handle_ref);
+                });
+            }
+        }
+
         self.gen_if_correct_state(stream, |var_stream| {
             self.fallback.variables.gen_variables(
                 &*self.widget_declaration.widget,
@@ -239,7 +359,7 @@ impl<'a> Widget<'a> {
         self.widget_declaration
             .widget
             .create_widget(self.id, &mut stream);
-        if child_init.is_some() {
+        let init = if child_init.is_some() {
             quote!(
                 {
                     let mut widget = #stream;
@@ -249,6 +369,11 @@ impl<'a> Widget<'a> {
             )
         } else {
             stream
+        };
+        if self.visible.is_some() {
+            quote!(::gui::gui_widget::Visible::new(#init))
+        } else {
+            init
         }
     }
 
@@ -256,6 +381,9 @@ impl<'a> Widget<'a> {
         if let Some(set) = &self.child_widgets {
             set.gen_widget_set(stream)
         }
+        if let Some(for_each) = &self.for_each {
+            for_each.template.gen_widget_set(stream)
+        }
     }
 
     fn gen_shared_overrides(&self, stream: &mut TokenStream, func: impl FnOnce(&mut TokenStream)) {
@@ -310,13 +438,40 @@ impl<'a> Widget<'a> {
         })
     }
 
-    pub fn gen_statics(&self, widget_stmt: Option<&TokenStream>, stream: &mut TokenStream) {
+    /// Generates the code to set all the statics. If `hot_reload_component` is given, the statics
+    /// will be looked up in case they have been changed by a hot reload. Statics with a transition
+    /// are animated towards their new value.
+    pub fn gen_statics(
+        &self,
+        widget_stmt: Option<&TokenStream>,
+        hot_reload_component: Option<&str>,
+        transitions: &Transitions,
+        stream: &mut TokenStream,
+    ) {
         let widget_stmt = widget_stmt.map_or_else(|| quote! {&mut self.widget}, Clone::clone);
+        let lookup = |in_state| {
+            hot_reload_component.map(|component| StaticLookup {
+                component,
+                id: self.id,
+                in_state,
+            })
+        };
+        let widget_transitions = transitions.for_widget(self);
+        let widget_transitions = widget_transitions.as_ref();
+
+        if let Some(Var::Value(visible)) = self.visible {
+            stream.extend(quote! {
+                let widget = #widget_stmt;
+                widget.set_visible(#visible, handle_ref);
+            });
+        }
 
         self.gen_if_correct_state(stream, |static_stream| {
             self.fallback.statics.gen_statics(
                 &*self.widget_declaration.widget,
                 &widget_stmt,
+                lookup(false).as_ref(),
+                widget_transitions,
                 static_stream,
             )
         });
@@ -325,6 +480,8 @@ impl<'a> Widget<'a> {
             self.shared_overrides.statics.gen_statics(
                 &*self.widget_declaration.widget,
                 &widget_stmt,
+                lookup(!self.state_overrides.is_empty()).as_ref(),
+                widget_transitions,
                 static_stream,
             );
         });
@@ -337,6 +494,8 @@ impl<'a> Widget<'a> {
                 widget.statics.gen_statics(
                     &*self.widget_declaration.widget,
                     &widget_stmt,
+                    lookup(true).as_ref(),
+                    widget_transitions,
                     static_stream,
                 )
             });
@@ -344,11 +503,22 @@ impl<'a> Widget<'a> {
 
         if let Some(ws) = &self.child_widgets {
             for (get_stmt, w) in ws.gen_widget_gets(&widget_stmt) {
-                w.gen_statics(Some(&get_stmt), stream);
+                w.gen_statics(Some(&get_stmt), hot_reload_component, transitions, stream);
             }
         }
     }
 
+    /// The names of the properties set by statics, in any state.
+    pub fn static_properties(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.fallback
+            .statics
+            .0
+            .iter()
+            .chain(&self.shared_overrides.statics.0)
+            .chain(self.state_overrides.iter().flat_map(|o| &o.statics.0))
+            .map(|(p, _)| *p)
+    }
+
     pub fn gen_widget_id_to_widget(
         &self,
         widget_stmt: Option<&TokenStream>,
@@ -378,9 +548,19 @@ impl<'a> Widget<'a> {
                 .collect_vec();
             acc.push((self.id, child_ids));
         }
+        if let Some(for_each) = &self.for_each {
+            for_each.template.get_parent_ids(acc);
+            acc.push((self.id, vec![for_each.template.id]));
+        }
     }
 
-    pub fn gen_handler_structs(&self, stream: &mut TokenStream) -> anyhow::Result<()> {
+    /// Generates the handler structs and the handler implementations of any actions declared in
+    /// the layout file.
+    pub fn gen_handler_structs(
+        &self,
+        states: &[StateDeclaration],
+        stream: &mut TokenStream,
+    ) -> anyhow::Result<()> {
         if let Some(name) = &self.handler {
             stream.extend(quote! {
                 pub(crate) struct #name;
@@ -391,16 +571,376 @@ impl<'a> Widget<'a> {
             });
         }
 
+        if let Some(action) = &self.widget_declaration.on_press {
+            let Some(handler_trait) = self.widget_declaration.widget.handler_trait() else {
+                bail!(
+                    "Widget {} does not support the onPress action",
+                    self.widget_type_name
+                );
+            };
+            let Some(name) = &self.handler else {
+                bail!(
+                    "Widget {} must be named to use the onPress action",
+                    self.widget_type_name
+                );
+            };
+            let action = gen_action(action, states)
+                .with_context(|| format!("Invalid onPress action for widget {name}"))?;
+            stream.extend(quote! {
+                impl #handler_trait<#name> for CompStruct {
+                    fn on_press(&mut self) {
+                        #action
+                    }
+                }
+            });
+        }
+
         if let Some(ws) = &self.child_widgets {
             for (_, w) in ws.widgets.iter().flat_map(|(_, w)| w.iter()) {
-                w.gen_handler_structs(stream)?;
+                w.gen_handler_structs(states, stream)?;
             }
         }
 
+        if let Some(for_each) = &self.for_each {
+            for_each.template.gen_handler_structs(states, stream)?;
+        }
+
         Ok(())
     }
 
+    /// The names of the handler structs generated for the widgets.
+    pub fn handler_names(&self) -> HashSet<String> {
+        let mut names = HashSet::new();
+        for widget in self.iter() {
+            names.extend(widget.handler.as_ref().map(ToString::to_string));
+            if let Some(for_each) = &widget.for_each {
+                names.extend(for_each.template.handler_names());
+            }
+        }
+        names
+    }
+
     pub fn iter<'b>(&'b self) -> WidgetIter<'a, 'b> {
         WidgetIter::new(self)
     }
 }
+
+/// Checks the static values of the widget's properties, such as sizes being in range, and that
+/// the theme tokens it uses are in the built-in themes.
+pub(crate) fn validate_widget(
+    widget: &dyn WidgetBuilder,
+    widget_name: Option<&str>,
+) -> anyhow::Result<()> {
+    let widget_name = widget_name.unwrap_or(widget.name());
+    if let Some((property, error)) = widget.validate().into_iter().next() {
+        bail!("Invalid property {property} of widget {widget_name}: {error}");
+    }
+    let theme = Theme::light();
+    for (property, token) in widget.get_theme_tokens() {
+        if Colour::from_theme(&theme, &token).is_err() && f32::from_theme(&theme, &token).is_err() {
+            bail!(
+                "Property {property} of widget {widget_name} uses the theme token {token}, which \
+                 is not a colour, font size or spacing of the built-in themes"
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Generates the body of a handler function that runs `action`. Switching states requires the
+/// component to store its state in a `state` field (normally an `Updateable<State>`), which can
+/// not be checked here as the component struct is written by the user. The generated code fails
+/// to compile without it.
+fn gen_action(action: &Action, states: &[StateDeclaration]) -> anyhow::Result<TokenStream> {
+    match action {
+        Action::SetState(state) => {
+            if !states.iter().any(|s| &s.name == state) {
+                bail!("Could not find state {state}");
+            }
+            let state = format_ident!("{}", state.as_str());
+            Ok(quote! {
+                self.state.set_value(State::#state);
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::widget::Widget;
+    use gui_core::parse::ComponentDeclaration;
+    use quote::quote;
+
+    fn gen_handlers(yaml: &str) -> anyhow::Result<String> {
+        let declaration: ComponentDeclaration = serde_yaml::from_str(yaml)?;
+        let widget = Widget::new(&declaration)?;
+        let mut stream = Default::default();
+        widget.gen_handler_structs(&declaration.states[..], &mut stream)?;
+        Ok(stream.to_string())
+    }
+
+    #[test]
+    fn test_set_state_action() {
+        let yaml = |state: &str, widget: &str| {
+            format!(
+                r#"
+name: Filter
+states:
+  - name: All
+    overrides: []
+  - name: Active
+    overrides: []
+child:
+  name: ActiveBtn
+  widget: {widget}
+  onPress:
+    setState: {state}"#
+            )
+        };
+
+        let handlers = gen_handlers(&yaml("Active", "Button")).unwrap();
+        assert!(handlers.contains("ButtonHandler < ActiveBtn > for CompStruct"));
+        assert!(handlers.contains("self . state . set_value (State :: Active)"));
+
+        let handlers = gen_handlers(&yaml("Active", "Clickable")).unwrap();
+        assert!(handlers.contains("ClickableHandler < ActiveBtn > for CompStruct"));
+
+        assert!(gen_handlers(&yaml("Missing", "Button")).is_err());
+        assert!(gen_handlers(&yaml("Active", "Text")).is_err());
+
+        let unnamed = yaml("Active", "Button").replace("name: ActiveBtn", "");
+        let error = gen_handlers(&unnamed).unwrap_err().to_string();
+        assert_eq!(
+            error,
+            "Widget Button must be named to use the onPress action"
+        );
+    }
+
+    #[test]
+    fn test_visibility() {
+        let declaration: ComponentDeclaration = serde_yaml::from_str(
+            r#"
+name: Login
+variables:
+  - name: logged_in
+    type: bool
+child:
+  widget: VStack
+  properties:
+    children:
+      - widget: Text
+        properties:
+          text: Welcome
+        layoutProperties:
+          visible:
+            variable: logged_in
+      - if:
+          variable: logged_in
+        then:
+          widget: Text
+          properties:
+            text: Log out
+        else:
+          widget: Text
+          properties:
+            text: Log in"#,
+        )
+        .unwrap();
+        let widget = Widget::new(&declaration).unwrap();
+        let widget_types = widget
+            .iter()
+            .map(|w| w.gen_widget_type().to_string())
+            .collect::<Vec<_>>();
+        assert!(widget_types
+            .contains(&":: gui :: gui_widget :: Visible < :: gui :: gui_widget :: Text >".into()));
+        assert!(widget_types
+            .iter()
+            .any(|t| t.starts_with(":: gui :: gui_widget :: If < WidgetSet")));
+
+        let var_update = widget
+            .gen_var_update(declaration.variables[0].get_normal().unwrap())
+            .to_string();
+        assert!(var_update.contains("widget . set_visible (value , handle_ref)"));
+        assert!(var_update.contains("widget . set_condition (value , handle_ref)"));
+    }
+
+    #[test]
+    fn test_binding() {
+        let component = |property: &str| -> ComponentDeclaration {
+            serde_yaml::from_str(&format!(
+                r#"
+name: Terms
+variables:
+  - name: agreed
+    type: bool
+child:
+  widget: Checkbox
+  properties:
+    {property}:
+      binding: agreed"#
+            ))
+            .unwrap()
+        };
+
+        let declaration = component("checked");
+        let widget = Widget::new(&declaration).unwrap();
+        let var_update = widget
+            .gen_var_update(declaration.variables[0].get_normal().unwrap())
+            .to_string();
+        assert!(var_update.contains("set_checked (value , handle_ref)"));
+        assert_eq!(
+            widget
+                .gen_binding_updates(&quote!(self.widget), &quote!())
+                .to_string(),
+            "if let Some (value) = self . widget . take_checked () { < CompStruct as Bind < agreed > > :: set_value (& mut self . comp_struct , value) ; }"
+        );
+        assert!(widget
+            .gen_binding_updates(&quote!(self.widget), &quote!(rebound = true;))
+            .to_string()
+            .ends_with("set_value (& mut self . comp_struct , value) ; rebound = true ; }"));
+
+        let declaration = component("disabled");
+        assert!(Widget::new(&declaration).is_err());
+    }
+
+    #[test]
+    fn test_translated_options() {
+        let component = |option: &str| -> ComponentDeclaration {
+            serde_yaml::from_str(&format!(
+                r#"
+name: Settings
+states:
+  - name: Compact
+    overrides:
+      - name: Size
+        widget: Select
+        properties:
+          options: [Tiny, Small]
+  - name: Wide
+    overrides:
+      - name: Size
+        widget: Select
+        properties:
+          disabled: true
+child:
+  name: Size
+  widget: Select
+  properties:
+    options: [Small, "{option}"]"#
+            ))
+            .unwrap()
+        };
+
+        let declaration = component("Large");
+        let widget = Widget::new(&declaration).unwrap();
+        let mut fluents = vec![];
+        widget.push_fluents(&mut fluents);
+        let names = fluents.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "Settings-Size-options-0",
+                "Settings-Size-options-1",
+                "Settings-Size-Compact-options-0",
+                "Settings-Size-Compact-options-1",
+            ]
+        );
+
+        let statics = widget
+            .iter()
+            .flat_map(|w| {
+                w.fallback
+                    .statics
+                    .0
+                    .iter()
+                    .chain(&w.shared_overrides.statics.0)
+                    .chain(w.state_overrides.iter().flat_map(|s| &s.statics.0))
+            })
+            .filter(|(p, _)| *p == "options")
+            .map(|(_, s)| s.get().to_string())
+            .collect::<Vec<_>>();
+        assert!(statics.contains(&":: std :: vec ! [get_bundle_message (\"Settings-Size-options-0\" , None) . into_owned () , get_bundle_message (\"Settings-Size-options-1\" , None) . into_owned ()]".to_string()));
+        assert!(statics
+            .iter()
+            .any(|s| s.contains("\"Settings-Size-Compact-options-1\"")));
+
+        let declaration = component("{ $size }");
+        assert!(Widget::new(&declaration).is_err());
+    }
+
+    #[test]
+    fn test_validation() {
+        let component = |size: &str| -> ComponentDeclaration {
+            serde_yaml::from_str(&format!(
+                r#"
+name: Title
+child:
+  name: Heading
+  widget: Text
+  properties:
+    text: Hello
+    size: {size}"#
+            ))
+            .unwrap()
+        };
+
+        assert!(Widget::new(&component("24")).is_ok());
+        let error = Widget::new(&component("-5")).unwrap_err().to_string();
+        assert!(error.contains("size"));
+        assert!(error.contains("Heading"));
+
+        assert!(Widget::new(&component("{theme: heading}")).is_ok());
+        assert!(Widget::new(&component("{theme: spacing.lg}")).is_ok());
+        let error = Widget::new(&component("{theme: headng}"))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("theme token headng"));
+    }
+
+    fn for_each_component(template: &str) -> ComponentDeclaration {
+        serde_yaml::from_str(&format!(
+            r#"
+name: Names
+variables:
+  - name: names
+    type: Vec<String>
+  - name: title
+    type: String
+child:
+  forEach: names
+  template:
+    {template}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_for_each() {
+        let declaration = for_each_component(
+            r#"widget: Text
+    properties:
+      text: "{ $index }: { $item }""#,
+        );
+        let widget = Widget::new(&declaration).unwrap();
+        assert!(widget.gen_widget_type().to_string().starts_with(
+            ":: gui :: gui_widget :: for_each :: ForEach < :: gui :: gui_widget :: Text"
+        ));
+
+        let var_update = widget
+            .gen_var_update(declaration.variables[0].get_normal().unwrap())
+            .to_string();
+        assert!(
+            var_update.contains("widget . update_items (& value [..] , | index , _ | index as u64")
+        );
+        assert!(var_update.contains("self . for_each_items . extend"));
+        assert!(var_update.contains("args . set (\"item\" , item . clone ())"));
+
+        let declaration = for_each_component(
+            r#"widget: Text
+    properties:
+      text:
+        variable: title"#,
+        );
+        assert!(Widget::new(&declaration).is_err());
+    }
+}
@@ -1,5 +1,9 @@
+use crate::animation::WidgetTransitions;
 use crate::fluent::FluentIdent;
+use crate::hot_reload::StaticLookup;
 use crate::tokenstream::EqTokenStream;
+use anyhow::bail;
+use gui_core::parse::list::StringList;
 use gui_core::parse::var::Name;
 use gui_core::widget::WidgetBuilder;
 use itertools::Itertools;
@@ -28,10 +32,54 @@ impl Statics {
         )
     }
 
+    /// Replaces static lists of fluent strings, such as the options of a `Select`, with their
+    /// translations. Each item is given the name `{name}-{index}`, where `name` is created from
+    /// the property by `fluent_name`. Returns the fluents that need to be added to the bundle.
+    pub fn translate_lists(
+        &mut self,
+        builder: &dyn WidgetBuilder,
+        fluent_name: impl Fn(&str) -> String,
+    ) -> anyhow::Result<Vec<FluentIdent>> {
+        let mut result = vec![];
+        for (prop, value) in builder.get_static_values() {
+            let Some(list) = value.downcast_ref::<StringList>() else {
+                continue;
+            };
+            let name = fluent_name(prop);
+            let items = list
+                .0
+                .iter()
+                .enumerate()
+                .map(|(i, fluent)| {
+                    if !fluent.vars.is_empty() {
+                        bail!("Item {i} of property {prop} cannot use variables");
+                    }
+                    let name = format!("{name}-{i}");
+                    Ok(FluentIdent {
+                        property: prop,
+                        ident: format_ident!("{}", name.replace('-', "_")),
+                        name,
+                        fluent: fluent.clone(),
+                        property_ident: Ident::new(prop, Span::call_site()),
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            let names = items.iter().map(|f| &f.name);
+            let value = quote!(::std::vec![#(get_bundle_message(#names, None).into_owned()),*]);
+            if let Some((_, stream)) = self.0.iter_mut().find(|(p, _)| *p == prop) {
+                *stream = value.into();
+            }
+            result.extend(items);
+        }
+        Ok(result)
+    }
+
     pub fn gen_statics(
         &self,
         widget_builder: &dyn WidgetBuilder,
         widget_stmt: &TokenStream,
+        lookup: Option<&StaticLookup>,
+        transitions: Option<&WidgetTransitions>,
         static_stream: &mut TokenStream,
     ) {
         let (widget_ident, value_ident, handle_ident) = gen_idents();
@@ -42,9 +90,14 @@ impl Statics {
         }
 
         for (prop, value) in &self.0 {
+            let value =
+                lookup.map_or_else(|| value.get().clone(), |l| l.gen_value(prop, value.get()));
             static_stream.extend(quote! {
                 let value = #value;
             });
+            if let Some(animate) = transitions.and_then(|t| t.gen_animate(prop)) {
+                static_stream.extend(animate);
+            }
             widget_builder.on_property_update(
                 prop,
                 &widget_ident,
@@ -139,6 +192,58 @@ impl Fluents {
         }
     }
 
+    /// Generates the code to set the fluents of an item of a `ForEach` widget, which can only
+    /// use the `index` and `item` variables.
+    pub fn gen_item_fluents(
+        &self,
+        widget_builder: &dyn WidgetBuilder,
+        widget_stmt: &TokenStream,
+        fluent_stream: &mut TokenStream,
+    ) {
+        let (widget_ident, value_ident, handle_ident) = gen_idents();
+
+        for fluent in &self.0 {
+            let fluent_name = &fluent.name;
+            let mut on_property_update = TokenStream::new();
+            widget_builder.on_property_update(
+                fluent.property,
+                &widget_ident,
+                &value_ident,
+                &handle_ident,
+                &mut on_property_update,
+            );
+
+            let set_args = fluent.fluent.vars.iter().map(|var| {
+                let value = match var.as_str() {
+                    "index" => quote!(index),
+                    _ => quote!(item.clone()),
+                };
+                let var_name = var.as_str();
+                quote!(args.set(#var_name, #value);)
+            });
+            let (args, arg) = if fluent.fluent.vars.is_empty() {
+                (None, quote! {None})
+            } else {
+                (
+                    Some(quote! {
+                        let mut args = FluentArgs::new();
+                        #(#set_args)*
+                    }),
+                    quote! {Some(&args)},
+                )
+            };
+
+            fluent_stream.extend(quote! {
+                {
+                    #args
+                    let value = get_bundle_message(#fluent_name, #arg);
+                    let #widget_ident = #widget_stmt;
+                    #on_property_update
+                }
+            });
+        }
+    }
+
     pub fn gen_fluent_arg_update(&self, var_name: &Name, fluent_stream: &mut TokenStream) {
         let (_, value_ident, _) = gen_idents();
 
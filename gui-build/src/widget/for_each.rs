@@ -0,0 +1,140 @@
+use crate::animation::Transitions;
+use crate::widget::Widget;
+use anyhow::{bail, Context};
+use gui_core::parse::var::Var;
+use gui_core::parse::{ForEachDeclaration, StateDeclaration};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+/// The variables that fluent text in a template can use.
+const ITEM_VARIABLES: [&str; 2] = ["item", "index"];
+
+/// The widget created for each item of a `Vec` variable by a `ForEach` widget. Each item has its
+/// own runtime ID, so the widgets of the template only have to be created once.
+#[derive(Clone, Debug)]
+pub struct ForEachTemplate<'a> {
+    pub declaration: &'a ForEachDeclaration,
+    pub template: Box<Widget<'a>>,
+}
+
+impl<'a> ForEachTemplate<'a> {
+    pub fn new(
+        component_name: &str,
+        declaration: &'a ForEachDeclaration,
+        states: &'a [StateDeclaration],
+        component_id: u32,
+    ) -> anyhow::Result<Self> {
+        let template =
+            Widget::new_inner(component_name, &declaration.template, states, component_id)
+                .context("Failed to create ForEach template")?;
+
+        for widget in template.iter() {
+            let name = widget.widget_type_name;
+            if widget.for_each.is_some() {
+                bail!("ForEach templates cannot contain another ForEach");
+            }
+            if !widget.state_overrides.is_empty() {
+                bail!("Widget {name} in a ForEach template cannot be overridden by states");
+            }
+            if !widget.fallback.variables.0.is_empty()
+                || matches!(widget.visible, Some(Var::Variable(_)))
+            {
+                bail!("Widget {name} in a ForEach template cannot use variables");
+            }
+            if !widget.components.0.is_empty() {
+                bail!("Widget {name} in a ForEach template cannot hold components");
+            }
+            for fluent in &widget.fallback.fluents.0 {
+                if let Some(var) = fluent
+                    .fluent
+                    .vars
+                    .iter()
+                    .find(|v| !ITEM_VARIABLES.contains(&v.as_str()))
+                {
+                    bail!("Widget {name} in a ForEach template can only use $item and $index in fluent text, but found ${var}");
+                }
+            }
+        }
+
+        Ok(Self {
+            declaration,
+            template: Box::new(template),
+        })
+    }
+
+    fn uses_variable(&self, variable: &str) -> bool {
+        self.template.iter().any(|w| {
+            w.fallback
+                .fluents
+                .0
+                .iter()
+                .any(|f| f.fluent.vars.iter().any(|v| v.as_str() == variable))
+        })
+    }
+
+    /// Generates the code to update the items of the `ForEach` widget when the list of items is
+    /// updated. The generated code expects the list to be called `value`.
+    pub fn gen_items_update(&self, widget: &Widget, widget_stmt: &TokenStream) -> TokenStream {
+        let id = widget.id;
+        let key = match &self.declaration.key {
+            Some(key) => {
+                let key = format_ident!("{}", key.as_str());
+                quote!(|_, item| ::gui::gui_widget::for_each::hash_key(&item.#key))
+            }
+            None => quote!(|index, _| index as u64),
+        };
+        let index = if self.uses_variable("index") {
+            quote!(index)
+        } else {
+            quote!(_)
+        };
+        let item = if self.uses_variable("item") {
+            quote!(item)
+        } else {
+            quote!(_)
+        };
+
+        let init = self.template.gen_widget_init();
+        let item_widget = quote!(&mut *item_widget);
+        let mut updates = TokenStream::new();
+        self.template
+            .gen_statics(Some(&item_widget), None, &Transitions::none(), &mut updates);
+        self.template.gen_item_fluents(&item_widget, &mut updates);
+
+        quote! {
+            let widget = #widget_stmt;
+            widget.update_items(
+                &value[..],
+                #key,
+                || #init,
+                |item_widget, #index, #item, handle_ref| {
+                    #updates
+                },
+                force_update,
+                handle_ref,
+            );
+            self.for_each_items.retain(|_, for_each_id| *for_each_id != #id);
+            self.for_each_items.extend(widget.runtime_ids().map(|runtime_id| (runtime_id, #id)));
+        }
+    }
+
+    /// Generates the match arm that sends an event to a widget of an item.
+    pub fn gen_item_event(&self, widget: &Widget, widget_stmt: &TokenStream) -> TokenStream {
+        let id = widget.id.id();
+        let mut id_to_widgets = vec![];
+        self.template
+            .gen_widget_id_to_widget(Some(&quote!(item_widget)), &mut id_to_widgets);
+        let arms = id_to_widgets.iter().map(|(widget_id, widget_get)| {
+            let widget_id = widget_id.id();
+            quote!(#widget_id => {#widget_get.event(event, handle_ref);})
+        });
+        quote! {
+            #id => #widget_stmt.item_event(runtime_id, handle_ref, |item_widget, handle_ref| {
+                match widget_id.id() {
+                    #(#arms)*
+                    _ => {},
+                }
+            }),
+        }
+    }
+}
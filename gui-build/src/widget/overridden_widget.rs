@@ -1,6 +1,7 @@
 use crate::fluent::FluentIdent;
 use crate::widget::common::{Fluents, Statics, Variables};
-use anyhow::{anyhow, bail};
+use crate::widget::validate_widget;
+use anyhow::{anyhow, bail, Context};
 use gui_core::parse::var::Name;
 use gui_core::parse::{StateDeclaration, WidgetDeclaration};
 use gui_core::widget::WidgetBuilder;
@@ -14,6 +15,8 @@ use std::hash::Hash;
 pub struct WidgetProperties {
     pub statics: Statics,
     pub fluents: Fluents,
+    /// The translated items of static lists, which are set with the statics.
+    pub list_fluents: Vec<FluentIdent>,
     pub variables: Variables,
 }
 
@@ -59,6 +62,7 @@ impl WidgetProperties {
                     .sorted_by_key(|v| v.0)
                     .collect(),
             ),
+            list_fluents: vec![],
         }
     }
 }
@@ -70,6 +74,8 @@ pub struct OverriddenWidget<'a> {
     pub statics: Statics,
     pub fluents: Fluents,
     pub state_fluent_overrides: Fluents,
+    /// The translated items of static lists declared by the state.
+    pub state_list_fluents: Vec<FluentIdent>,
     pub variables: Variables,
 }
 
@@ -92,11 +98,19 @@ impl<'a> OverriddenWidget<'a> {
                         anyhow!("Can only override widget {widget_name} once in {state_name}.")
                     })?
                 {
+                    if !state_override.widget.get_bindings().is_empty() {
+                        bail!("Overridden widget {widget_name} in {state_name} cannot bind properties.");
+                    }
                     let mut new_widget = state_override.widget.clone();
                     if new_widget.widgets().is_some_and(|v| !v.is_empty()) {
                         bail!("Overridden widget {widget_name} in {state_name} contains children.");
                     }
                     let widget_builder = widget_declaration.widget.as_ref();
+                    let overridden_statics = new_widget
+                        .get_statics()
+                        .into_iter()
+                        .map(|(p, _)| p)
+                        .collect();
                     let fluents = Fluents::new_state_override(
                         &*new_widget,
                         component_name,
@@ -104,13 +118,16 @@ impl<'a> OverriddenWidget<'a> {
                         state_name,
                     );
                     new_widget.combine(widget_builder);
+                    validate_widget(&*new_widget, Some(widget_name))
+                        .with_context(|| format!("In state {state_name}"))?;
                     result.push(Self::new_inner(
                         state_name,
                         widget_name,
                         component_name,
                         fluents,
+                        &overridden_statics,
                         &*new_widget,
-                    ));
+                    )?);
                 }
             }
         }
@@ -122,9 +139,22 @@ impl<'a> OverriddenWidget<'a> {
         widget_name: &str,
         component_name: &str,
         state_fluent_overrides: Fluents,
+        overridden_statics: &HashSet<&'static str>,
         builder: &'b (dyn WidgetBuilder + 'static),
-    ) -> Self {
-        let statics = Statics::new(builder);
+    ) -> anyhow::Result<Self> {
+        // Lists that are not overridden by the state use the translations of the widget.
+        let mut statics = Statics::new(builder);
+        let state_list_fluents = statics
+            .translate_lists(builder, |property| {
+                if overridden_statics.contains(property) {
+                    format!("{component_name}-{widget_name}-{state_name}-{property}")
+                } else {
+                    format!("{component_name}-{widget_name}-{property}")
+                }
+            })?
+            .into_iter()
+            .filter(|f| overridden_statics.contains(f.property))
+            .collect();
         let fluents = Fluents(
             builder
                 .get_fluents()
@@ -149,13 +179,14 @@ impl<'a> OverriddenWidget<'a> {
         );
         let variables = Variables(builder.get_vars());
 
-        Self {
+        Ok(Self {
             state_name,
             statics,
             fluents,
             state_fluent_overrides,
+            state_list_fluents,
             variables,
-        }
+        })
     }
 
     pub fn gen_if_correct_state(
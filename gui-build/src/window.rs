@@ -0,0 +1,142 @@
+use crate::fluent::FluentIdent;
+use anyhow::bail;
+use gui_core::parse::{ComponentDeclaration, WindowDeclaration};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+/// The `window` section of a component, which is used when the component is shown in a window.
+pub struct Window<'a> {
+    component: &'a ComponentDeclaration,
+}
+
+impl<'a> Window<'a> {
+    pub fn new(component: &'a ComponentDeclaration) -> anyhow::Result<Self> {
+        if let Some(window) = &component.window {
+            check_window(window)?;
+        }
+        Ok(Self { component })
+    }
+
+    fn title_name(&self) -> String {
+        format!("{}-window-title", self.component.name)
+    }
+
+    /// Pushes the title of the window, which is translated like the fluent properties of widgets.
+    pub fn push_fluents(&self, container: &mut Vec<FluentIdent>) {
+        let Some(title) = self
+            .component
+            .window
+            .as_ref()
+            .and_then(|w| w.title.as_ref())
+        else {
+            return;
+        };
+        let name = self.title_name();
+        container.push(FluentIdent {
+            property: "title",
+            ident: format_ident!("{}", name.replace('-', "_")),
+            name,
+            fluent: title.clone(),
+            property_ident: format_ident!("title"),
+        });
+    }
+
+    /// Generates the body of `Component::window_config`.
+    pub fn gen_window_config(&self) -> TokenStream {
+        let Some(window) = &self.component.window else {
+            return quote!(Default::default());
+        };
+        let title_name = self.title_name();
+        let title = window
+            .title
+            .as_ref()
+            .map(|_| quote!(Some(get_bundle_message(#title_name, None).into_owned())));
+        let [size, min_size, max_size] = [window.size, window.min_size, window.max_size].map(|s| {
+            s.map(|(width, height)| quote!(Some(::gui::gui_core::Size::new(#width, #height))))
+        });
+        let resizable = window.resizable.map(|r| quote!(Some(#r)));
+        let transparent = window.transparent.map(|t| quote!(Some(#t)));
+        let fields = [
+            ("title", title),
+            ("size", size),
+            ("min_size", min_size),
+            ("max_size", max_size),
+            ("resizable", resizable),
+            ("transparent", transparent),
+        ]
+        .into_iter()
+        .filter_map(|(field, value)| {
+            let field = format_ident!("{field}");
+            value.map(|value| quote!(#field: #value,))
+        });
+        quote! {
+            ::gui::gui_core::window::WindowConfig {
+                #(#fields)*
+                ..Default::default()
+            }
+        }
+    }
+}
+
+fn check_window(window: &WindowDeclaration) -> anyhow::Result<()> {
+    // The title is set when the window is created, so it cannot use variables.
+    if let Some(title) = window.title.as_ref().filter(|t| !t.vars.is_empty()) {
+        bail!("Window title {} cannot use variables", title.text.trim());
+    }
+    let sizes = [
+        ("size", window.size),
+        ("minSize", window.min_size),
+        ("maxSize", window.max_size),
+    ];
+    for (property, size) in sizes {
+        if let Some((width, height)) = size.filter(|(w, h)| *w <= 0.0 || *h <= 0.0) {
+            bail!("Window {property} [{width}, {height}] must be positive");
+        }
+    }
+    if let (Some(min), Some(max)) = (window.min_size, window.max_size) {
+        if min.0 > max.0 || min.1 > max.1 {
+            bail!("Window minSize cannot be larger than its maxSize");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::window::Window;
+    use gui_core::parse::ComponentDeclaration;
+
+    fn component(window: &str) -> ComponentDeclaration {
+        serde_yaml::from_str(&format!(
+            r#"
+name: Editor
+window:
+  {window}
+child:
+  widget: Text
+  properties:
+    text: Untitled"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_window() {
+        let declaration = component("{ title: Editor, minSize: [200, 100], resizable: false }");
+        let window = Window::new(&declaration).unwrap();
+        let mut fluents = vec![];
+        window.push_fluents(&mut fluents);
+        assert_eq!(fluents[0].name, "Editor-window-title");
+
+        let config = window.gen_window_config().to_string();
+        assert!(config.contains("\"Editor-window-title\""));
+        assert!(config
+            .contains("min_size : Some (:: gui :: gui_core :: Size :: new (200f64 , 100f64))"));
+        assert!(config.contains("resizable : Some (false)"));
+        assert!(!config.contains("max_size"));
+
+        assert!(Window::new(&component("{ title: \"{ $name }\" }")).is_err());
+        assert!(Window::new(&component("{ size: [0, 100] }")).is_err());
+        assert!(Window::new(&component("{ minSize: [200, 100], maxSize: [100, 100] }")).is_err());
+    }
+}
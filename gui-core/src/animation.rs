@@ -0,0 +1,247 @@
+//! Animated transitions of widget properties between component states.
+
+use crate::widget::UpdateHandle;
+use crate::Colour;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use serde::Deserialize;
+use std::any::Any;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Properties that can be interpolated between two values.
+pub trait Animatable: Clone + PartialEq + 'static {
+    /// Interpolate between `self` and `to`, where `t` goes from 0.0 to 1.0.
+    fn interpolate(&self, to: &Self, t: f32) -> Self;
+}
+
+impl Animatable for f32 {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Animatable for Colour {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        let channel = |from: u8, to: u8| (from as f32).interpolate(&(to as f32), t).round() as u8;
+        Colour::rgba8(
+            channel(self.0.r, to.0.r),
+            channel(self.0.g, to.0.g),
+            channel(self.0.b, to.0.b),
+            channel(self.0.a, to.0.a),
+        )
+    }
+}
+
+/// Whether a static of the type named `type_name`, as given by [`std::any::type_name`], implements
+/// [`Animatable`]. Used when building to reject transitions of other properties.
+pub fn is_animatable(type_name: &str) -> bool {
+    type_name == std::any::type_name::<f32>() || type_name == std::any::type_name::<Colour>()
+}
+
+/// How the progress of a transition changes over time.
+#[derive(Deserialize, Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum Easing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    /// Maps the linear progress `t` (from 0.0 to 1.0) to the eased progress.
+    pub fn apply(self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut if t < 0.5 => 4.0 * t * t * t,
+            Easing::EaseInOut => 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0,
+        }
+    }
+}
+
+impl ToTokens for Easing {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let variant = match self {
+            Easing::Linear => quote!(Linear),
+            Easing::EaseIn => quote!(EaseIn),
+            Easing::EaseOut => quote!(EaseOut),
+            Easing::EaseInOut => quote!(EaseInOut),
+        };
+        tokens.extend(quote!(::gui::gui_core::animation::Easing::#variant))
+    }
+}
+
+/// The duration and easing used to animate a property.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Transition {
+    pub duration: Duration,
+    pub easing: Easing,
+}
+
+impl Transition {
+    pub const fn new(duration_ms: u64, easing: Easing) -> Self {
+        Self {
+            duration: Duration::from_millis(duration_ms),
+            easing,
+        }
+    }
+}
+
+struct Animation<T> {
+    from: T,
+    to: T,
+    current: T,
+    start: Instant,
+    transition: Option<Transition>,
+}
+
+impl<T: Animatable> Animation<T> {
+    fn finished(value: T) -> Self {
+        Self {
+            from: value.clone(),
+            to: value.clone(),
+            current: value,
+            start: Instant::now(),
+            transition: None,
+        }
+    }
+
+    /// Moves the animation on to the current time, returning false if it has finished.
+    fn step(&mut self, now: Instant) -> bool {
+        let Some(transition) = self.transition else {
+            return false;
+        };
+        let t = if transition.duration.is_zero() {
+            1.0
+        } else {
+            now.duration_since(self.start).as_secs_f32() / transition.duration.as_secs_f32()
+        };
+        if t >= 1.0 {
+            self.current = self.to.clone();
+            self.transition = None;
+        } else {
+            self.current = self.from.interpolate(&self.to, transition.easing.apply(t));
+        }
+        true
+    }
+}
+
+/// The running animations of a component, keyed by widget ID and property.
+///
+/// Animations can only run in a window, as they rely on requesting animation frames. Without a
+/// window (such as in the `TestHarness`) properties are set to their final value straight away.
+#[derive(Default)]
+pub struct Animations {
+    animations: HashMap<(u32, &'static str), Box<dyn Any>>,
+}
+
+impl Animations {
+    /// Starts animating a property towards `target` using `transition`, returning the value the
+    /// property should be set to now. The property is set to `target` straight away if there is
+    /// no transition or the property has not been set before.
+    pub fn animate_to<T: Animatable>(
+        &mut self,
+        key: (u32, &'static str),
+        target: T,
+        transition: Option<Transition>,
+        handle: &mut UpdateHandle,
+    ) -> T {
+        let Some(animation) = self
+            .animations
+            .get_mut(&key)
+            .and_then(|a| a.downcast_mut::<Animation<T>>())
+        else {
+            self.animations
+                .insert(key, Box::new(Animation::finished(target.clone())));
+            return target;
+        };
+
+        if animation.to == target {
+            return animation.current.clone();
+        }
+
+        match transition {
+            Some(transition) if handle.request_anim_frame() => {
+                animation.from = animation.current.clone();
+                animation.to = target;
+                animation.start = Instant::now();
+                animation.transition = Some(transition);
+                animation.current.clone()
+            }
+            _ => {
+                *animation = Animation::finished(target.clone());
+                target
+            }
+        }
+    }
+
+    /// Returns the next value of the property if it is being animated.
+    pub fn next_frame<T: Animatable>(
+        &mut self,
+        key: (u32, &'static str),
+        handle: &mut UpdateHandle,
+    ) -> Option<T> {
+        let animation = self
+            .animations
+            .get_mut(&key)?
+            .downcast_mut::<Animation<T>>()?;
+        if !animation.step(Instant::now()) {
+            return None;
+        }
+        if animation.transition.is_some() {
+            handle.request_anim_frame();
+        }
+        Some(animation.current.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Animatable, Animation, Easing, Transition};
+    use crate::Colour;
+    use std::time::Duration;
+
+    #[test]
+    fn easing_bounds() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+        }
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    }
+
+    #[test]
+    fn interpolate_colour() {
+        let black = Colour::rgba8(0, 0, 0, 255);
+        let white = Colour::rgba8(255, 255, 255, 255);
+        assert_eq!(black.interpolate(&white, 0.0), black);
+        assert_eq!(
+            black.interpolate(&white, 0.5),
+            Colour::rgba8(128, 128, 128, 255)
+        );
+        assert_eq!(black.interpolate(&white, 1.0), white);
+    }
+
+    #[test]
+    fn step_animation() {
+        let mut animation = Animation::finished(0.0f32);
+        animation.to = 10.0;
+        animation.transition = Some(Transition::new(100, Easing::Linear));
+        let start = animation.start;
+
+        assert!(animation.step(start + Duration::from_millis(50)));
+        assert_eq!(animation.current, 5.0);
+        assert!(animation.step(start + Duration::from_millis(150)));
+        assert_eq!(animation.current, 10.0);
+        assert!(!animation.step(start + Duration::from_millis(200)));
+    }
+}
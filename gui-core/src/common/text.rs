@@ -1,5 +1,7 @@
+use std::ops::Range;
+
 use parley::Layout;
-use vello::kurbo::Affine;
+use vello::kurbo::{Affine, Point, Rect};
 use vello::{
     glyph::{fello::raw::FontRef, GlyphContext},
     peniko::{Brush, Color},
@@ -47,3 +49,103 @@ pub fn render_text(builder: &mut SceneBuilder, transform: Affine, layout: &Layou
         }
     }
 }
+
+/// The byte offset in the text of the cluster boundary closest to `point`, which is relative to the
+/// top left of the layout.
+pub fn hit_test(layout: &Layout<ParleyBrush>, point: Point) -> usize {
+    let mut offset = 0;
+    for line in layout.lines() {
+        let metrics = line.metrics();
+        offset = line.text_range().end;
+        let bottom = metrics.baseline + metrics.descent + metrics.leading * 0.5;
+        if (point.y as f32) > bottom {
+            continue;
+        }
+        for glyph_run in line.glyph_runs() {
+            let mut x = glyph_run.offset();
+            for cluster in glyph_run.run().visual_clusters() {
+                let range = cluster.text_range();
+                let advance = cluster.advance();
+                if (point.x as f32) < x + advance * 0.5 {
+                    return range.start;
+                } else if (point.x as f32) < x + advance {
+                    return range.end;
+                }
+                x += advance;
+            }
+        }
+        return offset;
+    }
+    offset
+}
+
+/// Rectangles covering the clusters of the text in `range`, relative to the top left of the
+/// layout.
+pub fn selection_rects(layout: &Layout<ParleyBrush>, range: Range<usize>) -> Vec<Rect> {
+    let mut rects = vec![];
+    for line in layout.lines() {
+        let metrics = line.metrics();
+        let top = metrics.baseline - metrics.ascent - metrics.leading * 0.5;
+        let bottom = metrics.baseline + metrics.descent + metrics.leading * 0.5;
+        for glyph_run in line.glyph_runs() {
+            let mut x = glyph_run.offset();
+            for cluster in glyph_run.run().visual_clusters() {
+                let advance = cluster.advance();
+                let cluster_range = cluster.text_range();
+                if cluster_range.start >= range.start && cluster_range.end <= range.end {
+                    rects.push(Rect::new(
+                        x as f64,
+                        top as f64,
+                        (x + advance) as f64,
+                        bottom as f64,
+                    ));
+                }
+                x += advance;
+            }
+        }
+    }
+    rects
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hit_test, selection_rects, ParleyBrush};
+    use parley::font::FontContext;
+    use parley::layout::{Alignment, Layout};
+    use parley::style::StyleProperty;
+    use parley::LayoutContext;
+    use vello::kurbo::Point;
+
+    fn layout(text: &str) -> Layout<ParleyBrush> {
+        let mut fcx = FontContext::new();
+        let mut lcx = LayoutContext::new();
+        let mut builder = lcx.ranged_builder(&mut fcx, text, 1.0);
+        builder.push_default(&StyleProperty::FontSize(20.0));
+        let mut layout = builder.build();
+        layout.break_all_lines(None, Alignment::Start);
+        layout
+    }
+
+    #[test]
+    fn hit_test_clusters() {
+        let layout = layout("Hello world");
+        assert_eq!(hit_test(&layout, Point::new(-5.0, 5.0)), 0);
+        assert_eq!(hit_test(&layout, Point::new(1000.0, 5.0)), 11);
+        // Points below the text are after the last line.
+        assert_eq!(hit_test(&layout, Point::new(0.0, 1000.0)), 11);
+    }
+
+    #[test]
+    fn selection_covers_clusters() {
+        let layout = layout("Hello world");
+        let rects = selection_rects(&layout, 0..5);
+        assert_eq!(rects.len(), 5);
+        assert_eq!(rects[0].x0, 0.0);
+        assert!(rects.windows(2).all(|r| r[0].x1 == r[1].x0));
+
+        // The end of the selection is found from a point near the end of its last cluster.
+        let end = Point::new(rects[4].x1 - 0.5, rects[4].center().y);
+        assert_eq!(hit_test(&layout, end), 5);
+        assert!(selection_rects(&layout, 3..3).is_empty());
+    }
+}
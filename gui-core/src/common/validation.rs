@@ -0,0 +1,17 @@
+//! Support for the checks widgets declare on their properties.
+
+use vello::peniko::Image;
+
+/// Used by the debug assertions of generated code to check the `non_empty` validation of values
+/// without their own `is_empty` function, such as images set by a separate variable function.
+/// An inherent `is_empty` function of a type is always used instead.
+#[doc(hidden)]
+pub trait IsEmpty {
+    fn is_empty(&self) -> bool;
+}
+
+impl IsEmpty for Image {
+    fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+}
@@ -0,0 +1,130 @@
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use glazier::kurbo::{Point, Rect, Vec2};
+use glazier::FileDialogToken;
+
+use crate::widget::{RuntimeID, WidgetID};
+
+/// Data carried by a drag. Drop targets check if they accept the drag by getting the payload as
+/// the type they expect.
+#[derive(Clone)]
+pub struct DragPayload(Rc<dyn Any>);
+
+impl DragPayload {
+    pub fn new<T: Any>(value: T) -> Self {
+        Self(Rc::new(value))
+    }
+
+    /// Returns the payload if it is of type `T`.
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref()
+    }
+}
+
+impl Debug for DragPayload {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DragPayload").finish_non_exhaustive()
+    }
+}
+
+/// Payloads are only equal if they are the same drag.
+impl PartialEq for DragPayload {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Sent to drop targets while a drag is over them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DragEvent {
+    /// Global position of the pointer.
+    pub pos: Point,
+    pub payload: DragPayload,
+}
+
+/// Sent to the widget that opened a file dialog with
+/// [`EventHandle::open_file_dialog`](crate::widget::EventHandle::open_file_dialog) or
+/// [`EventHandle::save_file_dialog`](crate::widget::EventHandle::save_file_dialog).
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileDialogResult {
+    pub token: FileDialogToken,
+    /// The chosen files, which is empty if the dialog was cancelled.
+    pub paths: Vec<PathBuf>,
+}
+
+/// A drag in progress, started by a widget calling
+/// [`EventHandle::start_drag`](crate::widget::EventHandle::start_drag).
+#[derive(Clone, Debug)]
+pub struct Drag {
+    pub payload: DragPayload,
+    pub source: (RuntimeID, WidgetID),
+    /// The drop target under the pointer.
+    pub target: Option<(RuntimeID, WidgetID)>,
+    /// Global position of the pointer.
+    pub pos: Point,
+    /// Size of the preview drawn under the pointer, which is the size of the source widget.
+    preview: Rect,
+    /// Offset from the top left of the source widget to where it was grabbed.
+    grab_offset: Vec2,
+}
+
+impl Drag {
+    pub fn new(
+        payload: DragPayload,
+        source: (RuntimeID, WidgetID),
+        source_rect: Rect,
+        pos: Point,
+    ) -> Self {
+        Self {
+            payload,
+            source,
+            target: None,
+            pos,
+            preview: source_rect.with_origin(Point::ZERO),
+            grab_offset: pos - source_rect.origin(),
+        }
+    }
+
+    /// Where the preview of the dragged widget is drawn, in global coordinates.
+    pub fn preview_rect(&self) -> Rect {
+        self.preview + (self.pos - self.grab_offset).to_vec2()
+    }
+
+    pub fn event(&self) -> DragEvent {
+        DragEvent {
+            pos: self.pos,
+            payload: self.payload.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Drag, DragPayload};
+    use crate::widget::{RuntimeID, WidgetID};
+    use glazier::kurbo::{Point, Rect};
+
+    #[test]
+    fn payload_type() {
+        let payload = DragPayload::new(5_usize);
+        assert_eq!(payload.get::<usize>(), Some(&5));
+        assert_eq!(payload.get::<String>(), None);
+        assert_eq!(payload, payload.clone());
+        assert_ne!(payload, DragPayload::new(5_usize));
+    }
+
+    #[test]
+    fn preview_follows_pointer() {
+        let mut drag = Drag::new(
+            DragPayload::new(()),
+            (RuntimeID::new(0), WidgetID::new(1)),
+            Rect::new(10.0, 10.0, 30.0, 20.0),
+            Point::new(15.0, 15.0),
+        );
+        drag.pos = Point::new(105.0, 55.0);
+        assert_eq!(drag.preview_rect(), Rect::new(100.0, 50.0, 120.0, 60.0));
+    }
+}
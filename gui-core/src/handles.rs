@@ -1,17 +1,40 @@
+use crate::drag::{Drag, DragPayload};
+use crate::menu::OptionList;
 use crate::positions::WidgetInfo;
 use crate::widget::{RuntimeID, Widget, WidgetEvent, WidgetID};
 use crate::{LayoutConstraints, MultiComponent, Point, Size, ToComponent};
 use glazier::kurbo::{Affine, Rect};
-use glazier::{Cursor, WindowHandle};
+use glazier::{Application, Cursor, FileDialogOptions, FileDialogToken, WindowHandle};
 use parley::FontContext;
 use vello::{SceneBuilder, SceneFragment};
 
+/// Where text copied to the clipboard is stored.
+#[derive(Clone, Debug)]
+pub enum Clipboard {
+    /// The clipboard of the operating system, used by windows.
+    System,
+    /// Text only stored by the handle, used when testing.
+    Memory(Option<String>),
+}
+
+/// How file dialogs opened by widgets are shown.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum FileDialogs {
+    /// The dialogs of the operating system, shown by the window.
+    #[default]
+    System,
+    /// Dialogs are only recorded, to be completed by the test harness.
+    Simulated,
+}
+
 /// Handle used by [`RenderHandle`], [`UpdateHandle`] and [`EventHandle`]
 #[derive(Clone)]
 pub struct Handle {
     pub fcx: FontContext,
     pub window: WindowHandle,
     pub info: WidgetInfo,
+    pub clipboard: Clipboard,
+    pub file_dialogs: FileDialogs,
 }
 
 impl Default for Handle {
@@ -20,11 +43,27 @@ impl Default for Handle {
             fcx: FontContext::new(),
             window: WindowHandle::default(),
             info: WidgetInfo::default(),
+            clipboard: Clipboard::Memory(None),
+            file_dialogs: FileDialogs::default(),
         }
     }
 }
 
 impl Handle {
+    pub fn get_clipboard(&self) -> Option<String> {
+        match &self.clipboard {
+            Clipboard::System => Application::global().clipboard().get_string(),
+            Clipboard::Memory(text) => text.clone(),
+        }
+    }
+
+    pub fn set_clipboard(&mut self, text: &str) {
+        match &mut self.clipboard {
+            Clipboard::System => Application::global().clipboard().put_string(text),
+            Clipboard::Memory(memory) => *memory = Some(text.to_string()),
+        }
+    }
+
     pub fn if_window<F: FnOnce(&mut WindowHandle)>(&mut self, f: F) {
         if self.window != WindowHandle::default() {
             f(&mut self.window);
@@ -56,6 +95,31 @@ impl Handle {
     }
 }
 
+/// Implements `with_runtime_id` for a handle with a `runtime_id` field.
+macro_rules! impl_with_runtime_id {
+    ($handle:ident $(<$param:ident>)?) => {
+        impl$(<$param: ToComponent>)? $handle<'_ $(, $param)?> {
+            /// Runs `f` with the handle acting on the widgets of another runtime ID, such as the
+            /// items of a `ForEach` widget.
+            pub fn with_runtime_id<R>(
+                &mut self,
+                runtime_id: RuntimeID,
+                f: impl FnOnce(&mut Self) -> R,
+            ) -> R {
+                let previous = std::mem::replace(&mut self.runtime_id, runtime_id);
+                let result = f(self);
+                self.runtime_id = previous;
+                result
+            }
+        }
+    };
+}
+
+impl_with_runtime_id!(UpdateHandle);
+impl_with_runtime_id!(RenderHandle<T>);
+impl_with_runtime_id!(ResizeHandle<T>);
+impl_with_runtime_id!(EventHandle<T>);
+
 pub struct UpdateHandle<'a> {
     handle: &'a mut Handle,
     runtime_id: RuntimeID,
@@ -73,6 +137,7 @@ impl<'a> UpdateHandle<'a> {
     pub fn get_fcx(&mut self) -> &mut FontContext {
         &mut self.handle.fcx
     }
+
     pub fn resize(&mut self) {
         self.resize = true;
     }
@@ -80,6 +145,12 @@ impl<'a> UpdateHandle<'a> {
         self.resize
     }
 
+    /// Removes the positions, hover and active state of the widgets of a runtime ID that no
+    /// longer exists, such as a removed item of a `ForEach` widget.
+    pub fn remove_runtime_id(&mut self, runtime_id: RuntimeID) {
+        self.handle.info.remove_runtime_id(runtime_id);
+    }
+
     pub fn invalidate_id(&mut self, id: WidgetID) {
         self.handle.invalidate_id(self.runtime_id, id)
     }
@@ -87,6 +158,17 @@ impl<'a> UpdateHandle<'a> {
     pub fn invalidate_rect(&mut self, id: WidgetID, local_rect: Rect) {
         self.handle.invalidate_rect(self.runtime_id, id, local_rect)
     }
+
+    /// Requests that the window is updated and painted again on the next frame. Returns false if
+    /// there is no window to animate.
+    pub fn request_anim_frame(&mut self) -> bool {
+        let mut requested = false;
+        self.handle.if_window(|w| {
+            w.request_anim_frame();
+            requested = true;
+        });
+        requested
+    }
 }
 
 pub struct RenderHandle<'a, T: ToComponent> {
@@ -180,6 +262,12 @@ impl<'a, T: ToComponent> RenderHandle<'a, T> {
         self.handle.info.is_hovered(self.runtime_id, id)
     }
 
+    /// Whether the widget was the last to start selecting text with
+    /// [`EventHandle::select_text`].
+    pub fn has_text_selection(&self, id: WidgetID) -> bool {
+        self.handle.info.text_selection == Some((self.runtime_id, id))
+    }
+
     pub fn get_global_rect(&self, id: WidgetID) -> Rect {
         self.handle.info.get_rect(self.runtime_id, id)
     }
@@ -215,6 +303,22 @@ impl<'a, T: ToComponent> ResizeHandle<'a, T> {
         &mut self.handle.fcx
     }
 
+    /// Allows the widget to be sent drag events when a drag is over it.
+    pub fn add_drop_target(&mut self, id: WidgetID) {
+        self.handle.info.add_drop_target(self.runtime_id, id);
+    }
+
+    /// Marks a widget as hidden until the next layout, so that its parent can leave it out
+    /// instead of laying out an empty widget.
+    pub fn hide_widget(&mut self, id: WidgetID) {
+        self.handle.info.hide_widget(self.runtime_id, id);
+    }
+
+    /// Whether a child hid itself with [`hide_widget`](Self::hide_widget) when it was resized.
+    pub fn is_hidden(&self, id: WidgetID) -> bool {
+        self.handle.info.is_hidden(self.runtime_id, id)
+    }
+
     pub fn position_widget(&mut self, rect: Rect, child_id: WidgetID) {
         self.handle
             .info
@@ -355,6 +459,89 @@ impl<'a, T: ToComponent> EventHandle<'a, T> {
         self.handle.info.add_hover(self.runtime_id, id)
     }
 
+    pub fn get_clipboard(&self) -> Option<String> {
+        self.handle.get_clipboard()
+    }
+
+    pub fn set_clipboard(&mut self, text: &str) {
+        self.handle.set_clipboard(text)
+    }
+
+    /// Called when the widget starts selecting text, so that the widget that had the selection
+    /// before can clear its selection. The previous widget is invalidated.
+    pub fn select_text(&mut self, id: WidgetID) {
+        let owner = (self.runtime_id, id);
+        if let Some(previous) = self.handle.info.text_selection.replace(owner) {
+            if previous != owner {
+                self.handle.invalidate_id(previous.0, previous.1);
+            }
+        }
+    }
+
+    /// Whether the widget was the last to start selecting text with
+    /// [`select_text`](Self::select_text).
+    pub fn has_text_selection(&self, id: WidgetID) -> bool {
+        self.handle.info.text_selection == Some((self.runtime_id, id))
+    }
+
+    /// Opens a dialog to choose files to open. The chosen files are sent to the widget as a
+    /// [`WidgetEvent::FileDialog`] with the returned token.
+    pub fn open_file_dialog(
+        &mut self,
+        id: WidgetID,
+        options: FileDialogOptions,
+    ) -> Option<FileDialogToken> {
+        self.file_dialog(id, |w| w.open_file(options))
+    }
+
+    /// Opens a dialog to choose where to save a file. The chosen file is sent to the widget as a
+    /// [`WidgetEvent::FileDialog`] with the returned token.
+    pub fn save_file_dialog(
+        &mut self,
+        id: WidgetID,
+        options: FileDialogOptions,
+    ) -> Option<FileDialogToken> {
+        self.file_dialog(id, |w| w.save_as(options))
+    }
+
+    fn file_dialog(
+        &mut self,
+        id: WidgetID,
+        open: impl FnOnce(&mut WindowHandle) -> Option<FileDialogToken>,
+    ) -> Option<FileDialogToken> {
+        let token = match self.handle.file_dialogs {
+            FileDialogs::System => open(&mut self.handle.window),
+            FileDialogs::Simulated => Some(FileDialogToken::next()),
+        }?;
+        self.handle
+            .info
+            .add_file_dialog(token, (self.runtime_id, id));
+        Some(token)
+    }
+
+    /// Opens a list of `options` below the widget, highlighting the `selected` option. The
+    /// chosen option is sent to the widget as a [`WidgetEvent::OptionChosen`], and nothing is sent
+    /// if the list is closed without choosing one.
+    pub fn open_option_list(
+        &mut self,
+        id: WidgetID,
+        options: Vec<String>,
+        selected: Option<usize>,
+    ) {
+        self.handle.info.option_list = Some(OptionList {
+            owner: (self.runtime_id, id),
+            options,
+            selected,
+        });
+    }
+
+    /// Starts dragging `payload` from the widget, which was grabbed at the global point `pos`.
+    /// Should be called in response to a [`WidgetEvent::DragStart`].
+    pub fn start_drag(&mut self, id: WidgetID, payload: DragPayload, pos: Point) {
+        let rect = self.get_global_rect(id);
+        self.handle.info.drag = Some(Drag::new(payload, (self.runtime_id, id), rect, pos));
+    }
+
     pub fn is_active(&self, id: WidgetID) -> bool {
         self.handle.info.is_active(self.runtime_id, id)
     }
@@ -383,6 +570,14 @@ mod test {
         assert!(!called);
     }
 
+    #[test]
+    fn memory_clipboard() {
+        let mut handle = Handle::default();
+        assert_eq!(handle.get_clipboard(), None);
+        handle.set_clipboard("copied");
+        assert_eq!(handle.get_clipboard().as_deref(), Some("copied"));
+    }
+
     #[test]
     fn update_handle_resize() {
         let mut handle = Handle::default();
@@ -390,4 +585,14 @@ mod test {
         update_handle.resize();
         assert!(update_handle.unwrap());
     }
+
+    #[test]
+    fn update_handle_with_runtime_id() {
+        let mut handle = Handle::default();
+        let (first, second) = (RuntimeID::next(), RuntimeID::next());
+        let mut update_handle = UpdateHandle::new(&mut handle, first);
+        let inner = update_handle.with_runtime_id(second, |h| h.runtime_id);
+        assert_eq!(inner, second);
+        assert_eq!(update_handle.runtime_id, first);
+    }
 }
@@ -2,6 +2,7 @@ use std::any::Any;
 
 pub use glazier;
 pub use glazier::kurbo::Point;
+use glazier::KeyEvent;
 pub use parley;
 pub use parley::font::FontContext;
 pub use vello;
@@ -15,16 +16,23 @@ pub use single_or_multi::{Children, MutWidgetChildren, WidgetChildren};
 
 pub use crate::comp_holder::CompHolder;
 use crate::handles::Handle;
+use crate::menu::Menu;
 pub use crate::parse::fluent::Fluent;
 use crate::widget::{RuntimeID, WidgetEvent, WidgetID};
+use crate::window::WindowConfig;
 
+pub mod animation;
 pub mod common;
+pub mod drag;
 pub mod parse;
+pub mod theme;
 pub mod widget;
+pub mod window;
 
 mod comp_holder;
 mod handles;
 pub mod layout;
+pub mod menu;
 mod positions;
 mod single_or_multi;
 
@@ -61,6 +69,24 @@ pub trait Component {
         event: WidgetEvent,
         handle: &mut Handle,
     ) -> bool;
+    /// Runs the handler of the first shortcut matching the key event, trying the components
+    /// held by this component before its own shortcuts. Returns true if a handler was run.
+    fn shortcut(&mut self, event: &KeyEvent) -> bool;
+    /// The settings from the `window` section of the layout file, which are only used for the
+    /// component of the window.
+    fn window_config(&self) -> WindowConfig;
+    /// The menus of the menu bar, which is only used for the component of the window.
+    fn menu_bar(&self) -> Option<Menu>;
+    /// The context menu of a widget, or `None` if it does not have one.
+    fn context_menu(&self, runtime_id: RuntimeID, widget_id: WidgetID) -> Option<Menu>;
+    /// The text of the tooltip of a widget, or `None` if it does not have one.
+    fn tooltip(&self, runtime_id: RuntimeID, widget_id: WidgetID) -> Option<String>;
+    /// Runs the menu handler `id` of the component with `runtime_id`. Returns true if the
+    /// component was found.
+    fn menu_command(&mut self, runtime_id: RuntimeID, id: u32) -> bool;
+    /// Runs `f` with the struct of the component with `runtime_id`. Returns true if the
+    /// component was found.
+    fn with_comp_struct(&mut self, runtime_id: RuntimeID, f: &mut dyn FnMut(&mut dyn Any)) -> bool;
     /// Get the component's ID.
     fn id(&self) -> RuntimeID;
 }
@@ -107,6 +133,12 @@ pub trait MultiComponent {
         widget_id: WidgetID,
     ) -> Option<(RuntimeID, WidgetID)>;
     fn get_id(&self, name: &str) -> Option<(RuntimeID, WidgetID)>;
+    /// Runs the first matching shortcut of any component.
+    fn shortcut(&mut self, event: &KeyEvent) -> bool;
+    fn context_menu(&self, runtime_id: RuntimeID, widget_id: WidgetID) -> Option<Menu>;
+    fn tooltip(&self, runtime_id: RuntimeID, widget_id: WidgetID) -> Option<String>;
+    fn menu_command(&mut self, runtime_id: RuntimeID, id: u32) -> bool;
+    fn with_comp_struct(&mut self, runtime_id: RuntimeID, f: &mut dyn FnMut(&mut dyn Any)) -> bool;
 }
 
 /// Trait that is derived on user-defined components using the derive macro.
@@ -136,6 +168,16 @@ pub trait ToHandler {
     type BaseHandler: ToComponent;
 }
 
+/// Trait implemented by components for each handler of their `shortcuts`.
+pub trait ShortcutHandler<T: ToHandler<BaseHandler = Self>> {
+    fn on_shortcut(&mut self);
+}
+
+/// Trait implemented by components for each handler of the items in their `menus`.
+pub trait MenuHandler<T: ToHandler<BaseHandler = Self>> {
+    fn on_select(&mut self);
+}
+
 /// Trait that broadcast the value of a variable back to the component.
 pub trait Update<T: Variable> {
     /// Decides when a variable should be re-evaluated and `value` run.
@@ -146,6 +188,13 @@ pub trait Update<T: Variable> {
     fn reset(&mut self) {}
 }
 
+/// Trait used to store the value of a variable bound to a widget property when the widget changes
+/// it. This is implemented by the derive macro for fields of bound variables, which must have a
+/// `set_value` function like `Updateable`.
+pub trait Bind<T: Variable>: Update<T> {
+    fn set_value(&mut self, value: T::VarType);
+}
+
 /// Trait used to gather information about the type of different user-defined components using the `type_registry` macro.
 pub trait ComponentTypeInfo {
     type ToComponent: ToComponent;
@@ -0,0 +1,91 @@
+use crate::widget::{RuntimeID, WidgetID};
+
+/// A menu built by a component, with its text already translated. It is shown as a native menu
+/// when there is a window, or drawn by the framework otherwise.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Menu {
+    pub items: Vec<MenuItem>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MenuItem {
+    /// Runs the menu handler `id` of the component with `runtime_id` when selected.
+    Item {
+        runtime_id: RuntimeID,
+        id: u32,
+        text: String,
+    },
+    Submenu {
+        text: String,
+        menu: Menu,
+    },
+    Separator,
+}
+
+impl MenuItem {
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            MenuItem::Item { text, .. } | MenuItem::Submenu { text, .. } => Some(text),
+            MenuItem::Separator => None,
+        }
+    }
+}
+
+impl Menu {
+    /// Finds the item at the end of `path`, following the text of submenus.
+    pub fn find(&self, path: &[&str]) -> Option<&MenuItem> {
+        let (first, rest) = path.split_first()?;
+        let item = self.items.iter().find(|i| i.text() == Some(*first))?;
+        match item {
+            MenuItem::Submenu { menu, .. } if !rest.is_empty() => menu.find(rest),
+            _ if rest.is_empty() => Some(item),
+            _ => None,
+        }
+    }
+}
+
+/// A list of options opened by a widget with
+/// [`EventHandle::open_option_list`](crate::widget::EventHandle::open_option_list), such as the
+/// options of a `Select`. The window draws the list above every widget.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OptionList {
+    /// The widget that opened the list, which is sent the chosen option as a
+    /// [`WidgetEvent::OptionChosen`](crate::widget::WidgetEvent::OptionChosen).
+    pub owner: (RuntimeID, WidgetID),
+    pub options: Vec<String>,
+    /// The option highlighted when the list is opened.
+    pub selected: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Menu, MenuItem};
+    use crate::widget::RuntimeID;
+
+    #[test]
+    fn find_item() {
+        let open = MenuItem::Item {
+            runtime_id: RuntimeID::new(0),
+            id: 0,
+            text: "Open".into(),
+        };
+        let menu = Menu {
+            items: vec![
+                MenuItem::Submenu {
+                    text: "File".into(),
+                    menu: Menu {
+                        items: vec![MenuItem::Separator, open.clone()],
+                    },
+                },
+                MenuItem::Separator,
+            ],
+        };
+        assert_eq!(menu.find(&["File", "Open"]), Some(&open));
+        assert!(matches!(
+            menu.find(&["File"]),
+            Some(MenuItem::Submenu { .. })
+        ));
+        assert_eq!(menu.find(&["Open"]), None);
+        assert_eq!(menu.find(&["File", "Open", "Recent"]), None);
+    }
+}
@@ -1,25 +1,122 @@
-use serde::Deserialize;
-
-use crate::parse::var::Name;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+use serde_yaml::{Mapping, Value};
+
+use crate::animation::Easing;
+use crate::parse::fluent::Fluent;
+use crate::parse::shortcut::KeyChord;
+use crate::parse::var::{ComponentPath, Name, Var};
 use crate::widget::WidgetBuilder;
 
 pub mod colour;
+pub mod list;
+pub mod shortcut;
 pub mod var;
 
 pub mod fluent;
 
-#[derive(Deserialize, Debug, Clone)]
-#[serde(rename_all = "camelCase")]
+/// A widget in a layout file. Instead of a `widget`, a declaration can contain an `if` block
+/// with `then` and `else` subtrees, or a `forEach` block with a `template`, which are shorthand
+/// for an `If` or `ForEach` widget with those properties.
+#[derive(Debug, Clone)]
 pub struct WidgetDeclaration {
     pub name: Option<Name>,
-    #[serde(flatten)]
+    /// The style classes applied to this widget, later classes take precedence over earlier ones.
+    pub style: Vec<Name>,
     pub widget: Box<dyn WidgetBuilder>,
     pub layout_properties: Option<LayoutDeclaration>,
+    /// Action run when the widget is pressed, implemented by a generated handler.
+    pub on_press: Option<Action>,
+    /// The menu of the component opened when the widget is pressed with the secondary button.
+    pub context_menu: Option<Name>,
+    /// Text shown near the widget after the pointer rests over it.
+    pub tooltip: Option<Fluent>,
+}
+
+impl<'de> Deserialize<'de> for WidgetDeclaration {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct RawWidgetDeclaration {
+            name: Option<Name>,
+            #[serde(default, deserialize_with = "one_or_many")]
+            style: Vec<Name>,
+            layout_properties: Option<LayoutDeclaration>,
+            on_press: Option<Action>,
+            context_menu: Option<Name>,
+            tooltip: Option<Fluent>,
+            #[serde(flatten)]
+            widget: Mapping,
+        }
+
+        let raw = RawWidgetDeclaration::deserialize(deserializer)?;
+        let shorthand = ["if", "forEach"]
+            .into_iter()
+            .zip(["If", "ForEach"])
+            .find(|(key, _)| raw.widget.contains_key(key));
+        let widget = match shorthand {
+            Some((_, widget)) => Mapping::from_iter([
+                ("widget".into(), widget.into()),
+                ("properties".into(), Value::Mapping(raw.widget)),
+            ]),
+            None => raw.widget,
+        };
+
+        Ok(Self {
+            name: raw.name,
+            style: raw.style,
+            widget: Deserialize::deserialize(Value::Mapping(widget)).map_err(D::Error::custom)?,
+            layout_properties: raw.layout_properties,
+            on_press: raw.on_press,
+            context_menu: raw.context_menu,
+            tooltip: raw.tooltip,
+        })
+    }
+}
+
+/// An action that can be run by a generated handler instead of Rust code.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum Action {
+    /// Switch the component to the given state.
+    SetState(Name),
 }
 
-#[derive(Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+/// Deserializes either a single name or a list of names.
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Name>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(Name),
+        Many(Vec<Name>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(name) => vec![name],
+        OneOrMany::Many(names) => names,
+    })
+}
+
+/// Widgets created for each item of a `Vec` variable.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ForEachDeclaration {
+    /// The variable holding the items.
+    #[serde(rename = "forEach")]
+    pub items: Name,
+    /// The field of each item used to match items when the list changes, otherwise items are
+    /// matched by their index.
+    pub key: Option<Name>,
+    /// The widget created for each item, which can use `$item` and `$index` in fluent text.
+    pub template: Box<WidgetDeclaration>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, Eq, PartialEq)]
 pub struct LayoutDeclaration {
+    #[serde(default)]
     pub padding: u32,
+    /// Hidden widgets keep their state but are not laid out or rendered.
+    pub visible: Option<Var<bool>>,
 }
 
 #[derive(Deserialize, Debug, Eq, PartialEq, Hash, Clone)]
@@ -27,12 +124,15 @@ pub struct NormalVariableDeclaration {
     pub name: Name,
     #[serde(rename = "type")]
     pub var_type: String,
+    /// A Rust expression using other variables of the component. The component does not need to
+    /// implement `Update` for variables with an expression.
+    pub expr: Option<String>,
 }
 
 #[derive(Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
 pub struct ComponentVariableDeclaration {
     pub name: Name,
-    pub component: String,
+    pub component: ComponentPath,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -84,15 +184,99 @@ impl VariableDeclaration {
 }
 
 #[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct ComponentDeclaration {
     pub name: Name,
     #[serde(default)]
     pub variables: Vec<VariableDeclaration>,
     #[serde(default)]
     pub states: Vec<StateDeclaration>,
+    /// Key chords that run a handler while the component is mounted, whichever widget has focus.
+    #[serde(default)]
+    pub shortcuts: Vec<ShortcutDeclaration>,
+    #[serde(default)]
+    pub menus: Vec<MenuDeclaration>,
+    /// The menus shown in the menu bar of the window, if this is the component of the window.
+    #[serde(default)]
+    pub menu_bar: Vec<Name>,
+    /// The settings of the window, if this is the component of the window.
+    pub window: Option<WindowDeclaration>,
     pub child: WidgetDeclaration,
 }
 
+/// The settings of the window showing a component. Sizes are given as `[width, height]`.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct WindowDeclaration {
+    pub title: Option<Fluent>,
+    pub size: Option<(f64, f64)>,
+    pub min_size: Option<(f64, f64)>,
+    pub max_size: Option<(f64, f64)>,
+    pub resizable: Option<bool>,
+    pub transparent: Option<bool>,
+}
+
+/// A menu used in the menu bar or as the context menu of widgets.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct MenuDeclaration {
+    pub name: Name,
+    /// The title of the menu in the menu bar.
+    pub text: Option<Fluent>,
+    pub items: Vec<MenuItemDeclaration>,
+}
+
+/// An item of a menu, which is either an item with a handler, a submenu or the string
+/// `separator`.
+#[derive(Debug, Clone)]
+pub enum MenuItemDeclaration {
+    /// Runs the `MenuHandler` implemented for `handler` when selected.
+    Item {
+        handler: Name,
+        text: Fluent,
+    },
+    Submenu {
+        text: Fluent,
+        items: Vec<MenuItemDeclaration>,
+    },
+    Separator,
+}
+
+impl<'de> Deserialize<'de> for MenuItemDeclaration {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawMenuItem {
+            Separator(String),
+            Submenu {
+                text: Fluent,
+                items: Vec<MenuItemDeclaration>,
+            },
+            Item {
+                handler: Name,
+                text: Fluent,
+            },
+        }
+
+        match RawMenuItem::deserialize(deserializer)? {
+            RawMenuItem::Separator(s) if s == "separator" => Ok(Self::Separator),
+            RawMenuItem::Separator(s) => Err(D::Error::custom(format!(
+                "Expected a menu item or separator but found {s}"
+            ))),
+            RawMenuItem::Submenu { text, items } => Ok(Self::Submenu { text, items }),
+            RawMenuItem::Item { handler, text } => Ok(Self::Item { handler, text }),
+        }
+    }
+}
+
+/// Runs the `ShortcutHandler` implemented for `handler` when `keys` are pressed.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
+#[serde(deny_unknown_fields)]
+pub struct ShortcutDeclaration {
+    pub keys: KeyChord,
+    pub handler: Name,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct StateWidgetDeclaration {
@@ -106,12 +290,41 @@ pub struct StateWidgetDeclaration {
 pub struct StateDeclaration {
     pub name: Name,
     pub overrides: Vec<StateWidgetDeclaration>,
+    /// Properties that are animated when switching to this state.
+    #[serde(default)]
+    pub transitions: Vec<TransitionDeclaration>,
+}
+
+/// Animates the given properties of a named widget when switching to a state.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
+#[serde(deny_unknown_fields)]
+pub struct TransitionDeclaration {
+    pub name: Name,
+    #[serde(deserialize_with = "one_or_many")]
+    pub properties: Vec<Name>,
+    /// The duration of the transition in milliseconds.
+    pub duration: u64,
+    #[serde(default)]
+    pub easing: Easing,
+}
+
+/// A style applied to widgets of a given type. Unnamed styles are applied to all widgets of that
+/// type, whereas named styles (style classes) are only applied to widgets that use them.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StyleDeclaration {
+    pub name: Option<Name>,
+    /// The style classes this class extends, later classes take precedence over earlier ones.
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub extends: Vec<Name>,
+    #[serde(flatten)]
+    pub widget: Box<dyn WidgetBuilder>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct GUIDeclaration {
     #[serde(default)]
-    pub styles: Vec<Box<dyn WidgetBuilder>>,
+    pub styles: Vec<StyleDeclaration>,
     pub components: Vec<ComponentDeclaration>,
 }
 
@@ -122,7 +335,7 @@ mod test {
 
     use crate::parse::fluent::Fluent;
     use crate::parse::var::Name;
-    use crate::parse::GUIDeclaration;
+    use crate::parse::{Action, GUIDeclaration, MenuItemDeclaration};
     use crate::widget::{WidgetBuilder, WidgetID};
     use crate::{MutWidgetChildren, WidgetChildren};
 
@@ -202,6 +415,9 @@ components:
         component: Component3
       - name: variable
         type: u32
+    shortcuts:
+      - keys: Ctrl+S
+        handler: Save
     child:
       widget: FakeWidget
       properties:
@@ -228,6 +444,8 @@ components:
       widget: FakeWidget
       properties:
         number: 4
+      onPress:
+        setState: State2
         "#;
 
         let decl = serde_yaml::from_str::<GUIDeclaration>(yaml).unwrap();
@@ -250,13 +468,25 @@ components:
         // Assert that the first variable of the first component is a component variable
         let first_variable = decl.components[0].variables[0].get_component().unwrap();
         assert_eq!(first_variable.name, "component_variable".parse().unwrap());
-        assert_eq!(first_variable.component, "Component3");
+        assert_eq!(first_variable.component, "Component3".parse().unwrap());
 
         // Assert that the second variable of the first component is a normal variable
         let second_variable = decl.components[0].variables[1].get_normal().unwrap();
         assert_eq!(second_variable.name, "variable".parse().unwrap());
         assert_eq!(second_variable.var_type, "u32");
 
+        // Assert that the first component saves with Ctrl+S
+        let shortcut = &decl.components[0].shortcuts[0];
+        assert_eq!(shortcut.keys, "Ctrl+S".parse().unwrap());
+        assert_eq!(shortcut.handler, "Save".parse().unwrap());
+        assert!(decl.components[1].shortcuts.is_empty());
+
+        // Assert that the child of the second component switches state when pressed
+        assert_eq!(
+            decl.components[1].child.on_press,
+            Some(Action::SetState("State2".parse().unwrap()))
+        );
+
         // Assert that the first state of the second component has the correct name and overrides
         let first_state = &decl.components[1].states[0];
         assert_eq!(first_state.name, "State1".parse().unwrap());
@@ -312,11 +542,128 @@ components:
 
         // Assert that the styling is correct
         assert_eq!(decl.styles.len(), 1);
+        assert_eq!(decl.styles[0].name, None);
         let style = decl.styles[0]
+            .widget
             .as_ref()
             .as_any()
             .downcast_ref::<FakeWidget>()
             .unwrap();
         assert_eq!(style.number, Some(20));
     }
+
+    #[test]
+    pub fn test_style_classes() {
+        let yaml = r#"
+styles:
+  - name: base
+    widget: FakeWidget
+    properties:
+      number: 1
+  - name: danger
+    extends: base
+    widget: FakeWidget
+    properties:
+      number: 2
+components:
+  - name: Component
+    child:
+      widget: FakeWidget
+      style: [base, danger]
+      properties:
+        number: 3
+        "#;
+
+        let decl = serde_yaml::from_str::<GUIDeclaration>(yaml).unwrap();
+
+        assert_eq!(decl.styles[0].name, Some("base".parse().unwrap()));
+        assert!(decl.styles[0].extends.is_empty());
+        assert_eq!(decl.styles[1].name, Some("danger".parse().unwrap()));
+        assert_eq!(decl.styles[1].extends, vec!["base".parse().unwrap()]);
+        assert_eq!(
+            decl.components[0].child.style,
+            vec!["base".parse().unwrap(), "danger".parse().unwrap()]
+        );
+    }
+
+    #[test]
+    pub fn test_menus() {
+        let yaml = r#"
+components:
+  - name: Editor
+    menuBar: [File]
+    menus:
+      - name: File
+        text: File
+        items:
+          - handler: Open
+            text: Open
+          - separator
+          - text: Recent
+            items:
+              - handler: OpenLast
+                text: Last file
+    child:
+      widget: FakeWidget
+      properties:
+        number: 1
+      contextMenu: File
+      tooltip: The file menu
+        "#;
+
+        let decl = serde_yaml::from_str::<GUIDeclaration>(yaml).unwrap();
+        let component = &decl.components[0];
+        assert_eq!(component.menu_bar, vec!["File".parse().unwrap()]);
+        assert_eq!(component.child.context_menu, Some("File".parse().unwrap()));
+        assert_eq!(
+            component.child.tooltip.as_ref().unwrap().text,
+            "The file menu"
+        );
+
+        let menu = &component.menus[0];
+        assert_eq!(menu.text.as_ref().unwrap().text, "File");
+        assert!(
+            matches!(&menu.items[0], MenuItemDeclaration::Item { handler, .. } if handler.as_str() == "Open")
+        );
+        assert!(matches!(menu.items[1], MenuItemDeclaration::Separator));
+        let MenuItemDeclaration::Submenu { text, items } = &menu.items[2] else {
+            panic!("Expected a submenu");
+        };
+        assert_eq!(text.text, "Recent");
+        assert_eq!(items.len(), 1);
+
+        let invalid = yaml.replace("- separator", "- divider");
+        assert!(serde_yaml::from_str::<GUIDeclaration>(&invalid).is_err());
+    }
+
+    #[test]
+    pub fn test_window() {
+        let yaml = r#"
+components:
+  - name: Editor
+    window:
+      title: Editor
+      size: [800, 600]
+      minSize: [400, 300.5]
+      resizable: false
+    child:
+      widget: FakeWidget
+      properties:
+        number: 1
+        "#;
+
+        let decl = serde_yaml::from_str::<GUIDeclaration>(yaml).unwrap();
+        let window = decl.components[0].window.as_ref().unwrap();
+        assert_eq!(window.title.as_ref().unwrap().text, "Editor");
+        assert_eq!(window.size, Some((800.0, 600.0)));
+        assert_eq!(window.min_size, Some((400.0, 300.5)));
+        assert_eq!(window.max_size, None);
+        assert_eq!(window.resizable, Some(false));
+
+        let invalid = yaml.replace("resizable", "fullscreen");
+        assert!(serde_yaml::from_str::<GUIDeclaration>(&invalid).is_err());
+        // Glazier cannot set the icon of a window yet.
+        let icon = yaml.replace("resizable: false", "icon: icon.png");
+        assert!(serde_yaml::from_str::<GUIDeclaration>(&icon).is_err());
+    }
 }
@@ -0,0 +1,45 @@
+use crate::parse::fluent::Fluent;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use serde::Deserialize;
+
+/// A list of fluent strings declared in a layout file, such as the options of a `Select`. Widgets
+/// usually take `Into<Vec<String>>` so the list can also be set by a `Vec<String>` variable.
+///
+/// The generated code translates each string, so the tokens of the list are only the untranslated
+/// fallback.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize)]
+#[serde(transparent)]
+pub struct StringList(pub Vec<Fluent>);
+
+impl ToTokens for StringList {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let items = self.0.iter().map(|f| &f.text);
+        tokens.extend(quote!(
+            ::std::vec![#(::std::string::String::from(#items)),*]
+        ))
+    }
+}
+
+impl From<StringList> for Vec<String> {
+    fn from(value: StringList) -> Self {
+        value.0.into_iter().map(|f| f.text).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StringList;
+    use quote::ToTokens;
+
+    #[test]
+    fn string_list_to_tokens() {
+        let list: StringList = serde_yaml::from_str("[Small, \"Size { $size }\"]").unwrap();
+        assert_eq!(list.0[0].text, "Small");
+        assert_eq!(list.0[1].vars.len(), 1);
+        assert_eq!(
+            list.to_token_stream().to_string(),
+            ":: std :: vec ! [:: std :: string :: String :: from (\"Small\") , :: std :: string :: String :: from (\"Size { $size }\")]"
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use glazier::KeyEvent;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+
+/// A key chord such as `Ctrl+S` or `Ctrl+Shift+Z`, written as modifiers followed by a key and
+/// separated by `+`. Keys are either a single character or the name of a key such as `Enter`,
+/// `Escape` or `F5`. The `Mod` modifier is `Cmd` on macOS and `Ctrl` on other platforms.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct KeyChord {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub meta: bool,
+    /// `Cmd` on macOS and `Ctrl` otherwise, which is decided when matching.
+    pub command: bool,
+    pub key: String,
+}
+
+impl KeyChord {
+    /// Returns true if the modifiers of the event are exactly those of the chord, and the key
+    /// is the same ignoring case.
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        let (ctrl, meta) = self.ctrl_meta(cfg!(target_os = "macos"));
+        event.mods.ctrl() == ctrl
+            && event.mods.meta() == meta
+            && event.mods.shift() == self.shift
+            && event.mods.alt() == self.alt
+            && event.key.to_string().eq_ignore_ascii_case(&self.key)
+    }
+
+    /// Returns true if both chords match the same key presses on any platform, such as
+    /// `Mod+S` and `Ctrl+S`, or `Ctrl+S` and `Ctrl+s`.
+    pub fn overlaps(&self, other: &KeyChord) -> bool {
+        self.shift == other.shift
+            && self.alt == other.alt
+            && self.key.eq_ignore_ascii_case(&other.key)
+            && [true, false]
+                .into_iter()
+                .any(|macos| self.ctrl_meta(macos) == other.ctrl_meta(macos))
+    }
+
+    /// The `Ctrl` and `Cmd` modifiers of the chord, with `Mod` resolved for the platform.
+    fn ctrl_meta(&self, macos: bool) -> (bool, bool) {
+        if macos {
+            (self.ctrl, self.meta || self.command)
+        } else {
+            (self.ctrl || self.command, self.meta)
+        }
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // A chord ending in `++` uses the plus key.
+        let (modifiers, key) = match s.strip_suffix("++") {
+            Some(modifiers) => (modifiers, "+"),
+            None => s.rsplit_once('+').unwrap_or(("", s)),
+        };
+        let key = match key.trim().to_ascii_lowercase().as_str() {
+            "" => return Err(format!("Missing key in shortcut {s}")),
+            "esc" => "Escape",
+            "space" => " ",
+            "plus" => "+",
+            "del" => "Delete",
+            "up" => "ArrowUp",
+            "down" => "ArrowDown",
+            "left" => "ArrowLeft",
+            "right" => "ArrowRight",
+            _ => key.trim(),
+        };
+
+        let mut chord = KeyChord {
+            key: key.to_string(),
+            ..Default::default()
+        };
+        for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+            let flag = match modifier.trim().to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => &mut chord.ctrl,
+                "shift" => &mut chord.shift,
+                "alt" | "option" => &mut chord.alt,
+                "cmd" | "meta" | "super" => &mut chord.meta,
+                "mod" => &mut chord.command,
+                _ => return Err(format!("Unknown modifier {modifier} in shortcut {s}")),
+            };
+            if *flag {
+                return Err(format!("Modifier {modifier} is repeated in shortcut {s}"));
+            }
+            *flag = true;
+        }
+        Ok(chord)
+    }
+}
+
+impl Display for KeyChord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let modifiers = [
+            (self.command, "Mod"),
+            (self.ctrl, "Ctrl"),
+            (self.meta, "Cmd"),
+            (self.alt, "Alt"),
+            (self.shift, "Shift"),
+        ];
+        for (_, name) in modifiers.into_iter().filter(|(set, _)| *set) {
+            write!(f, "{name}+")?;
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyChord {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+impl ToTokens for KeyChord {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let KeyChord {
+            ctrl,
+            shift,
+            alt,
+            meta,
+            command,
+            key,
+        } = self;
+        tokens.extend(quote! {
+            ::gui::gui_core::parse::shortcut::KeyChord {
+                ctrl: #ctrl,
+                shift: #shift,
+                alt: #alt,
+                meta: #meta,
+                command: #command,
+                key: #key.to_string(),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeyChord;
+
+    #[test]
+    fn parse_chords() {
+        let chord: KeyChord = "Ctrl+Shift+Z".parse().unwrap();
+        assert!(chord.ctrl && chord.shift && !chord.alt && !chord.meta && !chord.command);
+        assert_eq!(chord.key, "Z");
+
+        let chord: KeyChord = "Mod+Esc".parse().unwrap();
+        assert!(chord.command && !chord.ctrl);
+        assert_eq!(chord.key, "Escape");
+
+        let chord: KeyChord = "Ctrl++".parse().unwrap();
+        assert_eq!(chord.key, "+");
+        assert_eq!(chord.to_string(), "Ctrl++");
+
+        assert_eq!("F5".parse::<KeyChord>().unwrap().key, "F5");
+        assert!("Ctrl+".parse::<KeyChord>().is_err());
+        assert!("Hyper+S".parse::<KeyChord>().is_err());
+        assert!("Ctrl+Ctrl+S".parse::<KeyChord>().is_err());
+    }
+
+    #[test]
+    fn overlapping_chords() {
+        let chord = |s: &str| s.parse::<KeyChord>().unwrap();
+        assert!(chord("Mod+S").overlaps(&chord("Ctrl+S")));
+        assert!(chord("Mod+S").overlaps(&chord("Cmd+S")));
+        assert!(chord("Ctrl+S").overlaps(&chord("ctrl+s")));
+        assert!(!chord("Ctrl+S").overlaps(&chord("Cmd+S")));
+        assert!(!chord("Mod+S").overlaps(&chord("Mod+Shift+S")));
+        assert!(!chord("Mod+S").overlaps(&chord("Ctrl+Cmd+S")));
+    }
+
+    #[test]
+    fn deserialize_chord() {
+        let chord: KeyChord = serde_yaml::from_str("Alt+Enter").unwrap();
+        assert_eq!(chord.to_string(), "Alt+Enter");
+        assert!(serde_yaml::from_str::<KeyChord>("Ctrl+Hyper+S").is_err());
+    }
+}
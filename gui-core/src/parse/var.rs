@@ -1,20 +1,57 @@
 use proc_macro2::Ident;
-use serde::de::{Error, Unexpected, Visitor};
+use serde::de::{DeserializeOwned, Error, Unexpected, Visitor};
 use serde::{Deserialize, Deserializer};
+use serde_yaml::Value;
 use std::fmt::{Debug, Display, Formatter};
 use std::ops::Deref;
 use std::str::FromStr;
 use syn::__private::Span;
 
-/// A variable that can either contain a variable declaration or a value
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq)]
-#[serde(rename_all = "camelCase")]
+/// A variable that can either contain a variable declaration, a token of the current
+/// [`Theme`](crate::theme::Theme) or a value
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Var<T> {
     Variable(Name),
-    #[serde(untagged)]
+    Theme(Name),
+    /// A variable that is also set when the widget changes the property, only supported by
+    /// properties that can be bound.
+    Binding(Name),
     Value(T),
 }
 
+/// Values are deserialized separately, rather than as an untagged variant, so that errors from
+/// the value (such as listing the variants of an enum) are not replaced by a generic error.
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Var<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        enum Tagged {
+            Variable(Name),
+            Theme(Name),
+            Binding(Name),
+        }
+
+        let value = Value::deserialize(deserializer)?;
+        let is_tagged = matches!(&value, Value::Mapping(m) if m.len() == 1
+            && m.keys().all(|k| matches!(k.as_str(), Some("variable" | "theme" | "binding"))));
+        if !is_tagged {
+            return T::deserialize(value)
+                .map(Var::Value)
+                .map_err(D::Error::custom);
+        }
+        Ok(
+            match Tagged::deserialize(value).map_err(D::Error::custom)? {
+                Tagged::Variable(name) => Var::Variable(name),
+                Tagged::Theme(name) => Var::Theme(name),
+                Tagged::Binding(name) => Var::Binding(name),
+            },
+        )
+    }
+}
+
 #[derive(Deserialize, Debug, Clone, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum ComponentVar {
@@ -131,9 +168,75 @@ impl<'de> Deserialize<'de> for Name {
     }
 }
 
+/// A path to a user-defined component. Components defined in the current crate are referred to by
+/// their name, while components exported by another crate (using `type_registry!(export)`) are
+/// prefixed with the name of that crate: `my_widgets::DatePicker`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ComponentPath {
+    pub crate_name: Option<Name>,
+    pub name: Name,
+}
+
+impl Display for ComponentPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(crate_name) = &self.crate_name {
+            write!(f, "{crate_name}::")?;
+        }
+        Display::fmt(&self.name, f)
+    }
+}
+
+impl FromStr for ComponentPath {
+    type Err = syn::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once("::") {
+            None => Ok(ComponentPath {
+                crate_name: None,
+                name: s.parse()?,
+            }),
+            Some((crate_name, name)) => Ok(ComponentPath {
+                crate_name: Some(crate_name.parse()?),
+                name: name.parse()?,
+            }),
+        }
+    }
+}
+
+struct ComponentPathVisitor;
+
+impl<'de> Visitor<'de> for ComponentPathVisitor {
+    type Value = ComponentPath;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a component name optionally prefixed by a crate name")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        v.parse().map_err(|_| {
+            Error::invalid_value(
+                Unexpected::Other("path is not of the form `Component` or `crate::Component`."),
+                &self,
+            )
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for ComponentPath {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(ComponentPathVisitor)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::{ComponentVar, Name};
+    use super::{ComponentPath, ComponentVar, Name};
     use crate::Var;
     use serde::Deserialize;
     use serde_yaml::Value;
@@ -169,6 +272,29 @@ mod test {
             .unwrap(),
             Var::Variable("variable_name".parse().unwrap())
         );
+        assert_eq!(
+            serde_yaml::from_str::<Var<u8>>("theme: primary").unwrap(),
+            Var::Theme("primary".parse().unwrap())
+        );
+        assert_eq!(
+            serde_yaml::from_str::<Var<bool>>("binding: agreed").unwrap(),
+            Var::Binding("agreed".parse().unwrap())
+        );
+
+        #[derive(Deserialize, Debug, Eq, PartialEq)]
+        #[serde(rename_all = "camelCase")]
+        enum Fit {
+            Contain,
+            Cover,
+        }
+        assert_eq!(
+            serde_yaml::from_str::<Var<Fit>>("cover").unwrap(),
+            Var::Value(Fit::Cover)
+        );
+        let error = serde_yaml::from_str::<Var<Fit>>("fill").unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("unknown variant `fill`, expected `contain` or `cover`"));
     }
 
     #[test]
@@ -190,4 +316,24 @@ mod test {
             }
         );
     }
+
+    #[test]
+    fn component_path_deserialization() {
+        assert_eq!(
+            serde_yaml::from_str::<ComponentPath>("TrafficLight").unwrap(),
+            ComponentPath {
+                crate_name: None,
+                name: "TrafficLight".parse().unwrap()
+            }
+        );
+        assert_eq!(
+            serde_yaml::from_str::<ComponentPath>("my_widgets::DatePicker").unwrap(),
+            ComponentPath {
+                crate_name: Some("my_widgets".parse().unwrap()),
+                name: "DatePicker".parse().unwrap()
+            }
+        );
+        assert!(serde_yaml::from_str::<ComponentPath>("my_widgets::sub::DatePicker").is_err());
+        assert!(serde_yaml::from_str::<ComponentPath>("::DatePicker").is_err());
+    }
 }
@@ -1,6 +1,9 @@
+use crate::drag::Drag;
+use crate::menu::OptionList;
 use crate::widget::{RuntimeID, WidgetID};
 use crate::{Component, Point};
 use glazier::kurbo::Rect;
+use glazier::FileDialogToken;
 use itertools::Itertools;
 use std::collections::HashMap;
 
@@ -10,12 +13,29 @@ pub struct WidgetInfo {
     pos_map: HashMap<RuntimeID, Vec<Rect>>,
     active_widget: Option<(RuntimeID, WidgetID)>,
     hovered_widgets: Vec<(RuntimeID, WidgetID)>,
+    /// Widgets that accept drops, added when they are resized.
+    drop_targets: Vec<(RuntimeID, WidgetID)>,
+    /// Widgets that were hidden when they were last resized, so take up no space.
+    hidden: Vec<(RuntimeID, WidgetID)>,
+    pub drag: Option<Drag>,
+    /// The list of options opened by a widget, which is taken by the window to show it.
+    pub option_list: Option<OptionList>,
+    /// The widget with selected text, as only one widget can have a selection.
+    pub text_selection: Option<(RuntimeID, WidgetID)>,
+    /// File dialogs that have been opened, with the widget that opened them.
+    file_dialogs: Vec<(FileDialogToken, (RuntimeID, WidgetID))>,
 }
 
 impl WidgetInfo {
     pub fn reset_positions(&mut self) {
         self.pos_map.clear();
     }
+    pub fn clear_drop_targets(&mut self) {
+        self.drop_targets.clear();
+    }
+    pub fn clear_hidden(&mut self) {
+        self.hidden.clear();
+    }
     pub fn get_rect(&self, runtime_id: RuntimeID, widget_id: WidgetID) -> Rect {
         self.pos_map
             .get(&runtime_id)
@@ -24,6 +44,11 @@ impl WidgetInfo {
             .unwrap_or_default()
     }
 
+    /// Whether widgets of the component with `runtime_id` have been positioned.
+    pub fn has_runtime_id(&self, runtime_id: RuntimeID) -> bool {
+        self.pos_map.contains_key(&runtime_id)
+    }
+
     pub fn get_parent_rect(&self) -> Rect {
         let parent_runtime_id = self.pos_map.keys().min().copied().unwrap_or_default();
         self.get_rect(parent_runtime_id, WidgetID::new(0))
@@ -36,7 +61,11 @@ impl WidgetInfo {
     ///
     /// * `rect`: The rect of the first component's widget, this will normally be a rect that positions the widget in the window's space.
     /// * `component`: The main component used to run the application, this is used to get the parent of each widget.
-    pub fn convert_to_global_positions<C: Component>(&mut self, rect: Rect, component: &C) {
+    pub fn convert_to_global_positions<C: Component + ?Sized>(
+        &mut self,
+        rect: Rect,
+        component: &C,
+    ) {
         let parent_runtime_id = self.pos_map.keys().min().copied().unwrap_or_default();
         self.position_widget(parent_runtime_id, WidgetID::new(0), rect);
         let local_positions = self.pos_map.clone();
@@ -78,6 +107,10 @@ impl WidgetInfo {
         }
         self.hovered_widgets
             .retain(|(hovered_id, _)| *hovered_id != runtime_id);
+        self.drop_targets
+            .retain(|(target_id, _)| *target_id != runtime_id);
+        self.hidden
+            .retain(|(hidden_id, _)| *hidden_id != runtime_id);
     }
 
     pub fn is_active(&self, runtime_id: RuntimeID, widget_id: WidgetID) -> bool {
@@ -105,6 +138,60 @@ impl WidgetInfo {
         }
     }
 
+    pub fn add_drop_target(&mut self, runtime_id: RuntimeID, widget_id: WidgetID) {
+        self.drop_targets.push((runtime_id, widget_id));
+    }
+
+    pub fn hide_widget(&mut self, runtime_id: RuntimeID, widget_id: WidgetID) {
+        self.hidden.push((runtime_id, widget_id));
+    }
+
+    pub fn is_hidden(&self, runtime_id: RuntimeID, widget_id: WidgetID) -> bool {
+        self.hidden.contains(&(runtime_id, widget_id))
+    }
+
+    /// The smallest drop target containing `point`, so that targets inside other targets take
+    /// priority.
+    pub fn get_drop_target(&self, point: Point) -> Option<(RuntimeID, WidgetID)> {
+        self.drop_targets
+            .iter()
+            .map(|&(r, w)| ((r, w), self.get_rect(r, w)))
+            .filter(|(_, rect)| rect.contains(point))
+            .min_by(|(_, a), (_, b)| a.area().total_cmp(&b.area()))
+            .map(|(id, _)| id)
+    }
+
+    /// The widgets containing `point`, from the smallest to the largest.
+    pub fn widgets_at(&self, point: Point) -> Vec<(RuntimeID, WidgetID)> {
+        self.pos_map
+            .iter()
+            .flat_map(|(&r, rects)| {
+                rects
+                    .iter()
+                    .enumerate()
+                    .map(move |(w, rect)| ((r, WidgetID::new(w as u32)), *rect))
+            })
+            .filter(|(_, rect)| rect.contains(point))
+            .sorted_by(|(_, a), (_, b)| a.area().total_cmp(&b.area()))
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    pub fn add_file_dialog(&mut self, token: FileDialogToken, id: (RuntimeID, WidgetID)) {
+        self.file_dialogs.push((token, id));
+    }
+
+    /// Removes the file dialog, returning the widget that opened it.
+    pub fn take_file_dialog(&mut self, token: FileDialogToken) -> Option<(RuntimeID, WidgetID)> {
+        let index = self.file_dialogs.iter().position(|(t, _)| *t == token)?;
+        Some(self.file_dialogs.remove(index).1)
+    }
+
+    /// The token of the file dialog that was opened last, if it has not been closed.
+    pub fn last_file_dialog(&self) -> Option<FileDialogToken> {
+        self.file_dialogs.last().map(|(token, _)| *token)
+    }
+
     pub fn remove_un_hovered(&mut self, mouse_point: Point) -> Vec<(RuntimeID, WidgetID)> {
         let (hovered, un_hovered) = self
             .hovered_widgets
@@ -119,9 +206,12 @@ impl WidgetInfo {
 mod tests {
     use super::WidgetInfo;
     use crate::handles::Handle;
+    use crate::menu::Menu;
     use crate::widget::{RuntimeID, WidgetEvent, WidgetID};
+    use crate::window::WindowConfig;
     use crate::{Component, LayoutConstraints, Size};
     use glazier::kurbo::{Point, Rect};
+    use glazier::KeyEvent;
     use std::any::Any;
     use std::collections::HashMap;
     use vello::SceneBuilder;
@@ -176,6 +266,38 @@ mod tests {
             unimplemented!()
         }
 
+        fn shortcut(&mut self, _event: &KeyEvent) -> bool {
+            unimplemented!()
+        }
+
+        fn window_config(&self) -> WindowConfig {
+            unimplemented!()
+        }
+
+        fn menu_bar(&self) -> Option<Menu> {
+            unimplemented!()
+        }
+
+        fn context_menu(&self, _runtime_id: RuntimeID, _widget_id: WidgetID) -> Option<Menu> {
+            unimplemented!()
+        }
+
+        fn tooltip(&self, _runtime_id: RuntimeID, _widget_id: WidgetID) -> Option<String> {
+            unimplemented!()
+        }
+
+        fn menu_command(&mut self, _runtime_id: RuntimeID, _id: u32) -> bool {
+            unimplemented!()
+        }
+
+        fn with_comp_struct(
+            &mut self,
+            _runtime_id: RuntimeID,
+            _f: &mut dyn FnMut(&mut dyn Any),
+        ) -> bool {
+            unimplemented!()
+        }
+
         fn id(&self) -> RuntimeID {
             unimplemented!()
         }
@@ -189,6 +311,19 @@ mod tests {
         assert!(widget_info.pos_map.is_empty());
     }
 
+    #[test]
+    fn hidden_widgets_are_cleared() {
+        let mut widget_info = WidgetInfo::default();
+        widget_info.hide_widget(RUNTIME_ZERO, WIDGET_ZERO);
+        assert!(widget_info.is_hidden(RUNTIME_ZERO, WIDGET_ZERO));
+        assert!(!widget_info.is_hidden(RUNTIME_ZERO, WidgetID::new(1)));
+        // Hidden widgets are kept when positions are converted to global positions.
+        widget_info.reset_positions();
+        assert!(widget_info.is_hidden(RUNTIME_ZERO, WIDGET_ZERO));
+        widget_info.clear_hidden();
+        assert!(!widget_info.is_hidden(RUNTIME_ZERO, WIDGET_ZERO));
+    }
+
     #[test]
     fn get_rect_returns() {
         let mut widget_info = WidgetInfo::default();
@@ -239,6 +374,38 @@ mod tests {
         assert!(widget_info.is_hovered(RUNTIME_ZERO, WIDGET_ZERO));
     }
 
+    #[test]
+    fn innermost_drop_target() {
+        let mut widget_info = WidgetInfo::default();
+        let inner = WidgetID::new(1);
+        widget_info.add_drop_target(RUNTIME_ZERO, WIDGET_ZERO);
+        widget_info.add_drop_target(RUNTIME_ZERO, inner);
+        widget_info.position_widget(RUNTIME_ZERO, WIDGET_ZERO, Rect::new(0.0, 0.0, 50.0, 50.0));
+        widget_info.position_widget(RUNTIME_ZERO, inner, Rect::new(10.0, 10.0, 20.0, 20.0));
+        assert_eq!(
+            widget_info.get_drop_target(Point::new(15.0, 15.0)),
+            Some((RUNTIME_ZERO, inner))
+        );
+        assert_eq!(
+            widget_info.get_drop_target(Point::new(40.0, 40.0)),
+            Some((RUNTIME_ZERO, WIDGET_ZERO))
+        );
+        assert_eq!(widget_info.get_drop_target(Point::new(60.0, 60.0)), None);
+    }
+
+    #[test]
+    fn widgets_at_point() {
+        let mut widget_info = WidgetInfo::default();
+        let inner = WidgetID::new(2);
+        widget_info.position_widget(RUNTIME_ZERO, WIDGET_ZERO, Rect::new(0.0, 0.0, 50.0, 50.0));
+        widget_info.position_widget(RUNTIME_ZERO, inner, Rect::new(10.0, 10.0, 20.0, 20.0));
+        assert_eq!(
+            widget_info.widgets_at(Point::new(15.0, 15.0)),
+            vec![(RUNTIME_ZERO, inner), (RUNTIME_ZERO, WIDGET_ZERO)]
+        );
+        assert!(widget_info.widgets_at(Point::new(60.0, 60.0)).is_empty());
+    }
+
     #[test]
     fn global_position_of_empty_position_map() {
         let mut widget_info = WidgetInfo::default();
@@ -0,0 +1,276 @@
+//! App-wide themes. Layout files can reference a token of the current theme with
+//! `theme: <token>` instead of a value, and [`set_theme`] switches the theme of every component
+//! at runtime.
+
+use crate::Colour;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// A set of named tokens that widget properties can reference.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    /// Colour tokens, such as `primary` or `text`.
+    pub palette: HashMap<String, Colour>,
+    /// Font size tokens, such as `body` or `heading`.
+    pub typography: HashMap<String, f32>,
+    /// Spacing tokens, such as `sm` or `md`.
+    pub spacing: HashMap<String, f32>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+impl Theme {
+    /// A theme with no tokens. Prefer to create custom themes from [`Theme::light`] or
+    /// [`Theme::dark`], so that the tokens used by the built-in widgets are present.
+    pub fn empty() -> Self {
+        Self {
+            palette: HashMap::new(),
+            typography: HashMap::new(),
+            spacing: HashMap::new(),
+        }
+    }
+
+    /// The built-in light theme, used by default.
+    pub fn light() -> Self {
+        Self::with_sizes()
+            .with_colour("background", Colour::rgba8(255, 255, 255, 255))
+            .with_colour("surface", Colour::rgba8(255, 255, 255, 255))
+            .with_colour("surface_hover", Colour::rgba8(248, 249, 250, 255))
+            .with_colour("surface_active", Colour::rgba8(248, 249, 250, 255))
+            .with_colour("surface_disabled", Colour::rgba8(241, 243, 245, 255))
+            .with_colour("border", Colour::rgba8(206, 212, 218, 255))
+            .with_colour("text", Colour::rgba8(33, 37, 41, 255))
+            .with_colour("primary", Colour::rgba8(34, 139, 230, 255))
+            .with_colour("secondary", Colour::rgba8(134, 142, 150, 255))
+            .with_colour("success", Colour::rgba8(64, 192, 87, 255))
+            .with_colour("warning", Colour::rgba8(250, 176, 5, 255))
+            .with_colour("error", Colour::rgba8(250, 82, 82, 255))
+    }
+
+    /// The built-in dark theme.
+    pub fn dark() -> Self {
+        Self::with_sizes()
+            .with_colour("background", Colour::rgba8(26, 27, 30, 255))
+            .with_colour("surface", Colour::rgba8(37, 38, 43, 255))
+            .with_colour("surface_hover", Colour::rgba8(44, 46, 51, 255))
+            .with_colour("surface_active", Colour::rgba8(44, 46, 51, 255))
+            .with_colour("surface_disabled", Colour::rgba8(55, 58, 64, 255))
+            .with_colour("border", Colour::rgba8(55, 58, 64, 255))
+            .with_colour("text", Colour::rgba8(193, 194, 197, 255))
+            .with_colour("primary", Colour::rgba8(28, 126, 214, 255))
+            .with_colour("secondary", Colour::rgba8(144, 146, 150, 255))
+            .with_colour("success", Colour::rgba8(55, 178, 77, 255))
+            .with_colour("warning", Colour::rgba8(245, 159, 0, 255))
+            .with_colour("error", Colour::rgba8(240, 62, 62, 255))
+    }
+
+    /// Typography and spacing shared by the built-in themes.
+    fn with_sizes() -> Self {
+        Self::empty()
+            .with_font_size("small", 12.0)
+            .with_font_size("body", 14.0)
+            .with_font_size("large", 18.0)
+            .with_font_size("heading", 24.0)
+            .with_spacing("xs", 4.0)
+            .with_spacing("sm", 8.0)
+            .with_spacing("md", 10.0)
+            .with_spacing("lg", 16.0)
+            .with_spacing("xl", 24.0)
+    }
+
+    pub fn with_colour(mut self, token: &str, colour: Colour) -> Self {
+        self.palette.insert(token.to_string(), colour);
+        self
+    }
+
+    pub fn with_font_size(mut self, token: &str, size: f32) -> Self {
+        self.typography.insert(token.to_string(), size);
+        self
+    }
+
+    pub fn with_spacing(mut self, token: &str, spacing: f32) -> Self {
+        self.spacing.insert(token.to_string(), spacing);
+        self
+    }
+}
+
+/// Types that can be stored in a [`Theme`].
+pub trait ThemeValue: Sized {
+    /// Looks up a token, returning why it could not be found on failure.
+    fn from_theme(theme: &Theme, token: &str) -> Result<Self, String>;
+}
+
+impl ThemeValue for Colour {
+    fn from_theme(theme: &Theme, token: &str) -> Result<Self, String> {
+        theme
+            .palette
+            .get(token)
+            .copied()
+            .ok_or_else(|| format!("The current theme does not contain the colour token {token}"))
+    }
+}
+
+/// Font sizes and spacing are both `f32`, so a token can be prefixed with `typography.` or
+/// `spacing.` to pick the table. Tokens without a prefix must only be in one of the tables.
+impl ThemeValue for f32 {
+    fn from_theme(theme: &Theme, token: &str) -> Result<Self, String> {
+        let missing = || format!("The current theme does not contain the f32 token {token}");
+        if let Some(name) = token.strip_prefix("typography.") {
+            return theme.typography.get(name).copied().ok_or_else(missing);
+        }
+        if let Some(name) = token.strip_prefix("spacing.") {
+            return theme.spacing.get(name).copied().ok_or_else(missing);
+        }
+        match (theme.typography.get(token), theme.spacing.get(token)) {
+            (Some(_), Some(_)) => Err(format!(
+                "The theme token {token} is both a font size and a spacing, use \
+                 typography.{token} or spacing.{token} instead"
+            )),
+            (Some(value), None) | (None, Some(value)) => Ok(*value),
+            (None, None) => Err(missing()),
+        }
+    }
+}
+
+struct CurrentTheme {
+    theme: Rc<Theme>,
+    /// Incremented every time the theme is switched.
+    generation: u64,
+}
+
+thread_local! {
+    static CURRENT_THEME: RefCell<CurrentTheme> = RefCell::new(CurrentTheme {
+        theme: Rc::new(Theme::light()),
+        generation: 0,
+    });
+    static DEFAULT_THEME: Theme = Theme::default();
+}
+
+/// Switches the theme of every component. Components apply the new theme the next time their
+/// variables are updated, which happens after every event. Use `gui::theme::set_theme` to also
+/// repaint every open window.
+pub fn set_theme(theme: Theme) {
+    CURRENT_THEME.with_borrow_mut(|current| {
+        current.theme = Rc::new(theme);
+        current.generation += 1;
+    })
+}
+
+/// The theme currently in use.
+pub fn current_theme() -> Rc<Theme> {
+    CURRENT_THEME.with_borrow(|current| current.theme.clone())
+}
+
+/// Get the value of a token of the current theme.
+///
+/// # Panics
+///
+/// Panics if the current theme does not contain the token, or if the token is ambiguous.
+pub fn get<T: ThemeValue>(token: &str) -> T {
+    CURRENT_THEME.with_borrow(|current| {
+        T::from_theme(&current.theme, token).unwrap_or_else(|message| panic!("{message}"))
+    })
+}
+
+/// Get the value of a token of the current theme, or of the default theme if the current theme
+/// does not contain it. Used by generated code and to draw menus, tooltips and lists, so that a
+/// custom theme without a token does not crash the application.
+///
+/// # Panics
+///
+/// Panics if the default theme does not contain the token either, which is checked when building
+/// for tokens used by layout files.
+pub fn get_or_default<T: ThemeValue>(token: &str) -> T {
+    CURRENT_THEME
+        .with_borrow(|current| T::from_theme(&current.theme, token))
+        .or_else(|_| DEFAULT_THEME.with(|theme| T::from_theme(theme, token)))
+        .unwrap_or_else(|message| panic!("{message}"))
+}
+
+/// Used by generated code to check if the theme has been switched since `generation` was last
+/// updated.
+#[doc(hidden)]
+pub fn is_updated(generation: &mut u64) -> bool {
+    CURRENT_THEME.with_borrow(|current| {
+        let updated = *generation != current.generation;
+        *generation = current.generation;
+        updated
+    })
+}
+
+/// A reference to a token of the current theme, written out as the code that looks up the token
+/// at runtime. Can be used as the default of a widget property.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ThemeToken<'a>(pub &'a str);
+
+impl ToTokens for ThemeToken<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let token = self.0;
+        tokens.extend(quote!(::gui::gui_core::theme::get_or_default(#token)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get, get_or_default, is_updated, set_theme, Theme, ThemeValue};
+    use crate::Colour;
+
+    #[test]
+    fn switch_theme() {
+        let mut generation = 0;
+        assert!(!is_updated(&mut generation));
+        assert_eq!(
+            get::<Colour>("background"),
+            Theme::light().palette["background"]
+        );
+        assert_eq!(get::<f32>("body"), 14.0);
+        assert_eq!(get::<f32>("md"), 10.0);
+
+        set_theme(Theme::dark().with_font_size("body", 16.0));
+        assert!(is_updated(&mut generation));
+        assert!(!is_updated(&mut generation));
+        assert_eq!(
+            get::<Colour>("background"),
+            Theme::dark().palette["background"]
+        );
+        assert_eq!(get::<f32>("body"), 16.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "token missing")]
+    fn missing_token() {
+        get::<Colour>("missing");
+    }
+
+    #[test]
+    fn missing_token_uses_default() {
+        set_theme(Theme::empty().with_colour("primary", Colour::rgba8(1, 2, 3, 255)));
+        assert_eq!(
+            get_or_default::<Colour>("primary"),
+            Colour::rgba8(1, 2, 3, 255)
+        );
+        assert_eq!(
+            get_or_default::<Colour>("surface"),
+            Theme::light().palette["surface"]
+        );
+        assert_eq!(get_or_default::<f32>("body"), 14.0);
+    }
+
+    #[test]
+    fn prefixed_sizes() {
+        let theme = Theme::light().with_spacing("large", 32.0);
+        assert_eq!(f32::from_theme(&theme, "typography.large"), Ok(18.0));
+        assert_eq!(f32::from_theme(&theme, "spacing.large"), Ok(32.0));
+        assert_eq!(f32::from_theme(&theme, "spacing.md"), Ok(10.0));
+        assert!(f32::from_theme(&theme, "spacing.body").is_err());
+        let error = f32::from_theme(&theme, "large").unwrap_err();
+        assert!(error.contains("both a font size and a spacing"));
+    }
+}
@@ -1,19 +1,24 @@
 use std::any::Any;
+use std::cell::Cell;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Mutex;
 
 use dyn_clone::DynClone;
 use glazier::kurbo::Point;
-use glazier::PointerEvent;
+use glazier::{KeyEvent, Modifiers, PointerEvent};
 use proc_macro2::{Ident, TokenStream};
 use quote::{quote, ToTokens};
 use vello::kurbo::Size;
 use vello::SceneBuilder;
 
-pub use crate::handles::{EventHandle, Handle, RenderHandle, ResizeHandle, UpdateHandle};
+use crate::drag::{DragEvent, FileDialogResult};
+pub use crate::handles::{
+    Clipboard, EventHandle, FileDialogs, Handle, RenderHandle, ResizeHandle, UpdateHandle,
+};
 use crate::layout::LayoutConstraints;
 use crate::parse::fluent::Fluent;
 use crate::parse::var::{ComponentVar, Name};
+use crate::parse::ForEachDeclaration;
 use crate::ToComponent;
 use crate::{MutWidgetChildren, WidgetChildren};
 
@@ -40,6 +45,32 @@ impl RuntimeID {
         static WIDGET_COUNTER: AtomicU32 = AtomicU32::new(0);
         RuntimeID(WIDGET_COUNTER.fetch_add(1, Ordering::Relaxed))
     }
+
+    /// The component whose handlers are running, so that what a handler starts can be routed
+    /// back to its component.
+    pub fn running() -> Option<RuntimeID> {
+        RUNNING.get()
+    }
+
+    /// Marks the component as running its handlers until the guard is dropped.
+    #[doc(hidden)]
+    pub fn enter(self) -> RunningGuard {
+        RunningGuard(RUNNING.replace(Some(self)))
+    }
+}
+
+thread_local! {
+    static RUNNING: Cell<Option<RuntimeID>> = const { Cell::new(None) };
+}
+
+/// Restores the component that was running before [`RuntimeID::enter`] when dropped.
+#[doc(hidden)]
+pub struct RunningGuard(Option<RuntimeID>);
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        RUNNING.set(self.0);
+    }
 }
 
 /// An ID giving to all widgets to uniquely identify them in their component's namespace.
@@ -80,8 +111,31 @@ pub enum WidgetEvent<'a> {
     PointerUp(&'a PointerEvent),
     PointerDown(&'a PointerEvent),
     PointerMove(&'a PointerEvent),
-    /// Sent to all widgets that are no longer being hovered over
-    HoverChange,
+    /// Sent to the active widget when the pointer moves far enough while the primary button is
+    /// held down. The event is the one that pressed the button.
+    DragStart(&'a PointerEvent),
+    /// Sent to a drop target when a drag moves over it.
+    DragEnter(&'a DragEvent),
+    /// Sent to the drop target under a drag every time the pointer moves.
+    DragOver(&'a DragEvent),
+    /// Sent to a drop target when a drag moves off it.
+    DragLeave(&'a DragEvent),
+    /// Sent to the drop target under a drag when it is released.
+    Drop(&'a DragEvent),
+    /// Sent to all widgets when a key is pressed.
+    KeyDown(&'a KeyEvent),
+    /// Sent to the widget that opened a file dialog when it is closed.
+    FileDialog(&'a FileDialogResult),
+    /// Sent to the widget that opened a list of options with the index of the option chosen.
+    OptionChosen(usize),
+    /// Sent after the second of two primary presses in quick succession, at the same position.
+    DoubleClick(&'a PointerEvent),
+    /// Sent when the primary button has been held down without moving for a while. The event is
+    /// the one that pressed the button.
+    LongPress(&'a PointerEvent),
+    /// Sent to all widgets that are no longer being hovered over, with the event that moved the
+    /// pointer away.
+    HoverChange(&'a PointerEvent),
     /// Sent to the active widget if a new widget is now active
     ActiveChange,
 }
@@ -91,12 +145,33 @@ impl<'a> WidgetEvent<'a> {
         match self {
             WidgetEvent::PointerUp(e)
             | WidgetEvent::PointerDown(e)
-            | WidgetEvent::PointerMove(e) => Some(e.pos),
+            | WidgetEvent::PointerMove(e)
+            | WidgetEvent::DragStart(e)
+            | WidgetEvent::DoubleClick(e)
+            | WidgetEvent::LongPress(e) => Some(e.pos),
             _ => None,
         }
     }
 }
 
+/// The position of the pointer and the modifier keys held when a handler is called.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PointerInfo {
+    /// Position relative to the top left of the widget.
+    pub pos: Point,
+    pub modifiers: Modifiers,
+}
+
+impl PointerInfo {
+    /// Creates the info from an `event`, for a widget with its top left at `origin`.
+    pub fn new(event: &PointerEvent, origin: Point) -> Self {
+        Self {
+            pos: event.pos - origin.to_vec2(),
+            modifiers: event.modifiers,
+        }
+    }
+}
+
 pub trait Widget<T: ToComponent> {
     /// The id of the widget
     fn id(&self) -> WidgetID;
@@ -165,6 +240,21 @@ pub trait WidgetBuilder: std::fmt::Debug + AsAny + DynClone {
     fn get_statics(&self) -> Vec<(&'static str, TokenStream)> {
         vec![]
     }
+    /// The runtime value of each static and the property it relates to. Only used when hot
+    /// reloading a layout file, so values that can only be created using a [`TokenStream`] can be skipped.
+    fn get_static_values(&self) -> Vec<(&'static str, Box<dyn Any>)> {
+        vec![]
+    }
+    /// The name of the type of each static and the property it relates to, as given by
+    /// [`std::any::type_name`].
+    fn get_static_types(&self) -> Vec<(&'static str, &'static str)> {
+        vec![]
+    }
+    /// The theme tokens used by statics and the property they relate to. Theme tokens are looked
+    /// up by generated code, so they can not be hot reloaded.
+    fn get_theme_tokens(&self) -> Vec<(&'static str, Name)> {
+        vec![]
+    }
     /// The fluent and the property it is attached to.
     fn get_fluents(&self) -> Vec<(&'static str, Fluent)> {
         vec![]
@@ -173,6 +263,16 @@ pub trait WidgetBuilder: std::fmt::Debug + AsAny + DynClone {
     fn get_vars(&self) -> Vec<(&'static str, Name)> {
         vec![]
     }
+    /// The variables bound to properties, and the path of the function used to take a value that
+    /// the widget changed. The function is `None` if the property cannot be bound.
+    fn get_bindings(&self) -> Vec<(&'static str, Name, Option<TokenStream>)> {
+        vec![]
+    }
+    /// Checks the static values of properties, returning the name of each invalid property with a
+    /// description of the problem. Called when the layout is built.
+    fn validate(&self) -> Vec<(&'static str, String)> {
+        vec![]
+    }
     /// The components and property the widget holds.
     fn get_components(&self) -> Vec<(&'static str, ComponentVar)> {
         vec![]
@@ -181,6 +281,17 @@ pub trait WidgetBuilder: std::fmt::Debug + AsAny + DynClone {
     fn has_handler(&self) -> bool {
         false
     }
+    /// The path of the widget's handler trait, used to generate handlers for actions declared in
+    /// the layout file. The trait must take the handler struct as its only type parameter, and
+    /// have an `on_press(&mut self)` function to support `onPress` actions.
+    fn handler_trait(&self) -> Option<TokenStream> {
+        None
+    }
+    /// The widgets this widget creates for each item of a list. Templates are not children, so
+    /// must only be returned by [`get_widgets`](WidgetBuilder::get_widgets) to be styled.
+    fn for_each(&self) -> Option<&ForEachDeclaration> {
+        None
+    }
     /// Return [`WidgetDeclaration`](crate::parse::WidgetDeclaration)s for each child stored in the widget.
     /// None indicates that this widget does not normally store children
     fn get_widgets(&mut self) -> Option<Vec<MutWidgetChildren>> {
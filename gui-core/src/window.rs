@@ -0,0 +1,69 @@
+use crate::Size;
+
+/// The settings of a window, which can be set in the `window` section of the layout file of a
+/// component or by the application. Unset values use the default of the platform.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WindowConfig {
+    pub title: Option<String>,
+    /// The initial size of the window.
+    pub size: Option<Size>,
+    pub min_size: Option<Size>,
+    pub max_size: Option<Size>,
+    pub resizable: Option<bool>,
+    pub transparent: Option<bool>,
+}
+
+impl WindowConfig {
+    /// Uses the values of `other` for the values that are not set.
+    pub fn or(self, other: WindowConfig) -> WindowConfig {
+        WindowConfig {
+            title: self.title.or(other.title),
+            size: self.size.or(other.size),
+            min_size: self.min_size.or(other.min_size),
+            max_size: self.max_size.or(other.max_size),
+            resizable: self.resizable.or(other.resizable),
+            transparent: self.transparent.or(other.transparent),
+        }
+    }
+
+    /// Clamps `size` between the minimum and maximum size of the window.
+    pub fn clamp_size(&self, size: Size) -> Size {
+        let min = self.min_size.unwrap_or(Size::ZERO);
+        let max = self
+            .max_size
+            .unwrap_or(Size::new(f64::INFINITY, f64::INFINITY));
+        size.clamp(min, max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WindowConfig;
+    use crate::Size;
+
+    #[test]
+    fn clamp_and_merge() {
+        let layout = WindowConfig {
+            title: Some("Layout".into()),
+            min_size: Some(Size::new(200.0, 100.0)),
+            max_size: Some(Size::new(800.0, 600.0)),
+            ..Default::default()
+        };
+        let config = WindowConfig {
+            title: Some("App".into()),
+            resizable: Some(false),
+            ..Default::default()
+        }
+        .or(layout);
+        assert_eq!(config.title.as_deref(), Some("App"));
+        assert_eq!(config.resizable, Some(false));
+        assert_eq!(
+            config.clamp_size(Size::new(1000.0, 50.0)),
+            Size::new(800.0, 100.0)
+        );
+        assert_eq!(
+            WindowConfig::default().clamp_size(Size::new(1000.0, 50.0)),
+            Size::new(1000.0, 50.0)
+        );
+    }
+}
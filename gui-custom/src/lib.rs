@@ -13,6 +13,7 @@ pub mod __private {
 
     pub use gui_core::parse::fluent::Fluent;
     pub use gui_core::parse::var::{ComponentVar, Name};
+    pub use gui_core::theme::ThemeToken;
     pub use gui_core::widget::{WidgetBuilder, WidgetID};
     pub use gui_core::{Children, MutWidgetChildren, WidgetChildren};
 
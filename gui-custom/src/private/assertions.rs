@@ -8,6 +8,8 @@ pub fn property_path<W, T>(_: impl Fn(&mut W, T, &mut UpdateHandle)) {}
 
 pub fn fluent_path<W>(_: impl Fn(&mut W, Cow<str>, &mut UpdateHandle)) {}
 
+pub fn binding_path<W, T>(_: impl Fn(&mut W) -> Option<T>) {}
+
 pub fn component_path<W>(_: impl Fn(&mut W, RuntimeID, &mut UpdateHandle)) {}
 
 pub fn child_path<W, C>(_: impl Fn(&mut W) -> &mut Option<C>) {}
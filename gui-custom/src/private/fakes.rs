@@ -1,6 +1,9 @@
 use std::any::Any;
 
+use gui_core::glazier::KeyEvent;
+use gui_core::menu::Menu;
 use gui_core::widget::{Handle, RuntimeID, WidgetEvent, WidgetID};
+use gui_core::window::WindowConfig;
 use gui_core::{
     Component, LayoutConstraints, MultiComponent, SceneBuilder, Size, ToComponent, ToHandler,
 };
@@ -75,6 +78,38 @@ impl Component for Comp {
         unimplemented!()
     }
 
+    fn shortcut(&mut self, _event: &KeyEvent) -> bool {
+        unimplemented!()
+    }
+
+    fn window_config(&self) -> WindowConfig {
+        unimplemented!()
+    }
+
+    fn menu_bar(&self) -> Option<Menu> {
+        unimplemented!()
+    }
+
+    fn context_menu(&self, _runtime_id: RuntimeID, _widget_id: WidgetID) -> Option<Menu> {
+        unimplemented!()
+    }
+
+    fn tooltip(&self, _runtime_id: RuntimeID, _widget_id: WidgetID) -> Option<String> {
+        unimplemented!()
+    }
+
+    fn menu_command(&mut self, _runtime_id: RuntimeID, _id: u32) -> bool {
+        unimplemented!()
+    }
+
+    fn with_comp_struct(
+        &mut self,
+        _runtime_id: RuntimeID,
+        _f: &mut dyn FnMut(&mut dyn Any),
+    ) -> bool {
+        unimplemented!()
+    }
+
     fn id(&self) -> RuntimeID {
         unimplemented!()
     }
@@ -137,4 +172,24 @@ impl MultiComponent for MultiComp {
     fn get_id(&self, _name: &str) -> Option<(RuntimeID, WidgetID)> {
         unimplemented!()
     }
+    fn shortcut(&mut self, _event: &KeyEvent) -> bool {
+        unimplemented!()
+    }
+    fn context_menu(&self, _runtime_id: RuntimeID, _widget_id: WidgetID) -> Option<Menu> {
+        unimplemented!()
+    }
+    fn tooltip(&self, _runtime_id: RuntimeID, _widget_id: WidgetID) -> Option<String> {
+        unimplemented!()
+    }
+    fn menu_command(&mut self, _runtime_id: RuntimeID, _id: u32) -> bool {
+        unimplemented!()
+    }
+
+    fn with_comp_struct(
+        &mut self,
+        _runtime_id: RuntimeID,
+        _f: &mut dyn FnMut(&mut dyn Any),
+    ) -> bool {
+        unimplemented!()
+    }
 }
@@ -10,6 +10,7 @@ pub struct Derive {
     component_ident: Ident,
     component: String,
     vars_to_gen: Vec<(String, Ident)>,
+    bindings_to_gen: Vec<(String, Ident)>,
     components_to_gen: Vec<(String, Ident)>,
 }
 
@@ -38,6 +39,8 @@ impl Parse for Derive {
                     let env_component =
                         env::var(format!("GUI_COMPONENT_{component}_COMPONENT")).unwrap();
                     let components: HashSet<&str> = env_component.split(',').collect();
+                    let env_bindings = env::var(format!("GUI_COMPONENT_{component}_BIND")).unwrap();
+                    let bindings: HashSet<&str> = env_bindings.split(',').collect();
 
                     let fields_iter = fields
                         .named
@@ -50,6 +53,11 @@ impl Parse for Derive {
                         .filter(|(s, _i)| component_vars.contains(s.as_str()))
                         .collect();
 
+                    let bindings_to_gen = fields_iter
+                        .clone()
+                        .filter(|(s, _i)| bindings.contains(s.as_str()))
+                        .collect();
+
                     let components_to_gen = fields_iter
                         .filter(|(s, _i)| components.contains(s.as_str()))
                         .collect();
@@ -58,6 +66,7 @@ impl Parse for Derive {
                         component_ident: input.ident,
                         component,
                         vars_to_gen,
+                        bindings_to_gen,
                         components_to_gen,
                     })
                 } else {
@@ -65,6 +74,7 @@ impl Parse for Derive {
                         component_ident: input.ident,
                         component,
                         vars_to_gen: vec![],
+                        bindings_to_gen: vec![],
                         components_to_gen: vec![],
                     })
                 }
@@ -103,6 +113,17 @@ impl ToTokens for Derive {
             }
         });
 
+        let gen_bindings = self.bindings_to_gen.iter().map(|(v_name, ident)| {
+            let var_ident = Ident::new(v_name, ident.span());
+            quote! {
+                impl ::gui::gui_core::Bind<gen::#var_ident> for #component_ident {
+                    fn set_value(&mut self, value: <gen::#var_ident as ::gui::gui_core::Variable>::VarType) {
+                        self.#ident.set_value(value);
+                    }
+                }
+            }
+        });
+
         let gen_components = self.components_to_gen.iter().map(|(c_name, ident)| {
             let comp_ident = Ident::new(c_name, ident.span());
             quote! {
@@ -118,6 +139,7 @@ impl ToTokens for Derive {
             use #component_ident as __private_CompStruct;
             include!(concat!(env!("OUT_DIR"), #component_file));
             #(#gen_vars)*
+            #(#gen_bindings)*
             #(#gen_components)*
         })
     }
@@ -5,7 +5,7 @@ use syn::parse_macro_input;
 
 use crate::derive::Derive;
 use crate::type_registry::TypeRegistry;
-use crate::widget_builder::WidgetBuilder;
+use crate::widget_builder::WidgetBuilderInput;
 
 mod derive;
 mod type_registry;
@@ -19,6 +19,10 @@ pub fn derive_to_component(input: TokenStream) -> TokenStream {
 }
 
 /// Put this in the crate root.
+///
+/// Use `type_registry!(export)` in a library crate to allow the components it defines to be used
+/// in the layout files of other crates (as `component: library_name::ComponentName`). Exported
+/// components must be declared as `pub` structs.
 #[proc_macro]
 pub fn type_registry(item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as TypeRegistry);
@@ -41,6 +45,9 @@ pub fn type_registry(item: TokenStream) -> TokenStream {
 /// `ToComponent`) and `#child` (the type of the children this widget can hold))
 /// - `init_path` (of the form `new`) which describes how the runtime widget should be created
 /// of type `fn(WidgetId) -> RuntimeWidget`
+/// - `handler_trait` (optional, of the form `::crate_name::HandlerTrait`) the trait implemented by
+/// the component to handle the widget's events. Allows actions such as `onPress` to be declared
+/// in the layout file. Requires `type_path` to contain `#handler`.
 ///
 /// ## Field Attributes
 ///
@@ -51,13 +58,31 @@ pub fn type_registry(item: TokenStream) -> TokenStream {
 /// Should you get an error informing you that a type could not be inferred please add the `bound` attribute.
 /// - `<property|static|var|>bound = "T: Trait"` used for type assertions to ensure that the given
 /// function can deal with all the types declared by the bound.
+/// - `bind = "path_to_function"` of type `fn(&mut RuntimeWidget) -> Option<T>`, only available with
+/// `property`. Allows the property to be bound to a variable using `binding: name`, returning the
+/// value when it has been changed by the widget so that it can be stored in the component.
+/// - `range = start..=end` the static value must be within, checked when the layout is built.
+/// Values from variables are checked with debug assertions. Not available with a separate
+/// `var_only` function.
+/// - `non_empty` requires the value to have an `is_empty` function returning `false`, checked in the
+/// same way as `range`. Values passed to a separate `var_only` function are also checked, so their
+/// type must have an `is_empty` function or implement `gui_core::common::validation::IsEmpty`.
+/// - `validate = "::crate_name::path_to_function"` of type `fn(&T) -> Result<(), String>`, checked in
+/// the same way as `range`. The path must be absolute, as it is used by generated code.
 /// - `fluent = "path_to_function"` of type `fn<'a>(&mut RuntimeWidget, Cow<'a, str>, &mut UpdateHandle) -> ()`
 /// - `component = "path_to_function"` of type `fn(&mut RuntimeWidget, WidgetId, &mut UpdateHandle) -> ()`
 /// - `child = "path_to_function"` of type `fn(&mut RuntimeWidget) -> &mut Child`
 /// - `children = "path_to_function"` of type `fn(&mut RuntimeWidget, usize) -> &mut Child`
 ///
+/// ## Enums
+///
+/// The derive can also be added to enums with unit variants, which implements `ToTokens` so that
+/// the enum can be used as the type of a property. The enum must have a container attribute
+/// `#[widget(type_path = "::crate_name::EnumName")]` with the path of the enum, and should
+/// implement `Deserialize` and `Clone`.
+///
 #[proc_macro_derive(WidgetBuilder, attributes(widget))]
 pub fn derive_widget_builder(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as WidgetBuilder);
+    let input = parse_macro_input!(input as WidgetBuilderInput);
     input.to_token_stream().into()
 }
@@ -1,14 +1,26 @@
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, ToTokens};
 use std::env;
-use syn::parse::{Nothing, Parse, ParseStream};
+use syn::parse::{Parse, ParseStream};
+use syn::Ident;
 
-pub struct TypeRegistry;
+pub struct TypeRegistry {
+    export: bool,
+}
 
 impl Parse for TypeRegistry {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let _: Nothing = input.parse()?;
-        Ok(TypeRegistry)
+        if input.is_empty() {
+            return Ok(TypeRegistry { export: false });
+        }
+        let ident: Ident = input.parse()?;
+        if ident != "export" {
+            return Err(syn::Error::new(
+                ident.span(),
+                "Expected either no arguments or `export`",
+            ));
+        }
+        Ok(TypeRegistry { export: true })
     }
 }
 
@@ -16,11 +28,29 @@ impl ToTokens for TypeRegistry {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let env = env::var("GUI_COMPONENTS").unwrap();
         let component_idents = env.split(',').map(|s| format_ident!("{s}"));
+        let visibility = if self.export {
+            quote!(pub)
+        } else {
+            quote!(pub(crate))
+        };
         tokens.extend(quote! {
             #[doc(hidden)]
-            pub(crate) mod __gui_private {
-                #(pub(crate) struct #component_idents;)*
+            #visibility mod __gui_private {
+                #(#visibility struct #component_idents;)*
             }
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::TypeRegistry;
+
+    #[test]
+    fn parse_type_registry() {
+        assert!(!syn::parse_str::<TypeRegistry>("").unwrap().export);
+        assert!(syn::parse_str::<TypeRegistry>("export").unwrap().export);
+        assert!(syn::parse_str::<TypeRegistry>("import").is_err());
+        assert!(syn::parse_str::<TypeRegistry>("export, export").is_err());
+    }
+}
@@ -12,11 +12,13 @@ use field_attributes::FieldAttributes;
 use crate::widget_builder::attributes::StructAttributes;
 use crate::widget_builder::field_attributes::{Extension, Property, StaticDefault};
 use crate::widget_builder::interpolated_path::InterpolatedType;
+use crate::widget_builder::property_enum::PropertyEnum;
 
 mod assert;
 mod attributes;
 mod field_attributes;
 mod interpolated_path;
+mod property_enum;
 
 #[derive(Copy, Clone)]
 struct Import;
@@ -37,10 +39,45 @@ pub struct WidgetBuilder {
     fields: Vec<FieldAttributes>,
 }
 
-impl Parse for WidgetBuilder {
+/// The input of the derive macro: the builder of a widget, or an enum used as a property.
+#[derive(Clone)]
+pub enum WidgetBuilderInput {
+    Widget(WidgetBuilder),
+    PropertyEnum(PropertyEnum),
+}
+
+impl Parse for WidgetBuilderInput {
     fn parse(stream: ParseStream) -> syn::Result<Self> {
         let input: DeriveInput = stream.parse()?;
+        match input.data {
+            Data::Enum(e) => Ok(Self::PropertyEnum(PropertyEnum::new(
+                input.ident,
+                input.generics,
+                &input.attrs,
+                e,
+            )?)),
+            _ => WidgetBuilder::new(input).map(Self::Widget),
+        }
+    }
+}
 
+impl ToTokens for WidgetBuilderInput {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            WidgetBuilderInput::Widget(w) => w.to_tokens(tokens),
+            WidgetBuilderInput::PropertyEnum(e) => e.to_tokens(tokens),
+        }
+    }
+}
+
+impl Parse for WidgetBuilder {
+    fn parse(stream: ParseStream) -> syn::Result<Self> {
+        Self::new(stream.parse()?)
+    }
+}
+
+impl WidgetBuilder {
+    fn new(input: DeriveInput) -> syn::Result<Self> {
         match input.data {
             Data::Struct(s) => {
                 if let Fields::Named(fields) = s.fields {
@@ -65,7 +102,7 @@ impl Parse for WidgetBuilder {
             }
             Data::Enum(e) => Err(Error::new(
                 e.enum_token.span,
-                "The derive macro does not currently support enums.",
+                "Enums can only be used as properties.",
             )),
             Data::Union(u) => Err(Error::new(
                 u.union_token.span,
@@ -118,8 +155,11 @@ impl WidgetBuilder {
             .iter()
             .map(|(ident, ext, _, path)| {
                 let property = ext.form_prop_name(ident);
+                let assertions = Property::from(ext)
+                    .is_var()
+                    .then(|| self.runtime_assertions(ident, &property));
                 quote! {
-                    #property => stream.extend(#IMPORT::quote!( ##widget . #path (##value, ##handle); )),
+                    #property => stream.extend(#IMPORT::quote!( #assertions ##widget . #path (##value, ##handle); )),
                 }
             })
             .collect();
@@ -134,6 +174,111 @@ impl WidgetBuilder {
         })
     }
 
+    /// Debug assertions checking the value of a variable, which are added to the generated code.
+    fn runtime_assertions(&self, ident: &Ident, property: &str) -> TokenStream {
+        let validation = &self.field(ident).validation;
+        let widget_name = &self.attributes.widget_name;
+        let value = format_ident!("value");
+        let mut stream = TokenStream::new();
+        if let Some(range) = &validation.range {
+            let range_str = range.to_token_stream().to_string();
+            stream.extend(quote! {
+                ::std::debug_assert!(
+                    (#range).contains(&##value),
+                    "Property {} of widget {} is not in the range {}: {:?}",
+                    #property, #widget_name, #range_str, ##value
+                );
+            });
+        }
+        if validation.non_empty {
+            // Separate var functions may take a type without an `is_empty` function.
+            stream.extend(quote! {
+                #[allow(unused_imports)]
+                use ::gui::gui_core::common::validation::IsEmpty as _;
+                ::std::debug_assert!(
+                    !##value.is_empty(),
+                    "Property {} of widget {} must not be empty",
+                    #property, #widget_name
+                );
+            });
+        }
+        if let Some(validate) = &validation.validate {
+            stream.extend(quote! {
+                #[cfg(debug_assertions)]
+                if let Err(error) = #validate(&##value) {
+                    ::std::panic!("Invalid property {} of widget {}: {}", #property, #widget_name, error);
+                }
+            });
+        }
+        stream
+    }
+
+    /// Checks the static values of properties when the layout file is built.
+    fn validate_func(
+        &self,
+        property_names: &[(&Ident, Extension, Option<&StaticDefault>, &Path)],
+    ) -> Option<TokenStream> {
+        let checks: TokenStream = property_names
+            .iter()
+            .filter(|(_, e, ..)| Property::from(e).is_static())
+            .filter_map(|(ident, ext, ..)| {
+                let validation = &self.field(ident).validation;
+                if validation.is_empty() {
+                    return None;
+                }
+                let property_name = ext.form_prop_name(ident);
+                let binding =
+                    if let Extension::Unnecessary(Property::Both) | Extension::Static(true) = ext {
+                        quote!(Var::Value(v))
+                    } else {
+                        quote!(v)
+                    };
+
+                let range = validation.range.as_ref().map(|range| {
+                    let range_str = range.to_token_stream().to_string();
+                    quote! {
+                        if !(#range).contains(v) {
+                            result.push((#property_name, format!("{v:?} is not in the range {}", #range_str)));
+                        }
+                    }
+                });
+                let non_empty = validation.non_empty.then(|| {
+                    quote! {
+                        if v.is_empty() {
+                            result.push((#property_name, String::from("must not be empty")));
+                        }
+                    }
+                });
+                let validate = validation.validate.as_ref().map(|validate| {
+                    let validate = local_path(validate);
+                    quote! {
+                        if let Err(error) = #validate(v) {
+                            result.push((#property_name, error));
+                        }
+                    }
+                });
+
+                Some(quote! {
+                    if let Some(#binding) = &self. #ident {
+                        #range
+                        #non_empty
+                        #validate
+                    }
+                })
+            })
+            .collect();
+
+        checks.is_empty().not().then(|| {
+            quote! {
+                fn validate(&self) -> Vec<(&'static str, String)> {
+                    let mut result = vec![];
+                    #checks
+                    result
+                }
+            }
+        })
+    }
+
     fn widget_funcs(&self) -> Option<TokenStream> {
         let children_mut: TokenStream = self
             .fields
@@ -225,11 +370,16 @@ impl WidgetBuilder {
             .filter(|(_, e, ..)| Property::from(e).is_static())
             .map(|(property_ident, ext, default, _)| {
                 let property_name = ext.form_prop_name(property_ident);
-                let binding =
+                let (binding, theme) =
                     if let Extension::Unnecessary(Property::Both) | Extension::Static(true) = ext {
-                        quote!(Var::Value(v))
+                        (
+                            quote!(Var::Value(v)),
+                            Some(quote! {
+                                Some(Var::Theme(t)) => result.push((#property_name, #IMPORT::ThemeToken(t.as_str()).to_token_stream())),
+                            }),
+                        )
                     } else {
-                        quote!(v)
+                        (quote!(v), None)
                     };
 
                 let default = default.map(|d| match d {
@@ -244,6 +394,7 @@ impl WidgetBuilder {
                 quote! {
                     match &self . #property_ident {
                         Some(#binding) => result.push((#property_name, v.to_token_stream())),
+                        #theme
                         #default
                         _ => {}
                     }
@@ -263,6 +414,112 @@ impl WidgetBuilder {
         })
     }
 
+    fn static_types_func(
+        &self,
+        property_names: &[(&Ident, Extension, Option<&StaticDefault>, &Path)],
+    ) -> Option<TokenStream> {
+        let types: TokenStream = property_names
+            .iter()
+            .filter(|(_, e, ..)| Property::from(e).is_static())
+            .map(|(property_ident, ext, ..)| {
+                let property_name = ext.form_prop_name(property_ident);
+                let value_type = self.field(property_ident).value_type();
+                quote! {
+                    result.push((#property_name, ::std::any::type_name::<#value_type>()));
+                }
+            })
+            .collect();
+
+        types.is_empty().not().then(|| {
+            quote! {
+                fn get_static_types(&self) -> Vec<(&'static str, &'static str)> {
+                    let mut result = vec![];
+                    #types
+                    result
+                }
+            }
+        })
+    }
+
+    fn theme_tokens_func(
+        &self,
+        property_names: &[(&Ident, Extension, Option<&StaticDefault>, &Path)],
+    ) -> Option<TokenStream> {
+        // Only properties that can be static and variable hold a `Var`, which can be a theme token.
+        let tokens: TokenStream = property_names
+            .iter()
+            .filter(|(_, e, ..)| {
+                matches!(
+                    e,
+                    Extension::Unnecessary(Property::Both) | Extension::Static(true)
+                )
+            })
+            .map(|(property_ident, ext, ..)| {
+                let property_name = ext.form_prop_name(property_ident);
+                quote! {
+                    if let Some(Var::Theme(t)) = &self . #property_ident {
+                        result.push((#property_name, t.clone()));
+                    }
+                }
+            })
+            .collect();
+
+        tokens.is_empty().not().then(|| {
+            quote! {
+                fn get_theme_tokens(&self) -> Vec<(&'static str, #IMPORT::Name)> {
+                    let mut result = vec![];
+                    #tokens
+                    result
+                }
+            }
+        })
+    }
+
+    fn static_values_func(
+        &self,
+        property_names: &[(&Ident, Extension, Option<&StaticDefault>, &Path)],
+    ) -> Option<TokenStream> {
+        let statics: TokenStream = property_names
+            .iter()
+            .filter(|(_, e, ..)| Property::from(e).is_static())
+            .map(|(property_ident, ext, default, _)| {
+                let property_name = ext.form_prop_name(property_ident);
+                let binding =
+                    if let Extension::Unnecessary(Property::Both) | Extension::Static(true) = ext {
+                        quote!(Var::Value(v))
+                    } else {
+                        quote!(v)
+                    };
+
+                // Defaults created by a function are only available as a TokenStream.
+                let default = default.and_then(|d| match d {
+                    StaticDefault::Expression(e) => Some(quote! {
+                        None => result.push((#property_name, ::std::boxed::Box::new(#e) as ::std::boxed::Box<dyn ::std::any::Any>)),
+                    }),
+                    StaticDefault::Function(_) => None,
+                });
+
+                quote! {
+                    match &self . #property_ident {
+                        Some(#binding) => result.push((#property_name, ::std::boxed::Box::new(v.clone()) as ::std::boxed::Box<dyn ::std::any::Any>)),
+                        #default
+                        _ => {}
+                    }
+                }
+            })
+            .collect();
+
+        statics.is_empty().not().then(|| {
+            quote! {
+                fn get_static_values(&self) -> Vec<(&'static str, ::std::boxed::Box<dyn ::std::any::Any>)> {
+                    let mut result = vec![];
+                    #statics
+                    result
+                }
+            }
+        })
+    }
+
     fn fluents_func(
         &self,
         property_names: &[(&Ident, Extension, Option<&StaticDefault>, &Path)],
@@ -300,13 +557,17 @@ impl WidgetBuilder {
             .filter(|(_, e, ..)| Property::from(e).is_var())
             .map(|(property, ext, ..)| {
                 let property_name = ext.form_prop_name(property);
+                let bindable = self.field(property).bind.is_some();
 
-                let binding =
-                    if let Extension::Unnecessary(Property::Both) | Extension::Var(true) = ext {
+                let binding = match ext {
+                    Extension::Unnecessary(Property::Both) | Extension::Var(true) if bindable => {
+                        quote!(Var::Variable(v) | Var::Binding(v))
+                    }
+                    Extension::Unnecessary(Property::Both) | Extension::Var(true) => {
                         quote!(Var::Variable(v))
-                    } else {
-                        quote!(v)
-                    };
+                    }
+                    _ => quote!(v),
+                };
 
                 quote! {
                     if let Some(#binding) = &self. #property {
@@ -327,6 +588,50 @@ impl WidgetBuilder {
         })
     }
 
+    fn bindings_func(
+        &self,
+        property_names: &[(&Ident, Extension, Option<&StaticDefault>, &Path)],
+    ) -> Option<TokenStream> {
+        let bindings: TokenStream = property_names
+            .iter()
+            .filter(|(_, ext, ..)| {
+                matches!(
+                    ext,
+                    Extension::Unnecessary(Property::Both) | Extension::Var(true)
+                )
+            })
+            .map(|(property, ext, ..)| {
+                let property_name = ext.form_prop_name(property);
+                let bind = match &self.field(property).bind {
+                    Some(path) => quote!(Some(#IMPORT::quote!(#path))),
+                    None => quote!(None),
+                };
+                quote! {
+                    if let Some(Var::Binding(v)) = &self. #property {
+                        result.push(( #property_name, v.clone(), #bind ));
+                    }
+                }
+            })
+            .collect();
+
+        bindings.is_empty().not().then(|| {
+            quote! {
+                fn get_bindings(&self) -> Vec<(&'static str, #IMPORT::Name, Option<#IMPORT::TokenStream>)> {
+                    let mut result = vec![];
+                    #bindings
+                    result
+                }
+            }
+        })
+    }
+
+    fn field(&self, ident: &Ident) -> &FieldAttributes {
+        self.fields
+            .iter()
+            .find(|f| f.field.ident.as_ref() == Some(ident))
+            .expect("property names are created from fields")
+    }
+
     fn components_func(
         &self,
         property_names: &[(&Ident, Extension, Option<&StaticDefault>, &Path)],
@@ -378,8 +683,13 @@ impl ToTokens for WidgetBuilder {
         let property_names = self.get_property_names();
         let property_func = self.property_func(&property_names);
         let statics_func = self.statics_func(&property_names);
+        let static_values_func = self.static_values_func(&property_names);
+        let static_types_func = self.static_types_func(&property_names);
+        let theme_tokens_func = self.theme_tokens_func(&property_names);
         let fluents_func = self.fluents_func(&property_names);
         let vars_func = self.vars_func(&property_names);
+        let bindings_func = self.bindings_func(&property_names);
+        let validate_func = self.validate_func(&property_names);
         let components_func = self.components_func(&property_names);
 
         let widget_funcs = self.widget_funcs();
@@ -390,6 +700,13 @@ impl ToTokens for WidgetBuilder {
                 }
             }
         });
+        let handler_trait_func = self.attributes.handler_trait.as_ref().map(|path| {
+            quote! {
+                fn handler_trait(&self) -> Option<#IMPORT::TokenStream> {
+                    Some(#IMPORT::quote!(#path))
+                }
+            }
+        });
 
         tokens.extend(quote! {
             #assertions
@@ -412,9 +729,15 @@ impl ToTokens for WidgetBuilder {
 
                 #property_func
                 #statics_func
+                #static_values_func
+                #static_types_func
+                #theme_tokens_func
                 #fluents_func
                 #vars_func
+                #bindings_func
+                #validate_func
                 #has_handler_func
+                #handler_trait_func
                 #components_func
 
                 #widget_funcs
@@ -423,11 +746,43 @@ impl ToTokens for WidgetBuilder {
     }
 }
 
+/// Converts a path used by generated code to a path that can be used by the crate the widget is
+/// defined in, which is the crate being compiled.
+fn local_path(path: &Path) -> Path {
+    let crate_name = std::env::var("CARGO_CRATE_NAME").unwrap_or_default();
+    local_path_in(path, &crate_name)
+}
+
+/// Replaces the start of an absolute path to an item of `crate_name`, either `::crate_name::` or
+/// `::gui::crate_name::` for the crates re-exported by `gui`, with `crate::`. Paths to items of
+/// other crates are left absolute.
+fn local_path_in(path: &Path, crate_name: &str) -> Path {
+    if path.leading_colon.is_none() {
+        return path.clone();
+    }
+    let segments = path
+        .segments
+        .iter()
+        .map(|s| s.ident.to_string())
+        .collect_vec();
+    let start = match segments.as_slice() {
+        [first, ..] if first == crate_name => 1,
+        [first, second, ..] if first == "gui" && second == crate_name => 2,
+        _ => return path.clone(),
+    };
+    let mut local = path.clone();
+    local.leading_colon = None;
+    local.segments = std::iter::once(syn::PathSegment::from(format_ident!("crate")))
+        .chain(path.segments.iter().skip(start).cloned())
+        .collect();
+    local
+}
+
 #[cfg(test)]
 mod test {
     use syn::parse_quote;
 
-    use crate::widget_builder::WidgetBuilder;
+    use crate::widget_builder::{WidgetBuilder, WidgetBuilderInput};
 
     #[test]
     pub fn parse_attributes() {
@@ -451,4 +806,125 @@ mod test {
         assert_eq!(builder.attributes, multi_attributes.attributes);
         assert_eq!(builder.fields, multi_attributes.fields);
     }
+
+    #[test]
+    pub fn property_enum() {
+        let input: WidgetBuilderInput = parse_quote! {
+            #[widget(type_path = "::gui::gui_widget::TextAlign")]
+            enum TextAlign {
+                Start,
+                End,
+            }
+        };
+        let tokens = quote::ToTokens::to_token_stream(&input).to_string();
+        assert!(tokens.contains(
+            "Self :: End => :: gui_custom :: __private :: quote ! (:: gui :: gui_widget :: TextAlign :: End)"
+        ));
+
+        let with_fields = syn::parse2::<WidgetBuilderInput>(quote::quote! {
+            #[widget(type_path = "::gui::gui_widget::Fit")]
+            enum Fit {
+                Scale(f32),
+            }
+        });
+        assert!(with_fields.is_err());
+    }
+
+    #[test]
+    pub fn bindings() {
+        let builder: WidgetBuilder = parse_quote! {
+            #[widget(name = "test", init_path = "new", type_path = "::gui::Test")]
+            struct TestBuilder {
+                #[widget(property = "set_checked", bind = "take_checked")]
+                checked: Option<Var<bool>>
+            }
+        };
+        let tokens = quote::ToTokens::to_token_stream(&builder).to_string();
+        assert!(tokens.contains("Var :: Variable (v) | Var :: Binding (v)"));
+        assert!(tokens.contains("fn get_bindings"));
+
+        let var_only = syn::parse2::<WidgetBuilder>(quote::quote! {
+            #[widget(name = "test", init_path = "new", type_path = "::gui::Test")]
+            struct TestBuilder {
+                #[widget(var_only = "set_checked", bind = "take_checked")]
+                checked: Option<Name>
+            }
+        });
+        assert!(var_only.is_err());
+    }
+
+    #[test]
+    pub fn validation() {
+        let builder: WidgetBuilder = parse_quote! {
+            #[widget(name = "test", init_path = "new", type_path = "::gui::Test")]
+            struct TestBuilder {
+                #[widget(property = "set_size", range = 0.0..=10.0)]
+                size: Option<Var<f32>>,
+                #[widget(static_only = "set_path", non_empty, validate = "::gui::check_path")]
+                path: Option<String>,
+            }
+        };
+        let tokens = quote::ToTokens::to_token_stream(&builder).to_string();
+        assert!(tokens.contains("fn validate"));
+        assert!(tokens.contains("if ! (0.0 ..= 10.0) . contains (v)"));
+        // The test crate is not `gui`, so the path is left absolute.
+        assert!(tokens.contains("if let Err (error) = :: gui :: check_path (v)"));
+        assert!(tokens.contains(":: std :: debug_assert ! ((0.0 ..= 10.0) . contains (& # value)"));
+
+        let fluent = syn::parse2::<WidgetBuilder>(quote::quote! {
+            #[widget(name = "test", init_path = "new", type_path = "::gui::Test")]
+            struct TestBuilder {
+                #[widget(fluent = "set_text", non_empty)]
+                text: Option<Fluent>,
+            }
+        });
+        assert!(fluent.is_err());
+
+        // Properties with a separate var function are also checked when the variable changes.
+        let builder: WidgetBuilder = parse_quote! {
+            #[widget(name = "test", init_path = "new", type_path = "::gui::Test")]
+            struct TestBuilder {
+                #[widget(static_only = "set_path", var_only = "set_image", non_empty)]
+                image: Option<Var<String>>,
+            }
+        };
+        let tokens = quote::ToTokens::to_token_stream(&builder).to_string();
+        assert!(tokens.contains("# widget . set_image (# value , # handle)"));
+        assert!(tokens.contains("IsEmpty as _ ; :: std :: debug_assert ! (! # value . is_empty ()"));
+
+        // The type of a separate var function may not support the range or validate function.
+        let separate_range = syn::parse2::<WidgetBuilder>(quote::quote! {
+            #[widget(name = "test", init_path = "new", type_path = "::gui::Test")]
+            struct TestBuilder {
+                #[widget(static_only = "set_path", var_only = "set_image", range = 0..10)]
+                image: Option<Var<String>>,
+            }
+        });
+        assert!(separate_range.is_err());
+        let separate_validate = syn::parse2::<WidgetBuilder>(quote::quote! {
+            #[widget(name = "test", init_path = "new", type_path = "::gui::Test")]
+            struct TestBuilder {
+                #[widget(static_only = "set_path", var_only = "set_image", validate = "::gui::check_path")]
+                image: Option<Var<String>>,
+            }
+        });
+        assert!(separate_validate.is_err());
+    }
+
+    #[test]
+    pub fn local_paths() {
+        let local = |path: syn::Path, crate_name: &str| {
+            let local = super::local_path_in(&path, crate_name);
+            quote::ToTokens::to_token_stream(&local).to_string()
+        };
+        let path: syn::Path = parse_quote!(::gui::gui_widget::check);
+        assert_eq!(local(path.clone(), "gui_widget"), "crate :: check");
+        assert_eq!(local(path.clone(), "gui"), "crate :: gui_widget :: check");
+        assert_eq!(local(path, "my_widgets"), ":: gui :: gui_widget :: check");
+        assert_eq!(
+            local(parse_quote!(::my_widgets::check), "my_widgets"),
+            "crate :: check"
+        );
+        assert_eq!(local(parse_quote!(check), "my_widgets"), "check");
+    }
 }
@@ -69,6 +69,11 @@ impl FieldAttributes {
                 var_prop,
             ));
         }
+        if let Some(bind) = &self.bind {
+            stream.extend(
+                quote!( #assert_path binding_path::<#widget_type, _>(#widget_turbo :: #bind); ),
+            )
+        }
         if let Some(fluent) = &self.fluent {
             stream.extend(
                 quote!( #assert_path fluent_path::<#widget_type>(#widget_turbo :: #fluent); ),
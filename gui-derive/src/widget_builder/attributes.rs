@@ -1,26 +1,40 @@
 use itertools::Itertools;
 use proc_macro2::{Ident, Span};
-use syn::parse::Parse;
+use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{Attribute, Error, Expr, Lit, MetaNameValue, Path, PredicateType, Token, WherePredicate};
+use syn::{parse_quote, Attribute, Error, Expr, Lit, Path, PredicateType, Token, WherePredicate};
 
 use crate::widget_builder::interpolated_path::{InterpolatedPath, InterpolatedType};
 
+/// A `name = value` pair, or a flag (`name`) which is given the value `true`.
+struct WidgetAttribute {
+    name: Ident,
+    value: Expr,
+}
+
+impl Parse for WidgetAttribute {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let value = if input.parse::<Option<Token![=]>>()?.is_some() {
+            input.parse()?
+        } else {
+            parse_quote!(true)
+        };
+        Ok(Self { name, value })
+    }
+}
+
+/// Gets the `name = value` pairs of `#[widget]` attributes. Flags (`#[widget(name)]`) are given
+/// the value `true`.
 pub fn get_attributes(attributes: &[Attribute]) -> syn::Result<Vec<(Ident, Expr)>> {
     attributes
         .iter()
         .filter(|a| a.path().is_ident("widget"))
         .map(|a| {
             let values =
-                a.parse_args_with(Punctuated::<MetaNameValue, Token![,]>::parse_terminated)?;
-            values
-                .iter()
-                .map(|meta| {
-                    let ident = meta.path.require_ident()?;
-                    Ok((ident.clone(), meta.value.clone()))
-                })
-                .collect::<syn::Result<Vec<_>>>()
+                a.parse_args_with(Punctuated::<WidgetAttribute, Token![,]>::parse_terminated)?;
+            Ok(values.into_iter().map(|a| (a.name, a.value)))
         })
         .flatten_ok()
         .collect()
@@ -35,6 +49,15 @@ pub fn parse_from_lit<T: Parse>(expr: Expr) -> syn::Result<T> {
     Err(Error::new(expr.span(), "Expected literal string"))
 }
 
+pub fn require_bool(expr: Expr) -> syn::Result<bool> {
+    if let Expr::Lit(lit) = &expr {
+        if let Lit::Bool(bool) = &lit.lit {
+            return Ok(bool.value);
+        }
+    }
+    Err(Error::new(expr.span(), "Expected literal bool"))
+}
+
 pub fn require_lit(expr: Expr) -> syn::Result<String> {
     if let Expr::Lit(lit) = &expr {
         if let Lit::Str(str) = &lit.lit {
@@ -111,6 +134,7 @@ pub struct StructAttributes {
     pub has_handler: bool,
     pub type_path: InterpolatedPath,
     pub init_path: Path,
+    pub handler_trait: Option<Path>,
 }
 
 impl StructAttributes {
@@ -120,6 +144,7 @@ impl StructAttributes {
         let mut widget_name = None;
         let mut type_path = None;
         let mut init_path = None;
+        let mut handler_trait: Option<Path> = None;
 
         for (name, expr) in attributes {
             match name.to_string().as_str() {
@@ -128,6 +153,9 @@ impl StructAttributes {
                 "init_path" if init_path.is_none() => {
                     init_path = Some(require_func_path(parse_from_lit(expr)?)?)
                 }
+                "handler_trait" if handler_trait.is_none() => {
+                    handler_trait = Some(parse_from_lit(expr)?)
+                }
                 _ => return Err(Error::new(name.span(), "Unexpected attribute")),
             }
         }
@@ -138,11 +166,19 @@ impl StructAttributes {
 
         let has_handler = check_interpolated_path(&type_path)?;
 
+        if let (false, Some(handler_trait)) = (has_handler, &handler_trait) {
+            return Err(Error::new(
+                handler_trait.span(),
+                "A handler trait requires the type_path to contain #handler",
+            ));
+        }
+
         Ok(StructAttributes {
             has_handler,
             widget_name,
             type_path,
             init_path,
+            handler_trait,
         })
     }
 }
@@ -1,9 +1,9 @@
 use proc_macro2::Ident;
 use syn::spanned::Spanned;
-use syn::{Error, Expr, Field, Path, PredicateType};
+use syn::{Error, Expr, Field, GenericArgument, Path, PathArguments, PredicateType, Type};
 
 use crate::widget_builder::attributes::{
-    get_attributes, parse_from_lit, require_func_path, require_type_predicate,
+    get_attributes, parse_from_lit, require_bool, require_func_path, require_type_predicate,
 };
 
 #[derive(Clone, Debug)]
@@ -14,6 +14,9 @@ pub struct FieldAttributes {
     pub static_bound: Option<PredicateType>,
     pub var_prop: Option<Path>,
     pub var_bound: Option<PredicateType>,
+    /// Function taking the value of the property when it has been changed by the widget.
+    pub bind: Option<Path>,
+    pub validation: Validation,
     pub fluent: Option<Path>,
     pub component: Option<Path>,
     pub child: Option<Path>,
@@ -27,6 +30,8 @@ impl PartialEq for FieldAttributes {
             && self.static_default == other.static_default
             && self.static_prop == other.static_prop
             && self.var_prop == other.var_prop
+            && self.bind == other.bind
+            && self.validation == other.validation
             && self.fluent == other.fluent
             && self.component == other.component
             && self.child == other.child
@@ -99,7 +104,32 @@ impl Property {
     }
 }
 
+/// The first type argument of `ty` if it is a `wrapper<T>`.
+fn unwrap_type<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != wrapper {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    }
+}
+
 impl FieldAttributes {
+    /// The type of the value of the property, without the `Option` and `Var` wrapping it.
+    pub fn value_type(&self) -> &Type {
+        let ty = &self.field.ty;
+        let ty = unwrap_type(ty, "Option").unwrap_or(ty);
+        unwrap_type(ty, "Var").unwrap_or(ty)
+    }
+
     pub fn property_names(&self) -> Vec<(Extension, Option<&StaticDefault>, &Path)> {
         match (
             &self.static_prop,
@@ -164,6 +194,23 @@ impl FieldAttributes {
     }
 }
 
+/// Checks run on the values of static and var properties.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Validation {
+    /// A range expression the value must be in.
+    pub range: Option<Expr>,
+    /// The value must have an `is_empty` function returning false.
+    pub non_empty: bool,
+    /// Function of type `fn(&T) -> Result<(), String>`.
+    pub validate: Option<Path>,
+}
+
+impl Validation {
+    pub fn is_empty(&self) -> bool {
+        self.range.is_none() && !self.non_empty && self.validate.is_none()
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum StaticDefault {
     Expression(Expr),
@@ -177,6 +224,8 @@ impl FieldAttributes {
         let mut static_bound = None;
         let mut var_prop = None;
         let mut var_bound = None;
+        let mut bind = None;
+        let mut validation = Validation::default();
         let mut fluent = None;
         let mut component = None;
         let mut child = None;
@@ -206,6 +255,12 @@ impl FieldAttributes {
                 "var_bound" if static_bound.is_none() => {
                     var_bound = Some(require_type_predicate(parse_from_lit(expr)?)?)
                 }
+                "bind" if bind.is_none() => bind = Some(require_func_path(parse_from_lit(expr)?)?),
+                "range" if validation.range.is_none() => validation.range = Some(expr),
+                "non_empty" if !validation.non_empty => validation.non_empty = require_bool(expr)?,
+                "validate" if validation.validate.is_none() => {
+                    validation.validate = Some(parse_from_lit(expr)?)
+                }
                 "fluent" if fluent.is_none() => {
                     fluent = Some(require_func_path(parse_from_lit(expr)?)?)
                 }
@@ -256,6 +311,26 @@ impl FieldAttributes {
                 field.ident.span(),
                 "Bounds only apply to var properties",
             ))
+        } else if !validation.is_empty() && static_prop.is_none() && var_prop.is_none() {
+            Err(Error::new(
+                field.ident.span(),
+                "Validation only applies to static and var properties",
+            ))
+        } else if (validation.range.is_some() || validation.validate.is_some())
+            && static_prop.is_some()
+            && var_prop.is_some()
+            && static_prop != var_prop
+        {
+            Err(Error::new(
+                field.ident.span(),
+                "Range and validate only apply to properties without a separate var function, \
+                as the var function may take a different type",
+            ))
+        } else if bind.is_some() && (static_prop.is_none() || var_prop.is_none()) {
+            Err(Error::new(
+                field.ident.span(),
+                "Bindings only apply to properties that are both static and var",
+            ))
         } else {
             Ok(FieldAttributes {
                 field,
@@ -264,6 +339,8 @@ impl FieldAttributes {
                 static_bound,
                 var_prop,
                 var_bound,
+                bind,
+                validation,
                 fluent,
                 component,
                 child,
@@ -0,0 +1,81 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::spanned::Spanned;
+use syn::{Attribute, DataEnum, Error, Fields, Generics, Path};
+
+use crate::widget_builder::attributes::{get_attributes, parse_from_lit, require_attribute};
+use crate::widget_builder::IMPORT;
+
+/// An enum used as the value of a property, which can be written out as the path of its variant.
+#[derive(Clone)]
+pub struct PropertyEnum {
+    name: Ident,
+    generics: Generics,
+    type_path: Path,
+    variants: Vec<Ident>,
+}
+
+impl PropertyEnum {
+    pub fn new(
+        name: Ident,
+        generics: Generics,
+        attributes: &[Attribute],
+        data: DataEnum,
+    ) -> syn::Result<Self> {
+        let mut type_path = None;
+        for (attribute, expr) in get_attributes(attributes)? {
+            match attribute.to_string().as_str() {
+                "type_path" if type_path.is_none() => type_path = Some(parse_from_lit(expr)?),
+                _ => return Err(Error::new(attribute.span(), "Unexpected attribute")),
+            }
+        }
+        let type_path = require_attribute(type_path, Span::call_site, "type_path")?;
+
+        let variants = data
+            .variants
+            .into_iter()
+            .map(|v| match v.fields {
+                Fields::Unit => Ok(v.ident),
+                fields => Err(Error::new(
+                    fields.span(),
+                    "Only enums with unit variants can be used as properties",
+                )),
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+        if variants.is_empty() {
+            return Err(Error::new(
+                name.span(),
+                "Enums used as properties must have at least one variant",
+            ));
+        }
+
+        Ok(Self {
+            name,
+            generics,
+            type_path,
+            variants,
+        })
+    }
+}
+
+impl ToTokens for PropertyEnum {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let name = &self.name;
+        let type_path = &self.type_path;
+        let arms = self
+            .variants
+            .iter()
+            .map(|variant| quote!(Self::#variant => #IMPORT::quote!(#type_path::#variant),));
+
+        tokens.extend(quote! {
+            impl #impl_generics #IMPORT::ToTokens for #name #ty_generics #where_clause {
+                fn to_tokens(&self, tokens: &mut #IMPORT::TokenStream) {
+                    tokens.extend(match self {
+                        #(#arms)*
+                    })
+                }
+            }
+        })
+    }
+}
@@ -3,19 +3,28 @@ use std::marker::PhantomData;
 use serde::Deserialize;
 
 use gui_custom::glazier::kurbo::{Shape, Size};
-use gui_custom::glazier::Cursor;
+use gui_custom::glazier::{Cursor, PointerButton};
 use gui_custom::layout::LayoutConstraints;
 use gui_custom::parse::WidgetDeclaration;
+use gui_custom::theme::ThemeToken;
 use gui_custom::vello::kurbo::{Affine, Vec2};
 use gui_custom::vello::peniko::{BlendMode, Brush, Color, Compose, Fill, Mix, Stroke};
 use gui_custom::vello::SceneFragment;
-use gui_custom::widget::{RenderHandle, ResizeHandle, UpdateHandle, Widget, WidgetEvent, WidgetID};
+use gui_custom::widget::{
+    PointerInfo, RenderHandle, ResizeHandle, UpdateHandle, Widget, WidgetEvent, WidgetID,
+};
 use gui_custom::WidgetBuilder;
 use gui_custom::{widget, Colour, SceneBuilder, ToComponent, ToHandler, Var};
 use widget::EventHandle;
 
 pub trait ButtonHandler<T: ToHandler<BaseHandler = Self>> {
     fn on_press(&mut self) {}
+    fn on_hover_enter(&mut self, _pointer: PointerInfo) {}
+    fn on_hover_leave(&mut self, _pointer: PointerInfo) {}
+    fn on_secondary_press(&mut self, _pointer: PointerInfo) {}
+    fn on_double_click(&mut self, _pointer: PointerInfo) {}
+    /// Called instead of `on_press` when the button is held down.
+    fn on_long_press(&mut self, _pointer: PointerInfo) {}
 }
 
 pub struct Button<T: ToHandler<BaseHandler = C>, C: ToComponent, W: Widget<C>> {
@@ -26,6 +35,8 @@ pub struct Button<T: ToHandler<BaseHandler = C>, C: ToComponent, W: Widget<C>> {
     hover_colour: Colour,
     border_colour: Colour,
     disabled: bool,
+    /// Set when the current press has become a long press, so releasing it is not a press.
+    long_pressed: bool,
     child: Option<W>,
     phantom: PhantomData<(T, C)>,
 }
@@ -40,6 +51,7 @@ impl<T: ToHandler<BaseHandler = C>, C: ToComponent, W: Widget<C>> Button<T, C, W
             hover_colour: Default::default(),
             border_colour: Default::default(),
             disabled: Default::default(),
+            long_pressed: false,
             child: None,
             phantom: PhantomData,
         }
@@ -165,35 +177,71 @@ impl<T: ToHandler<BaseHandler = C>, C: ToComponent + ButtonHandler<T>, W: Widget
         if self.disabled {
             return;
         }
-        let hit = event.get_point().map_or(false, |pos| {
-            handle
-                .get_global_rect(self.id)
-                .to_rounded_rect(4.0)
-                .contains(pos)
-        });
+        let rect = handle.get_global_rect(self.id);
+        let hit = event
+            .get_point()
+            .map_or(false, |pos| rect.to_rounded_rect(4.0).contains(pos));
         match event {
-            WidgetEvent::PointerUp(_) => {
+            WidgetEvent::PointerUp(e) => {
+                if e.button != PointerButton::Primary {
+                    return;
+                }
                 handle.set_active(self.id, false);
                 handle.invalidate_id(self.id);
-                if hit {
+                if hit && !self.long_pressed {
                     handle.get_handler().on_press();
                 }
             }
-            WidgetEvent::PointerDown(_) => {
-                if hit {
+            WidgetEvent::PointerDown(e) => {
+                if !hit {
+                    return;
+                }
+                if e.button == PointerButton::Secondary {
+                    let pointer = PointerInfo::new(e, rect.origin());
+                    handle.get_handler().on_secondary_press(pointer);
+                } else if e.button == PointerButton::Primary {
+                    self.long_pressed = false;
                     handle.set_active(self.id, true);
                     handle.invalidate_id(self.id);
                 }
             }
-            WidgetEvent::PointerMove(_) => {
+            WidgetEvent::PointerMove(e) => {
                 if hit {
                     handle.set_cursor(&Cursor::Pointer);
                     if handle.add_hover(self.id) {
                         handle.invalidate_id(self.id);
+                        let pointer = PointerInfo::new(e, rect.origin());
+                        handle.get_handler().on_hover_enter(pointer);
                     }
                 }
             }
-            WidgetEvent::HoverChange | WidgetEvent::ActiveChange => handle.invalidate_id(self.id),
+            WidgetEvent::DoubleClick(e) => {
+                if hit {
+                    let pointer = PointerInfo::new(e, rect.origin());
+                    handle.get_handler().on_double_click(pointer);
+                }
+            }
+            WidgetEvent::LongPress(e) => {
+                if hit && handle.is_active(self.id) {
+                    self.long_pressed = true;
+                    let pointer = PointerInfo::new(e, rect.origin());
+                    handle.get_handler().on_long_press(pointer);
+                }
+            }
+            WidgetEvent::HoverChange(e) => {
+                handle.invalidate_id(self.id);
+                let pointer = PointerInfo::new(e, rect.origin());
+                handle.get_handler().on_hover_leave(pointer);
+            }
+            WidgetEvent::ActiveChange => handle.invalidate_id(self.id),
+            WidgetEvent::DragStart(_)
+            | WidgetEvent::DragEnter(_)
+            | WidgetEvent::DragOver(_)
+            | WidgetEvent::DragLeave(_)
+            | WidgetEvent::Drop(_)
+            | WidgetEvent::KeyDown(_)
+            | WidgetEvent::FileDialog(_)
+            | WidgetEvent::OptionChosen(_) => {}
         }
     }
 }
@@ -203,25 +251,26 @@ impl<T: ToHandler<BaseHandler = C>, C: ToComponent + ButtonHandler<T>, W: Widget
 #[widget(
     name = "Button",
     type_path = "::gui::gui_widget::Button<#handler, #component, #child>",
-    init_path = "new"
+    init_path = "new",
+    handler_trait = "::gui::gui_widget::button::ButtonHandler"
 )]
 pub struct ButtonBuilder {
     #[widget(property = "set_disabled")]
     disabled: Option<Var<bool>>,
     #[widget(property = "set_background_colour")]
-    #[widget(default = Colour(Color::WHITE))]
+    #[widget(default = ThemeToken("surface"))]
     background_colour: Option<Var<Colour>>,
     #[widget(property = "set_disabled_colour")]
-    #[widget(default = Colour(Color::rgb8(241, 243, 245)))]
+    #[widget(default = ThemeToken("surface_disabled"))]
     disabled_colour: Option<Var<Colour>>,
     #[widget(property = "set_clicked_colour")]
-    #[widget(default = Colour(Color::rgb8(248, 249, 250)))]
+    #[widget(default = ThemeToken("surface_active"))]
     clicked_colour: Option<Var<Colour>>,
     #[widget(property = "set_hover_colour")]
-    #[widget(default = Colour(Color::rgb8(248, 249, 250)))]
+    #[widget(default = ThemeToken("surface_hover"))]
     hover_colour: Option<Var<Colour>>,
     #[widget(property = "set_border_colour")]
-    #[widget(default = Colour(Color::rgb8(206, 212, 218)))]
+    #[widget(default = ThemeToken("border"))]
     border_colour: Option<Var<Colour>>,
     #[widget(child = "get_widget")]
     child: Option<WidgetDeclaration>,
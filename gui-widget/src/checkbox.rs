@@ -0,0 +1,181 @@
+use serde::Deserialize;
+
+use gui_custom::glazier::kurbo::Size;
+use gui_custom::glazier::Cursor;
+use gui_custom::layout::LayoutConstraints;
+use gui_custom::theme::ThemeToken;
+use gui_custom::vello::kurbo::{Affine, BezPath, Point};
+use gui_custom::vello::peniko::{Brush, Fill, Stroke};
+use gui_custom::widget::{
+    EventHandle, RenderHandle, ResizeHandle, UpdateHandle, Widget, WidgetEvent, WidgetID,
+};
+use gui_custom::WidgetBuilder;
+use gui_custom::{Colour, SceneBuilder, ToComponent, Var};
+
+const SIZE: f64 = 18.0;
+
+/// A box that is checked and unchecked when pressed. Bind `checked` to a variable to store the
+/// value in the component.
+pub struct Checkbox {
+    id: WidgetID,
+    checked: bool,
+    /// Set when the checkbox is pressed, until taken by the component.
+    changed: Option<bool>,
+    disabled: bool,
+    background_colour: Colour,
+    checked_colour: Colour,
+    border_colour: Colour,
+}
+
+impl Checkbox {
+    pub fn new(id: WidgetID) -> Self {
+        Checkbox {
+            id,
+            checked: false,
+            changed: None,
+            disabled: false,
+            background_colour: Default::default(),
+            checked_colour: Default::default(),
+            border_colour: Default::default(),
+        }
+    }
+
+    pub fn set_checked(&mut self, checked: bool, handle: &mut UpdateHandle) {
+        if self.checked != checked {
+            self.checked = checked;
+            handle.invalidate_id(self.id)
+        }
+    }
+
+    pub fn take_checked(&mut self) -> Option<bool> {
+        self.changed.take()
+    }
+
+    pub fn set_disabled(&mut self, disabled: bool, handle: &mut UpdateHandle) {
+        self.disabled = disabled;
+        handle.invalidate_id(self.id)
+    }
+    pub fn set_background_colour(&mut self, colour: Colour, handle: &mut UpdateHandle) {
+        self.background_colour = colour;
+        handle.invalidate_id(self.id)
+    }
+    pub fn set_checked_colour(&mut self, colour: Colour, handle: &mut UpdateHandle) {
+        self.checked_colour = colour;
+        handle.invalidate_id(self.id)
+    }
+    pub fn set_border_colour(&mut self, colour: Colour, handle: &mut UpdateHandle) {
+        self.border_colour = colour;
+        handle.invalidate_id(self.id)
+    }
+}
+
+impl<C: ToComponent> Widget<C> for Checkbox {
+    fn id(&self) -> WidgetID {
+        self.id
+    }
+
+    fn render(&mut self, scene: &mut SceneBuilder, handle: &mut RenderHandle<C>) {
+        let rect = handle.get_local_rect(self.id).to_rounded_rect(3.0);
+        let fill_colour = if self.checked {
+            self.checked_colour
+        } else {
+            self.background_colour
+        };
+        scene.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            &Brush::Solid(fill_colour.0),
+            None,
+            &rect,
+        );
+        scene.stroke(
+            &Stroke::new(1.0),
+            Affine::IDENTITY,
+            &Brush::Solid(self.border_colour.0),
+            None,
+            &rect,
+        );
+
+        if self.checked {
+            let mut tick = BezPath::new();
+            tick.move_to(Point::new(SIZE * 0.25, SIZE * 0.5));
+            tick.line_to(Point::new(SIZE * 0.45, SIZE * 0.7));
+            tick.line_to(Point::new(SIZE * 0.75, SIZE * 0.3));
+            scene.stroke(
+                &Stroke::new(2.0),
+                Affine::IDENTITY,
+                &Brush::Solid(self.background_colour.0),
+                None,
+                &tick,
+            );
+        }
+    }
+
+    fn resize(&mut self, constraints: LayoutConstraints, _handle: &mut ResizeHandle<C>) -> Size {
+        constraints.max_clamp(Size::new(SIZE, SIZE))
+    }
+
+    fn event(&mut self, event: WidgetEvent, handle: &mut EventHandle<C>) {
+        if self.disabled {
+            return;
+        }
+        let hit = event
+            .get_point()
+            .is_some_and(|pos| handle.get_global_rect(self.id).contains(pos));
+        match event {
+            WidgetEvent::PointerUp(_) => {
+                if hit && handle.is_active(self.id) {
+                    self.checked = !self.checked;
+                    self.changed = Some(self.checked);
+                    handle.invalidate_id(self.id);
+                }
+                handle.set_active(self.id, false);
+            }
+            WidgetEvent::PointerDown(_) => {
+                if hit {
+                    handle.set_active(self.id, true);
+                }
+            }
+            WidgetEvent::PointerMove(_) => {
+                if hit {
+                    handle.set_cursor(&Cursor::Pointer);
+                }
+            }
+            WidgetEvent::DragStart(_)
+            | WidgetEvent::DragEnter(_)
+            | WidgetEvent::DragOver(_)
+            | WidgetEvent::DragLeave(_)
+            | WidgetEvent::Drop(_)
+            | WidgetEvent::KeyDown(_)
+            | WidgetEvent::FileDialog(_)
+            | WidgetEvent::OptionChosen(_)
+            | WidgetEvent::DoubleClick(_)
+            | WidgetEvent::LongPress(_)
+            | WidgetEvent::HoverChange(_)
+            | WidgetEvent::ActiveChange => {}
+        }
+    }
+}
+
+#[derive(Deserialize, WidgetBuilder, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+#[widget(
+    name = "Checkbox",
+    type_path = "::gui::gui_widget::Checkbox",
+    init_path = "new"
+)]
+pub struct CheckboxBuilder {
+    #[widget(property = "set_checked", bind = "take_checked")]
+    checked: Option<Var<bool>>,
+    #[widget(property = "set_disabled")]
+    disabled: Option<Var<bool>>,
+    #[widget(property = "set_background_colour")]
+    #[widget(default = ThemeToken("surface"))]
+    background_colour: Option<Var<Colour>>,
+    #[widget(property = "set_checked_colour")]
+    #[widget(default = ThemeToken("primary"))]
+    checked_colour: Option<Var<Colour>>,
+    #[widget(property = "set_border_colour")]
+    #[widget(default = ThemeToken("border"))]
+    border_colour: Option<Var<Colour>>,
+}
@@ -0,0 +1,239 @@
+use std::marker::PhantomData;
+
+use serde::Deserialize;
+
+use gui_custom::drag::DragPayload;
+use gui_custom::glazier::kurbo::Size;
+use gui_custom::glazier::{Cursor, PointerButton};
+use gui_custom::layout::LayoutConstraints;
+use gui_custom::parse::WidgetDeclaration;
+use gui_custom::vello::kurbo::{Point, Vec2};
+use gui_custom::widget::{
+    EventHandle, PointerInfo, RenderHandle, ResizeHandle, UpdateHandle, Widget, WidgetEvent,
+    WidgetID,
+};
+use gui_custom::WidgetBuilder;
+use gui_custom::{SceneBuilder, ToComponent, ToHandler, Var};
+
+/// How far the pointer needs to move while pressed before it starts a drag.
+const DRAG_DISTANCE: f64 = 4.0;
+
+pub trait ClickableHandler<T: ToHandler<BaseHandler = Self>> {
+    fn on_press(&mut self) {}
+    /// Called when the widget is pressed down with the primary button, and again when released.
+    fn on_active_change(&mut self, _active: bool) {}
+    fn on_hover_enter(&mut self, _pointer: PointerInfo) {}
+    fn on_hover_leave(&mut self, _pointer: PointerInfo) {}
+    fn on_secondary_press(&mut self, _pointer: PointerInfo) {}
+    fn on_double_click(&mut self, _pointer: PointerInfo) {}
+    /// Called instead of `on_press` when the widget is held down.
+    fn on_long_press(&mut self, _pointer: PointerInfo) {}
+    fn on_drag_start(&mut self, _pointer: PointerInfo) {}
+    /// Called when the pointer moves during a drag, with the `offset` from where the drag started.
+    fn on_drag(&mut self, _pointer: PointerInfo, _offset: Vec2) {}
+    /// Called instead of `on_press` when the drag ends.
+    fn on_drag_end(&mut self, _pointer: PointerInfo) {}
+    /// The data to drag and drop onto other widgets when the widget is dragged, or `None` if it
+    /// can not be dropped.
+    fn drag_payload(&mut self) -> Option<DragPayload> {
+        None
+    }
+    /// Called when a drag moves onto the widget, if `accept_drops` is set.
+    fn on_drag_enter(&mut self, _payload: &DragPayload) {}
+    /// Called every time a drag moves over the widget, with the position relative to the widget.
+    fn on_drag_over(&mut self, _payload: &DragPayload, _pos: Point) {}
+    fn on_drag_leave(&mut self, _payload: &DragPayload) {}
+    /// Called when a drag is released over the widget, with the position relative to the widget.
+    fn on_drop(&mut self, _payload: &DragPayload, _pos: Point) {}
+}
+
+/// Makes any widget interactive by sending its pointer gestures to a [`ClickableHandler`]. The
+/// child is drawn as it is, and still receives the pointer events. Widgets can be dragged onto
+/// clickables that accept drops by returning a payload from
+/// [`ClickableHandler::drag_payload`].
+pub struct Clickable<T: ToHandler<BaseHandler = C>, C: ToComponent, W: Widget<C>> {
+    id: WidgetID,
+    disabled: bool,
+    accept_drops: bool,
+    /// Where the primary button was pressed, while it is held down.
+    press_start: Option<Point>,
+    dragging: bool,
+    /// Set when the current press has become a long press, so releasing it is not a press.
+    long_pressed: bool,
+    child: Option<W>,
+    phantom: PhantomData<(T, C)>,
+}
+
+impl<T: ToHandler<BaseHandler = C>, C: ToComponent, W: Widget<C>> Clickable<T, C, W> {
+    pub fn new(id: WidgetID) -> Self {
+        Clickable {
+            id,
+            disabled: false,
+            accept_drops: false,
+            press_start: None,
+            dragging: false,
+            long_pressed: false,
+            child: None,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn set_disabled(&mut self, disabled: bool, handle: &mut UpdateHandle) {
+        self.disabled = disabled;
+        handle.invalidate_id(self.id)
+    }
+
+    pub fn set_accept_drops(&mut self, accept_drops: bool, handle: &mut UpdateHandle) {
+        if self.accept_drops != accept_drops {
+            self.accept_drops = accept_drops;
+            handle.resize();
+        }
+    }
+
+    pub fn get_widget(&mut self) -> &mut Option<W> {
+        &mut self.child
+    }
+}
+
+impl<T: ToHandler<BaseHandler = C>, C: ToComponent + ClickableHandler<T>, W: Widget<C>> Widget<C>
+    for Clickable<T, C, W>
+{
+    fn id(&self) -> WidgetID {
+        self.id
+    }
+
+    fn render(&mut self, scene: &mut SceneBuilder, handle: &mut RenderHandle<C>) {
+        handle.render_widgets(scene, [self.child.as_mut().unwrap()].into_iter());
+    }
+
+    fn resize(&mut self, constraints: LayoutConstraints, handle: &mut ResizeHandle<C>) -> Size {
+        if self.accept_drops {
+            handle.add_drop_target(self.id);
+        }
+        handle.layout_widget(Point::ZERO, self.child.as_mut().unwrap(), constraints)
+    }
+
+    fn event(&mut self, event: WidgetEvent, handle: &mut EventHandle<C>) {
+        // Hover and active changes are sent to the widget they are for, so are not forwarded.
+        if event.get_point().is_some() || matches!(event, WidgetEvent::KeyDown(_)) {
+            handle.propagate_event(event.clone(), [self.child.as_mut().unwrap()].into_iter());
+        }
+        if self.disabled {
+            return;
+        }
+        let rect = handle.get_global_rect(self.id);
+        let hit = event.get_point().is_some_and(|pos| rect.contains(pos));
+        match event {
+            WidgetEvent::PointerUp(e) => {
+                if e.button != PointerButton::Primary || self.press_start.take().is_none() {
+                    return;
+                }
+                handle.set_active(self.id, false);
+                let handler = handle.get_handler();
+                handler.on_active_change(false);
+                if self.dragging {
+                    self.dragging = false;
+                    handler.on_drag_end(PointerInfo::new(e, rect.origin()));
+                } else if hit && !self.long_pressed {
+                    handler.on_press();
+                }
+            }
+            WidgetEvent::PointerDown(e) => {
+                if !hit {
+                    return;
+                }
+                if e.button == PointerButton::Secondary {
+                    let pointer = PointerInfo::new(e, rect.origin());
+                    handle.get_handler().on_secondary_press(pointer);
+                } else if e.button == PointerButton::Primary {
+                    self.press_start = Some(e.pos);
+                    self.long_pressed = false;
+                    handle.set_active(self.id, true);
+                    handle.get_handler().on_active_change(true);
+                }
+            }
+            WidgetEvent::PointerMove(e) => {
+                let pointer = PointerInfo::new(e, rect.origin());
+                if hit {
+                    handle.set_cursor(&Cursor::Pointer);
+                    if handle.add_hover(self.id) {
+                        handle.get_handler().on_hover_enter(pointer);
+                    }
+                }
+                if let Some(start) = self.press_start {
+                    let offset = e.pos - start;
+                    if !self.dragging && offset.hypot() > DRAG_DISTANCE {
+                        self.dragging = true;
+                        let start = PointerInfo {
+                            pos: start - rect.origin().to_vec2(),
+                            ..pointer
+                        };
+                        handle.get_handler().on_drag_start(start);
+                    }
+                    if self.dragging {
+                        handle.get_handler().on_drag(pointer, offset);
+                    }
+                }
+            }
+            WidgetEvent::DoubleClick(e) => {
+                if hit {
+                    let pointer = PointerInfo::new(e, rect.origin());
+                    handle.get_handler().on_double_click(pointer);
+                }
+            }
+            WidgetEvent::LongPress(e) => {
+                if hit && !self.dragging && handle.is_active(self.id) {
+                    self.long_pressed = true;
+                    let pointer = PointerInfo::new(e, rect.origin());
+                    handle.get_handler().on_long_press(pointer);
+                }
+            }
+            WidgetEvent::DragStart(e) => {
+                if handle.is_active(self.id) {
+                    if let Some(payload) = handle.get_handler().drag_payload() {
+                        handle.start_drag(self.id, payload, e.pos);
+                    }
+                }
+            }
+            WidgetEvent::DragEnter(e) => handle.get_handler().on_drag_enter(&e.payload),
+            WidgetEvent::DragOver(e) => {
+                let pos = e.pos - rect.origin().to_vec2();
+                handle.get_handler().on_drag_over(&e.payload, pos);
+            }
+            WidgetEvent::DragLeave(e) => handle.get_handler().on_drag_leave(&e.payload),
+            WidgetEvent::Drop(e) => {
+                let pos = e.pos - rect.origin().to_vec2();
+                handle.get_handler().on_drop(&e.payload, pos);
+            }
+            WidgetEvent::KeyDown(_) | WidgetEvent::FileDialog(_) | WidgetEvent::OptionChosen(_) => {
+            }
+            WidgetEvent::HoverChange(e) => {
+                let pointer = PointerInfo::new(e, rect.origin());
+                handle.get_handler().on_hover_leave(pointer);
+            }
+            WidgetEvent::ActiveChange => {
+                if self.press_start.take().is_some() {
+                    self.dragging = false;
+                    handle.get_handler().on_active_change(false);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Deserialize, WidgetBuilder, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+#[widget(
+    name = "Clickable",
+    type_path = "::gui::gui_widget::Clickable<#handler, #component, #child>",
+    init_path = "new",
+    handler_trait = "::gui::gui_widget::clickable::ClickableHandler"
+)]
+pub struct ClickableBuilder {
+    #[widget(property = "set_disabled")]
+    disabled: Option<Var<bool>>,
+    #[widget(property = "set_accept_drops")]
+    accept_drops: Option<Var<bool>>,
+    #[widget(child = "get_widget")]
+    child: Option<WidgetDeclaration>,
+}
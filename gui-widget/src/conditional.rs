@@ -0,0 +1,96 @@
+use serde::Deserialize;
+
+use gui_custom::glazier::kurbo::Size;
+use gui_custom::layout::LayoutConstraints;
+use gui_custom::parse::WidgetDeclaration;
+use gui_custom::widget::{
+    EventHandle, RenderHandle, ResizeHandle, UpdateHandle, Widget, WidgetEvent, WidgetID,
+};
+use gui_custom::WidgetBuilder;
+use gui_custom::{Point, SceneBuilder, ToComponent, Var};
+
+/// Shows one of two children depending on a condition. Both children are kept when switching,
+/// so the hidden child keeps its state. Layout files normally create this widget using an
+/// `if` block rather than by name.
+pub struct If<W> {
+    id: WidgetID,
+    condition: bool,
+    then_child: Option<W>,
+    else_child: Option<W>,
+}
+
+impl<W> If<W> {
+    pub fn new(id: WidgetID) -> Self {
+        Self {
+            id,
+            condition: false,
+            then_child: None,
+            else_child: None,
+        }
+    }
+
+    pub fn set_condition(&mut self, condition: bool, handle: &mut UpdateHandle) {
+        if self.condition != condition {
+            self.condition = condition;
+            handle.resize();
+        }
+    }
+
+    pub fn then_widget(&mut self) -> &mut Option<W> {
+        &mut self.then_child
+    }
+
+    pub fn else_widget(&mut self) -> &mut Option<W> {
+        &mut self.else_child
+    }
+
+    fn active_child(&mut self) -> Option<&mut W> {
+        if self.condition {
+            self.then_child.as_mut()
+        } else {
+            self.else_child.as_mut()
+        }
+    }
+}
+
+impl<C: ToComponent, W: Widget<C>> Widget<C> for If<W> {
+    fn id(&self) -> WidgetID {
+        self.id
+    }
+
+    fn render(&mut self, scene: &mut SceneBuilder, handle: &mut RenderHandle<C>) {
+        handle.render_widgets(scene, self.active_child().into_iter());
+    }
+
+    fn resize(&mut self, constraints: LayoutConstraints, handle: &mut ResizeHandle<C>) -> Size {
+        match self.active_child() {
+            Some(child) => handle.layout_widget(Point::ZERO, child, constraints),
+            None => {
+                handle.hide_widget(self.id);
+                Size::ZERO
+            }
+        }
+    }
+
+    fn event(&mut self, event: WidgetEvent, handle: &mut EventHandle<C>) {
+        handle.propagate_event(event, self.active_child().into_iter())
+    }
+}
+
+#[derive(Deserialize, WidgetBuilder, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+#[widget(
+    name = "If",
+    type_path = "::gui::gui_widget::If<#child>",
+    init_path = "new"
+)]
+pub struct IfBuilder {
+    #[serde(rename = "if")]
+    #[widget(property = "set_condition")]
+    condition: Option<Var<bool>>,
+    #[widget(child = "then_widget")]
+    then: Option<WidgetDeclaration>,
+    #[serde(rename = "else")]
+    #[widget(child = "else_widget")]
+    otherwise: Option<WidgetDeclaration>,
+}
@@ -0,0 +1,142 @@
+use std::marker::PhantomData;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use gui_custom::glazier::kurbo::Size;
+use gui_custom::glazier::{Cursor, FileDialogOptions, PointerButton};
+use gui_custom::layout::LayoutConstraints;
+use gui_custom::parse::WidgetDeclaration;
+use gui_custom::vello::kurbo::Point;
+use gui_custom::widget::{
+    EventHandle, RenderHandle, ResizeHandle, UpdateHandle, Widget, WidgetEvent, WidgetID,
+};
+use gui_custom::WidgetBuilder;
+use gui_custom::{SceneBuilder, ToComponent, ToHandler, Var};
+
+pub trait FilePickerHandler<T: ToHandler<BaseHandler = Self>> {
+    /// Called with the files chosen in the dialog. A save dialog only chooses one file.
+    fn on_files_chosen(&mut self, _paths: Vec<PathBuf>) {}
+    fn on_cancel(&mut self) {}
+}
+
+/// Opens a file dialog when the child is pressed, sending the chosen files to a
+/// [`FilePickerHandler`]. The child is drawn as it is.
+pub struct FilePicker<T: ToHandler<BaseHandler = C>, C: ToComponent, W: Widget<C>> {
+    id: WidgetID,
+    disabled: bool,
+    /// Choose where to save a file instead of files to open.
+    save: bool,
+    multiple: bool,
+    child: Option<W>,
+    phantom: PhantomData<(T, C)>,
+}
+
+impl<T: ToHandler<BaseHandler = C>, C: ToComponent, W: Widget<C>> FilePicker<T, C, W> {
+    pub fn new(id: WidgetID) -> Self {
+        FilePicker {
+            id,
+            disabled: false,
+            save: false,
+            multiple: false,
+            child: None,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn set_disabled(&mut self, disabled: bool, _handle: &mut UpdateHandle) {
+        self.disabled = disabled;
+    }
+    pub fn set_save(&mut self, save: bool, _handle: &mut UpdateHandle) {
+        self.save = save;
+    }
+    pub fn set_multiple(&mut self, multiple: bool, _handle: &mut UpdateHandle) {
+        self.multiple = multiple;
+    }
+    pub fn get_widget(&mut self) -> &mut Option<W> {
+        &mut self.child
+    }
+
+    fn open_dialog(&self, handle: &mut EventHandle<C>) {
+        let mut options = FileDialogOptions::new();
+        if self.save {
+            handle.save_file_dialog(self.id, options);
+        } else {
+            if self.multiple {
+                options = options.multi_selection();
+            }
+            handle.open_file_dialog(self.id, options);
+        }
+    }
+}
+
+impl<T: ToHandler<BaseHandler = C>, C: ToComponent + FilePickerHandler<T>, W: Widget<C>> Widget<C>
+    for FilePicker<T, C, W>
+{
+    fn id(&self) -> WidgetID {
+        self.id
+    }
+
+    fn render(&mut self, scene: &mut SceneBuilder, handle: &mut RenderHandle<C>) {
+        handle.render_widgets(scene, [self.child.as_mut().unwrap()].into_iter());
+    }
+
+    fn resize(&mut self, constraints: LayoutConstraints, handle: &mut ResizeHandle<C>) -> Size {
+        handle.layout_widget(Point::ZERO, self.child.as_mut().unwrap(), constraints)
+    }
+
+    fn event(&mut self, event: WidgetEvent, handle: &mut EventHandle<C>) {
+        if let WidgetEvent::FileDialog(result) = event {
+            let handler = handle.get_handler();
+            if result.paths.is_empty() {
+                handler.on_cancel();
+            } else {
+                handler.on_files_chosen(result.paths.clone());
+            }
+            return;
+        }
+        if event.get_point().is_some() || matches!(event, WidgetEvent::KeyDown(_)) {
+            handle.propagate_event(event.clone(), [self.child.as_mut().unwrap()].into_iter());
+        }
+        if self.disabled {
+            return;
+        }
+        let hit = event
+            .get_point()
+            .is_some_and(|pos| handle.get_global_rect(self.id).contains(pos));
+        match event {
+            WidgetEvent::PointerDown(e) if hit && e.button == PointerButton::Primary => {
+                handle.set_active(self.id, true);
+            }
+            WidgetEvent::PointerUp(e) if e.button == PointerButton::Primary => {
+                if handle.is_active(self.id) {
+                    handle.set_active(self.id, false);
+                    if hit {
+                        self.open_dialog(handle);
+                    }
+                }
+            }
+            WidgetEvent::PointerMove(_) if hit => handle.set_cursor(&Cursor::Pointer),
+            _ => {}
+        }
+    }
+}
+
+#[derive(Deserialize, WidgetBuilder, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+#[widget(
+    name = "FilePicker",
+    type_path = "::gui::gui_widget::FilePicker<#handler, #component, #child>",
+    init_path = "new",
+    handler_trait = "::gui::gui_widget::file_picker::FilePickerHandler"
+)]
+pub struct FilePickerBuilder {
+    #[widget(property = "set_disabled")]
+    disabled: Option<Var<bool>>,
+    #[widget(property = "set_save")]
+    save: Option<Var<bool>>,
+    #[widget(property = "set_multiple")]
+    multiple: Option<Var<bool>>,
+    #[widget(child = "get_widget")]
+    child: Option<WidgetDeclaration>,
+}
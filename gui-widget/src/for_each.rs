@@ -0,0 +1,280 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::iter;
+
+use serde::Deserialize;
+
+use gui_custom::__private::{quote, Ident, TokenStream};
+use gui_custom::glazier::kurbo::Size;
+use gui_custom::layout::LayoutConstraints;
+use gui_custom::parse::ForEachDeclaration;
+use gui_custom::vello::kurbo::Affine;
+use gui_custom::vello::SceneFragment;
+use gui_custom::widget::{
+    EventHandle, RenderHandle, ResizeHandle, RuntimeID, UpdateHandle, Widget, WidgetBuilder,
+    WidgetEvent, WidgetID,
+};
+use gui_custom::{Children, MutWidgetChildren, Point, SceneBuilder, ToComponent};
+
+/// Hashes the key of an item, used by generated code to match items when the list changes.
+pub fn hash_key<K: Hash + ?Sized>(key: &K) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+struct Item<W> {
+    key: u64,
+    /// Hash of the item and its index, used to skip updating unchanged items.
+    hash: u64,
+    /// Each item has its own runtime ID, so that the widgets of each item can share the widget
+    /// IDs of the template.
+    runtime_id: RuntimeID,
+    widget: W,
+}
+
+/// Creates a widget from a template for each item of a list, laying the widgets out vertically.
+/// Layout files normally create this widget using a `forEach` block rather than by name.
+pub struct ForEach<W> {
+    id: WidgetID,
+    items: Vec<Item<W>>,
+}
+
+impl<W> ForEach<W> {
+    pub fn new(id: WidgetID) -> Self {
+        Self { id, items: vec![] }
+    }
+
+    /// Matches `items` with the existing widgets using `key`, creating widgets for new items with
+    /// `create` and removing the widgets of removed items. `update` is only called with the items
+    /// that are new or have changed, unless `force_update` is true. Keys should be unique, items
+    /// with a key that has already been used are matched by how many times the key was used
+    /// before them.
+    pub fn update_items<T: Hash>(
+        &mut self,
+        items: &[T],
+        key: impl Fn(usize, &T) -> u64,
+        mut create: impl FnMut() -> W,
+        mut update: impl FnMut(&mut W, usize, &T, &mut UpdateHandle),
+        force_update: bool,
+        handle: &mut UpdateHandle,
+    ) {
+        let old_keys = self.items.iter().map(|i| i.key).collect::<Vec<_>>();
+        let mut old_items: HashMap<u64, Item<W>> = HashMap::new();
+        let mut removed = vec![];
+        for item in self.items.drain(..) {
+            if let Some(duplicate) = old_items.insert(item.key, item) {
+                removed.push(duplicate.runtime_id);
+            }
+        }
+
+        let mut uses: HashMap<u64, usize> = HashMap::new();
+        for (index, value) in items.iter().enumerate() {
+            let key = key(index, value);
+            let used = uses.entry(key).or_default();
+            let key = if *used == 0 {
+                key
+            } else {
+                hash_key(&(key, *used))
+            };
+            *used += 1;
+            let hash = hash_key(&(index, value));
+            let mut item = old_items.remove(&key).unwrap_or_else(|| Item {
+                key,
+                // Any hash would do as new items are always updated.
+                hash: !hash,
+                runtime_id: RuntimeID::next(),
+                widget: create(),
+            });
+            if force_update || item.hash != hash {
+                item.hash = hash;
+                handle.with_runtime_id(item.runtime_id, |handle| {
+                    update(&mut item.widget, index, value, handle)
+                });
+            }
+            self.items.push(item);
+        }
+
+        removed.extend(old_items.into_values().map(|i| i.runtime_id));
+        for &runtime_id in &removed {
+            handle.remove_runtime_id(runtime_id);
+        }
+        if !removed.is_empty() || !self.items.iter().map(|i| i.key).eq(old_keys) {
+            handle.resize();
+        }
+    }
+
+    /// The runtime IDs of the items.
+    pub fn runtime_ids(&self) -> impl Iterator<Item = RuntimeID> + '_ {
+        self.items.iter().map(|i| i.runtime_id)
+    }
+
+    /// Runs `f` with the widget of the item with the given runtime ID.
+    pub fn item_event<C: ToComponent>(
+        &mut self,
+        runtime_id: RuntimeID,
+        handle: &mut EventHandle<C>,
+        f: impl FnOnce(&mut W, &mut EventHandle<C>),
+    ) {
+        if let Some(item) = self.items.iter_mut().find(|i| i.runtime_id == runtime_id) {
+            handle.with_runtime_id(runtime_id, |handle| f(&mut item.widget, handle));
+        }
+    }
+}
+
+impl<C: ToComponent, W: Widget<C>> Widget<C> for ForEach<W> {
+    fn id(&self) -> WidgetID {
+        self.id
+    }
+
+    fn render(&mut self, scene: &mut SceneBuilder, handle: &mut RenderHandle<C>) {
+        let origin = handle.get_global_rect(self.id).origin();
+        for item in &mut self.items {
+            handle.with_runtime_id(item.runtime_id, |handle| {
+                let item_origin = handle.get_global_rect(item.widget.id()).origin();
+                let mut fragment = SceneFragment::new();
+                let mut builder = SceneBuilder::for_fragment(&mut fragment);
+                item.widget.render(&mut builder, handle);
+                scene.append(&fragment, Some(Affine::translate(item_origin - origin)));
+            });
+        }
+    }
+
+    fn resize(&mut self, constraints: LayoutConstraints, handle: &mut ResizeHandle<C>) -> Size {
+        let mut size = Size::ZERO;
+        for item in &mut self.items {
+            let remaining = constraints.max_clamp(Size::new(
+                constraints.get_max().width,
+                (constraints.get_max().height - size.height).max(0.0),
+            ));
+            let item_size = handle.with_runtime_id(item.runtime_id, |handle| {
+                handle.layout_widget(Point::new(0.0, size.height), &mut item.widget, remaining)
+            });
+            size = Size::new(
+                size.width.max(item_size.width),
+                size.height + item_size.height,
+            );
+        }
+        size
+    }
+
+    fn event(&mut self, event: WidgetEvent, handle: &mut EventHandle<C>) {
+        for item in &mut self.items {
+            handle.with_runtime_id(item.runtime_id, |handle| {
+                handle.propagate_event(event.clone(), iter::once(&mut item.widget))
+            });
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct ForEachBuilder(ForEachDeclaration);
+
+#[typetag::deserialize(name = "ForEach")]
+impl WidgetBuilder for ForEachBuilder {
+    fn widget_type(
+        &self,
+        _handler: Option<&Ident>,
+        _component: &Ident,
+        child: Option<&TokenStream>,
+        stream: &mut TokenStream,
+    ) {
+        stream.extend(quote!(::gui::gui_widget::for_each::ForEach<#child>))
+    }
+
+    fn name(&self) -> &'static str {
+        "ForEach"
+    }
+
+    fn combine(&mut self, _rhs: &dyn WidgetBuilder) {}
+
+    fn create_widget(&self, id: WidgetID, stream: &mut TokenStream) {
+        stream.extend(quote!(::gui::gui_widget::for_each::ForEach::new(#id)))
+    }
+
+    fn for_each(&self) -> Option<&ForEachDeclaration> {
+        Some(&self.0)
+    }
+
+    fn get_widgets(&mut self) -> Option<Vec<MutWidgetChildren>> {
+        Some(vec![Children::One(&mut *self.0.template)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hash_key, ForEach};
+    use gui_custom::glazier::kurbo::Rect;
+    use gui_custom::widget::{Handle, UpdateHandle, WidgetID};
+
+    #[test]
+    fn keyed_update() {
+        let mut handle = Handle::default();
+        let mut handle = UpdateHandle::new(&mut handle, Default::default());
+        let mut for_each = ForEach::new(WidgetID::new(0));
+        let mut update = |for_each: &mut ForEach<String>, items: &[(u32, &str)]| {
+            let mut updated = vec![];
+            for_each.update_items(
+                items,
+                |_, item| hash_key(&item.0),
+                String::new,
+                |widget, _, item, _| {
+                    *widget = item.1.to_string();
+                    updated.push(item.0);
+                },
+                false,
+                &mut handle,
+            );
+            updated
+        };
+
+        assert_eq!(update(&mut for_each, &[(1, "a"), (2, "b")]), vec![1, 2]);
+        let ids = for_each.runtime_ids().collect::<Vec<_>>();
+        assert_eq!(update(&mut for_each, &[(1, "a"), (2, "c")]), vec![2]);
+        assert_eq!(for_each.runtime_ids().collect::<Vec<_>>(), ids);
+
+        // Removing the first item moves the second item, so its index changes.
+        assert_eq!(update(&mut for_each, &[(2, "c")]), vec![2]);
+        assert_eq!(for_each.runtime_ids().collect::<Vec<_>>(), ids[1..]);
+        assert_eq!(for_each.items[0].widget, "c");
+    }
+
+    #[test]
+    fn removed_items_are_cleaned_up() {
+        let mut handle = Handle::default();
+        let mut for_each = ForEach::new(WidgetID::new(0));
+        let mut update = |handle: &mut Handle, items: &[u32]| {
+            for_each.update_items(
+                items,
+                |_, item| hash_key(item),
+                String::new,
+                |_, _, _, _| {},
+                false,
+                &mut UpdateHandle::new(handle, Default::default()),
+            );
+            for_each.runtime_ids().collect::<Vec<_>>()
+        };
+
+        // Items with duplicate keys are kept, each with their own widget.
+        let ids = update(&mut handle, &[1, 2, 2]);
+        assert_eq!(ids.len(), 3);
+        assert_ne!(ids[1], ids[2]);
+        for id in &ids {
+            let rect = Rect::new(0.0, 0.0, 10.0, 10.0);
+            handle.info.position_widget(*id, WidgetID::new(0), rect);
+            handle.info.add_hover(*id, WidgetID::new(0));
+        }
+
+        // The duplicates keep their widgets, matched by the order of the items with the key.
+        assert_eq!(update(&mut handle, &[2, 1, 2]), [ids[1], ids[0], ids[2]]);
+
+        assert_eq!(update(&mut handle, &[1]), ids[..1]);
+        assert!(handle.info.has_runtime_id(ids[0]));
+        for id in &ids[1..] {
+            assert!(!handle.info.has_runtime_id(*id));
+            assert!(!handle.info.is_hovered(*id, WidgetID::new(0)));
+        }
+    }
+}
@@ -110,7 +110,12 @@ impl<C: ToComponent, W: Widget<C>> Widget<C> for HVStack<W> {
             .unwrap_or_default();
         let mut acc = 0.0;
 
+        // Hidden children are positioned without adding any spacing.
         for (s, id) in layouts.iter().copied() {
+            if handle.is_hidden(id) {
+                handle.position_widget(Rect::from_origin_size(Point::ZERO, s), id);
+                continue;
+            }
             let pos = match self.axis {
                 Axis::Horizontal => {
                     Point::new(acc, (max_length - self.axis.invert().get_axis(s)) / 2.0)
@@ -123,16 +128,15 @@ impl<C: ToComponent, W: Widget<C>> Widget<C> for HVStack<W> {
             handle.position_widget(Rect::from_origin_size(pos, s), id)
         }
 
+        let shown = layouts.iter().filter(|(_, id)| !handle.is_hidden(*id));
         match self.axis {
             Axis::Horizontal => Size::new(
-                Itertools::intersperse(layouts.iter().map(|(s, _)| s.width), self.spacing as f64)
-                    .sum(),
+                Itertools::intersperse(shown.map(|(s, _)| s.width), self.spacing as f64).sum(),
                 max_length,
             ),
             Axis::Vertical => Size::new(
                 max_length,
-                Itertools::intersperse(layouts.iter().map(|(s, _)| s.height), self.spacing as f64)
-                    .sum(),
+                Itertools::intersperse(shown.map(|(s, _)| s.height), self.spacing as f64).sum(),
             ),
         }
     }
@@ -150,7 +154,7 @@ impl<C: ToComponent, W: Widget<C>> Widget<C> for HVStack<W> {
     init_path = "new_horizontal"
 )]
 pub struct HStackBuilder {
-    #[widget(property = "set_spacing", default = 0_10f32)]
+    #[widget(property = "set_spacing", default = ThemeToken("md"), range = 0.0..=1000.0)]
     spacing: Option<Var<f32>>,
     #[widget(children = "widgets")]
     children: Option<Vec<WidgetDeclaration>>,
@@ -164,7 +168,7 @@ pub struct HStackBuilder {
     init_path = "new_vertical"
 )]
 pub struct VStackBuilder {
-    #[widget(property = "set_spacing", default = 0_10f32)]
+    #[widget(property = "set_spacing", default = ThemeToken("md"), range = 0.0..=1000.0)]
     spacing: Option<Var<f32>>,
     #[widget(children = "widgets")]
     children: Option<Vec<WidgetDeclaration>>,
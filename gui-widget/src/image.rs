@@ -80,7 +80,8 @@ pub struct ImageBuilder {
     #[widget(
         static_only = "set_image_from_file",
         var_only = "set_image",
-        static_bound = "P: AsRef<Path>"
+        static_bound = "P: AsRef<Path>",
+        non_empty
     )]
     pub image: Option<Var<String>>,
 }
@@ -1,14 +1,28 @@
 pub use button::Button;
+pub use checkbox::Checkbox;
+pub use clickable::Clickable;
 pub use comp_holder::CompHolder;
+pub use conditional::If;
+pub use file_picker::FilePicker;
+pub use for_each::ForEach;
 pub use hvstack::HVStack;
 pub use image::ImageWidget;
-pub use text::Text;
+pub use select::Select;
+pub use text::{Text, TextAlign};
+pub use visible::Visible;
 
 pub mod button;
+mod checkbox;
+pub mod clickable;
 mod comp_holder;
+mod conditional;
+pub mod file_picker;
+pub mod for_each;
 mod hvstack;
 mod image;
+pub mod select;
 mod text;
+mod visible;
 
 /// Hack to allow widget paths to be asserted as unlike any other widget library
 /// this one gets imported through the gui crate
@@ -24,4 +38,53 @@ mod tests {
         let yaml = include_str!("simple.yaml");
         let _ser: gui_custom::parse::GUIDeclaration = serde_yaml::from_str(yaml).unwrap();
     }
+
+    #[test]
+    fn parse_if_block() {
+        let yaml = r#"
+if:
+  variable: logged_in
+then:
+  widget: Text
+  properties:
+    text: Log out
+else:
+  widget: Text
+  properties:
+    text: Log in"#;
+        let widget: gui_custom::parse::WidgetDeclaration = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(widget.widget.name(), "If");
+        assert_eq!(widget.widget.widgets().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn parse_enum_property() {
+        let parse = |align: &str| {
+            serde_yaml::from_str::<gui_custom::parse::WidgetDeclaration>(&format!(
+                "widget: Text\nproperties:\n  align: {align}"
+            ))
+        };
+        let statics = parse("middle").unwrap().widget.get_statics();
+        let (_, align) = statics.iter().find(|(p, _)| *p == "align").unwrap();
+        assert_eq!(
+            align.to_string(),
+            ":: gui :: gui_widget :: TextAlign :: Middle"
+        );
+
+        let error = parse("centre").unwrap_err().to_string();
+        assert!(error.contains("expected one of `start`, `middle`, `end`, `justified`"));
+    }
+
+    #[test]
+    fn parse_select_options() {
+        let yaml = r#"
+name: Size
+widget: Select
+properties:
+  options: [Small, Large]"#;
+        let widget: gui_custom::parse::WidgetDeclaration = serde_yaml::from_str(yaml).unwrap();
+        let statics = widget.widget.get_statics();
+        let (_, options) = statics.iter().find(|(p, _)| *p == "options").unwrap();
+        assert!(options.to_string().starts_with(":: std :: vec ! ["));
+    }
 }
@@ -0,0 +1,335 @@
+use std::marker::PhantomData;
+
+use serde::Deserialize;
+
+use gui_custom::common::text;
+use gui_custom::common::text::ParleyBrush;
+use gui_custom::glazier::kurbo::Size;
+use gui_custom::glazier::{Cursor, KbKey, PointerButton};
+use gui_custom::layout::LayoutConstraints;
+use gui_custom::parley::layout::{Alignment, Layout};
+use gui_custom::parley::style::StyleProperty;
+use gui_custom::parley::LayoutContext;
+use gui_custom::parse::list::StringList;
+use gui_custom::theme::ThemeToken;
+use gui_custom::vello::kurbo::{Affine, BezPath, Point};
+use gui_custom::vello::peniko::{Brush, Fill, Stroke};
+use gui_custom::widget::{
+    EventHandle, RenderHandle, ResizeHandle, UpdateHandle, Widget, WidgetEvent, WidgetID,
+};
+use gui_custom::WidgetBuilder;
+use gui_custom::{Colour, FontContext, SceneBuilder, ToComponent, ToHandler, Var};
+
+const PADDING: Size = Size::new(8.0, 4.0);
+/// The width of the space for the arrow after the text.
+const ARROW_WIDTH: f64 = 16.0;
+
+pub trait SelectHandler<T: ToHandler<BaseHandler = Self>> {
+    /// Called with the index of the option that was selected from the list or with the arrow
+    /// keys. Not called when the selected index is limited to fewer options.
+    fn on_select(&mut self, _index: usize) {}
+}
+
+/// Shows the selected option, opening a list of all the options when pressed. The arrow keys
+/// change the selected option while the pointer is over the widget. Bind `selected` to a variable
+/// to store the index of the selected option in the component. `selected` is always an index into
+/// the options, so binding it to an enum is not supported.
+pub struct Select<T: ToHandler<BaseHandler = C>, C: ToComponent> {
+    id: WidgetID,
+    options: Vec<String>,
+    selected: usize,
+    /// Set when an option is selected or the index is limited to the options, until taken by
+    /// the component.
+    changed: Option<usize>,
+    disabled: bool,
+    background_colour: Colour,
+    hover_colour: Colour,
+    disabled_colour: Colour,
+    border_colour: Colour,
+    text_colour: Colour,
+    size: f32,
+    /// The layout of each option, which are all built to find the width of the widest one.
+    layouts: Vec<Layout<ParleyBrush>>,
+    phantom: PhantomData<(T, C)>,
+}
+
+impl<T: ToHandler<BaseHandler = C>, C: ToComponent> Select<T, C> {
+    pub fn new(id: WidgetID) -> Self {
+        Select {
+            id,
+            options: vec![],
+            selected: 0,
+            changed: None,
+            disabled: false,
+            background_colour: Default::default(),
+            hover_colour: Default::default(),
+            disabled_colour: Default::default(),
+            border_colour: Default::default(),
+            text_colour: Default::default(),
+            size: Default::default(),
+            layouts: vec![],
+            phantom: PhantomData,
+        }
+    }
+
+    fn build(&mut self, fcx: &mut FontContext) {
+        let mut lcx = LayoutContext::new();
+        self.layouts = self
+            .options
+            .iter()
+            .map(|option| {
+                let mut layout_builder = lcx.ranged_builder(fcx, option, 1.0);
+                layout_builder.push_default(&StyleProperty::FontSize(self.size));
+                layout_builder.push_default(&StyleProperty::Brush(ParleyBrush(Brush::Solid(
+                    self.text_colour.0,
+                ))));
+                let mut layout = layout_builder.build();
+                layout.break_all_lines(None, Alignment::Start);
+                layout
+            })
+            .collect();
+    }
+
+    pub fn set_options<O: Into<Vec<String>>>(&mut self, options: O, handle: &mut UpdateHandle) {
+        let options = options.into();
+        if self.options != options {
+            self.options = options;
+            let selected = self.clamp(self.selected);
+            if self.selected != selected {
+                self.selected = selected;
+                self.changed = Some(selected);
+            }
+            self.layouts.clear();
+            handle.resize();
+        }
+    }
+
+    pub fn set_selected(&mut self, selected: usize, handle: &mut UpdateHandle) {
+        let clamped = self.clamp(selected);
+        if clamped != selected {
+            self.changed = Some(clamped);
+        }
+        if self.selected != clamped {
+            self.selected = clamped;
+            handle.invalidate_id(self.id)
+        }
+    }
+
+    /// Limits `index` to the last option. The index is kept while there are no options, as they
+    /// may be set after it.
+    fn clamp(&self, index: usize) -> usize {
+        self.options
+            .len()
+            .checked_sub(1)
+            .map_or(index, |last| index.min(last))
+    }
+
+    pub fn take_selected(&mut self) -> Option<usize> {
+        self.changed.take()
+    }
+
+    pub fn set_disabled(&mut self, disabled: bool, handle: &mut UpdateHandle) {
+        self.disabled = disabled;
+        handle.invalidate_id(self.id)
+    }
+    pub fn set_background_colour(&mut self, colour: Colour, handle: &mut UpdateHandle) {
+        self.background_colour = colour;
+        handle.invalidate_id(self.id)
+    }
+    pub fn set_hover_colour(&mut self, colour: Colour, handle: &mut UpdateHandle) {
+        self.hover_colour = colour;
+        handle.invalidate_id(self.id)
+    }
+    pub fn set_disabled_colour(&mut self, colour: Colour, handle: &mut UpdateHandle) {
+        self.disabled_colour = colour;
+        handle.invalidate_id(self.id)
+    }
+    pub fn set_border_colour(&mut self, colour: Colour, handle: &mut UpdateHandle) {
+        self.border_colour = colour;
+        handle.invalidate_id(self.id)
+    }
+    pub fn set_text_colour(&mut self, colour: Colour, handle: &mut UpdateHandle) {
+        if self.text_colour != colour {
+            self.text_colour = colour;
+            self.layouts.clear();
+            handle.invalidate_id(self.id);
+        }
+    }
+    pub fn set_size(&mut self, size: f32, handle: &mut UpdateHandle) {
+        if self.size != size {
+            self.size = size;
+            self.layouts.clear();
+            handle.resize();
+        }
+    }
+
+    fn open_list(&self, handle: &mut EventHandle<C>) {
+        if !self.options.is_empty() {
+            let selected = (self.selected < self.options.len()).then_some(self.selected);
+            handle.open_option_list(self.id, self.options.clone(), selected);
+        }
+    }
+}
+
+impl<T: ToHandler<BaseHandler = C>, C: ToComponent + SelectHandler<T>> Select<T, C> {
+    fn select(&mut self, index: usize, handle: &mut EventHandle<C>) {
+        if index != self.selected && index < self.options.len() {
+            self.selected = index;
+            self.changed = Some(index);
+            handle.invalidate_id(self.id);
+            handle.get_handler().on_select(index);
+        }
+    }
+}
+
+impl<T: ToHandler<BaseHandler = C>, C: ToComponent + SelectHandler<T>> Widget<C> for Select<T, C> {
+    fn id(&self) -> WidgetID {
+        self.id
+    }
+
+    fn render(&mut self, scene: &mut SceneBuilder, handle: &mut RenderHandle<C>) {
+        if self.layouts.len() != self.options.len() {
+            self.build(handle.get_fcx());
+        }
+        let rect = handle.get_local_rect(self.id);
+        let fill_colour = if self.disabled {
+            self.disabled_colour
+        } else if handle.is_hovered(self.id) || handle.is_active(self.id) {
+            self.hover_colour
+        } else {
+            self.background_colour
+        };
+        let rounded = rect.to_rounded_rect(4.0);
+        scene.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            &Brush::Solid(fill_colour.0),
+            None,
+            &rounded,
+        );
+        scene.stroke(
+            &Stroke::new(1.0),
+            Affine::IDENTITY,
+            &Brush::Solid(self.border_colour.0),
+            None,
+            &rounded,
+        );
+
+        if let Some(layout) = self.layouts.get(self.selected) {
+            let offset = (rect.height() - layout.height() as f64) / 2.0;
+            text::render_text(scene, Affine::translate((PADDING.width, offset)), layout);
+        }
+
+        let centre = Point::new(rect.x1 - PADDING.width - ARROW_WIDTH / 2.0, rect.center().y);
+        let mut arrow = BezPath::new();
+        arrow.move_to(centre + (-4.0, -2.0));
+        arrow.line_to(centre + (0.0, 2.0));
+        arrow.line_to(centre + (4.0, -2.0));
+        scene.stroke(
+            &Stroke::new(1.5),
+            Affine::IDENTITY,
+            &Brush::Solid(self.text_colour.0),
+            None,
+            &arrow,
+        );
+    }
+
+    fn resize(&mut self, constraints: LayoutConstraints, handle: &mut ResizeHandle<C>) -> Size {
+        if self.layouts.len() != self.options.len() {
+            self.build(handle.get_fcx());
+        }
+        // The widget is as wide as its widest option, so it does not change size when selecting.
+        let text_size =
+            self.layouts
+                .iter()
+                .fold(Size::new(0.0, self.size as f64), |size, layout| {
+                    Size::new(
+                        size.width.max(layout.width() as f64),
+                        size.height.max(layout.height() as f64),
+                    )
+                });
+        let size = Size::new(
+            text_size.width + ARROW_WIDTH + PADDING.width * 3.0,
+            text_size.height + PADDING.height * 2.0,
+        );
+        constraints.max_clamp(size)
+    }
+
+    fn event(&mut self, event: WidgetEvent, handle: &mut EventHandle<C>) {
+        if let WidgetEvent::OptionChosen(index) = event {
+            self.select(index, handle);
+            return;
+        }
+        if self.disabled {
+            return;
+        }
+        let hit = event
+            .get_point()
+            .is_some_and(|pos| handle.get_global_rect(self.id).contains(pos));
+        match event {
+            WidgetEvent::PointerDown(e) if hit && e.button == PointerButton::Primary => {
+                handle.set_active(self.id, true);
+                handle.invalidate_id(self.id);
+            }
+            WidgetEvent::PointerUp(e) if e.button == PointerButton::Primary => {
+                if handle.is_active(self.id) {
+                    handle.set_active(self.id, false);
+                    handle.invalidate_id(self.id);
+                    if hit {
+                        self.open_list(handle);
+                    }
+                }
+            }
+            WidgetEvent::PointerMove(_) if hit => {
+                handle.set_cursor(&Cursor::Pointer);
+                if handle.add_hover(self.id) {
+                    handle.invalidate_id(self.id);
+                }
+            }
+            WidgetEvent::HoverChange(_) => handle.invalidate_id(self.id),
+            // Without keyboard focus, the widget under the pointer takes the arrow keys.
+            WidgetEvent::KeyDown(e) if handle.is_hovered(self.id) => match &e.key {
+                KbKey::ArrowUp if self.selected > 0 => self.select(self.selected - 1, handle),
+                KbKey::ArrowDown => self.select(self.selected + 1, handle),
+                KbKey::Enter => self.open_list(handle),
+                KbKey::Character(c) if c == " " => self.open_list(handle),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+}
+
+#[derive(Deserialize, WidgetBuilder, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+#[widget(
+    name = "Select",
+    type_path = "::gui::gui_widget::Select<#handler, #component>",
+    init_path = "new",
+    handler_trait = "::gui::gui_widget::select::SelectHandler"
+)]
+pub struct SelectBuilder {
+    #[widget(property = "set_options", property_bound = "O: Into<Vec<String>>")]
+    options: Option<Var<StringList>>,
+    #[widget(property = "set_selected", bind = "take_selected")]
+    selected: Option<Var<usize>>,
+    #[widget(property = "set_disabled")]
+    disabled: Option<Var<bool>>,
+    #[widget(property = "set_background_colour")]
+    #[widget(default = ThemeToken("surface"))]
+    background_colour: Option<Var<Colour>>,
+    #[widget(property = "set_hover_colour")]
+    #[widget(default = ThemeToken("surface_hover"))]
+    hover_colour: Option<Var<Colour>>,
+    #[widget(property = "set_disabled_colour")]
+    #[widget(default = ThemeToken("surface_disabled"))]
+    disabled_colour: Option<Var<Colour>>,
+    #[widget(property = "set_border_colour")]
+    #[widget(default = ThemeToken("border"))]
+    border_colour: Option<Var<Colour>>,
+    #[widget(property = "set_text_colour")]
+    #[widget(default = ThemeToken("text"))]
+    text_colour: Option<Var<Colour>>,
+    #[widget(property = "set_size", default = ThemeToken("body"), range = 0.0..=500.0)]
+    size: Option<Var<f32>>,
+}
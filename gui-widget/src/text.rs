@@ -1,27 +1,57 @@
 use std::borrow::Cow;
+use std::ops::Range;
 
 use serde::Deserialize;
 
 use gui_custom::common::text;
 use gui_custom::common::text::ParleyBrush;
 use gui_custom::glazier::kurbo::Size;
+use gui_custom::glazier::{Cursor, KbKey, PointerButton};
 use gui_custom::layout::LayoutConstraints;
 use gui_custom::parley::layout::{Alignment, Layout};
 use gui_custom::parley::style::{FontWeight, StyleProperty};
 use gui_custom::parley::LayoutContext;
-use gui_custom::vello::kurbo::Affine;
-use gui_custom::vello::peniko::{Brush, Color};
+use gui_custom::theme;
+use gui_custom::vello::kurbo::{Affine, Point};
+use gui_custom::vello::peniko::{Brush, Fill};
 use gui_custom::widget::{
     EventHandle, RenderHandle, ResizeHandle, UpdateHandle, Widget, WidgetEvent, WidgetID,
 };
 use gui_custom::WidgetBuilder;
 use gui_custom::{Colour, Fluent, FontContext, SceneBuilder, ToComponent, Var};
 
+/// How the lines of a [`Text`] are aligned.
+#[derive(Deserialize, WidgetBuilder, Debug, Clone, Copy, Eq, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+#[widget(type_path = "::gui::gui_widget::TextAlign")]
+pub enum TextAlign {
+    #[default]
+    Start,
+    Middle,
+    End,
+    Justified,
+}
+
+impl From<TextAlign> for Alignment {
+    fn from(value: TextAlign) -> Self {
+        match value {
+            TextAlign::Start => Alignment::Start,
+            TextAlign::Middle => Alignment::Middle,
+            TextAlign::End => Alignment::End,
+            TextAlign::Justified => Alignment::Justified,
+        }
+    }
+}
+
 pub struct Text {
     id: WidgetID,
     text: String,
     colour: Colour,
     size: f32,
+    align: TextAlign,
+    selectable: bool,
+    /// The byte offsets where the selection started and where it is dragged to.
+    selection: Option<(usize, usize)>,
     // Use box to reduce struct size
     layout: Option<Box<Layout<ParleyBrush>>>,
 }
@@ -33,6 +63,9 @@ impl Text {
             text: Default::default(),
             colour: Default::default(),
             size: Default::default(),
+            align: Default::default(),
+            selectable: false,
+            selection: None,
             layout: None,
         }
     }
@@ -51,6 +84,7 @@ impl Text {
     pub fn set_text(&mut self, text: Cow<'_, str>, handle: &mut UpdateHandle) {
         if self.text != text {
             self.text = text.into_owned();
+            self.selection = None;
             self.layout = None;
             handle.resize();
         }
@@ -71,6 +105,32 @@ impl Text {
             handle.resize();
         }
     }
+
+    pub fn set_align(&mut self, align: TextAlign, handle: &mut UpdateHandle) {
+        if self.align != align {
+            self.align = align;
+            handle.resize();
+        }
+    }
+
+    pub fn set_selectable(&mut self, selectable: bool, handle: &mut UpdateHandle) {
+        self.selectable = selectable;
+        if !selectable && self.selection.take().is_some() {
+            handle.invalidate_id(self.id);
+        }
+    }
+
+    fn selected_range(&self) -> Option<Range<usize>> {
+        let (anchor, focus) = self.selection?;
+        (anchor != focus).then(|| anchor.min(focus)..anchor.max(focus))
+    }
+
+    /// Offset in the text closest to the `point` relative to the widget.
+    fn hit_test(&self, point: Point) -> usize {
+        self.layout
+            .as_ref()
+            .map_or(0, |layout| text::hit_test(layout, point))
+    }
 }
 
 impl<C: ToComponent> Widget<C> for Text {
@@ -86,6 +146,25 @@ impl<C: ToComponent> Widget<C> for Text {
             self.build(handle.get_fcx());
         }
 
+        // Another widget has started a selection since this one was selected.
+        if self.selection.is_some() && !handle.has_text_selection(self.id) {
+            self.selection = None;
+        }
+        if let Some(range) = self.selected_range() {
+            let colour = theme::get_or_default::<Colour>("primary")
+                .0
+                .with_alpha_factor(0.3);
+            for rect in text::selection_rects(self.layout.as_ref().unwrap(), range) {
+                scene.fill(
+                    Fill::NonZero,
+                    Affine::IDENTITY,
+                    &Brush::Solid(colour),
+                    None,
+                    &rect,
+                );
+            }
+        }
+
         let layout = self.layout.as_mut().unwrap();
         text::render_text(scene, Affine::IDENTITY, layout);
     }
@@ -98,11 +177,55 @@ impl<C: ToComponent> Widget<C> for Text {
             self.build(handle.get_fcx());
         }
         let layout = self.layout.as_mut().unwrap();
-        layout.break_all_lines(constraints.max_advance(), Alignment::Start);
+        layout.break_all_lines(constraints.max_advance(), self.align.into());
         Size::new(layout.width() as f64, layout.height() as f64)
     }
 
-    fn event(&mut self, _event: WidgetEvent, _handle: &mut EventHandle<C>) {}
+    fn event(&mut self, event: WidgetEvent, handle: &mut EventHandle<C>) {
+        if !self.selectable {
+            return;
+        }
+        let rect = handle.get_global_rect(self.id);
+        match event {
+            WidgetEvent::PointerDown(e) if e.button == PointerButton::Primary => {
+                if rect.contains(e.pos) {
+                    let offset = self.hit_test(e.pos - rect.origin().to_vec2());
+                    self.selection = Some((offset, offset));
+                    handle.select_text(self.id);
+                    handle.set_active(self.id, true);
+                    handle.invalidate_id(self.id);
+                } else if self.selection.take().is_some() {
+                    handle.invalidate_id(self.id);
+                }
+            }
+            WidgetEvent::PointerMove(e) => {
+                if rect.contains(e.pos) {
+                    handle.set_cursor(&Cursor::IBeam);
+                }
+                if handle.is_active(self.id) {
+                    let offset = self.hit_test(e.pos - rect.origin().to_vec2());
+                    if let Some((_, focus)) = &mut self.selection {
+                        *focus = offset;
+                        handle.invalidate_id(self.id);
+                    }
+                }
+            }
+            WidgetEvent::PointerUp(e) if e.button == PointerButton::Primary => {
+                handle.set_active(self.id, false);
+            }
+            WidgetEvent::KeyDown(e) => {
+                let copy = matches!(&e.key, KbKey::Character(c) if c == "c")
+                    && (e.mods.ctrl() || e.mods.meta());
+                if !handle.has_text_selection(self.id) {
+                    self.selection = None;
+                }
+                if let Some(range) = self.selected_range().filter(|_| copy) {
+                    handle.set_clipboard(&self.text[range]);
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
 #[derive(Deserialize, WidgetBuilder, Debug, Clone, Default)]
@@ -116,8 +239,12 @@ pub struct TextBuilder {
     #[widget(fluent = "set_text")]
     pub text: Option<Fluent>,
     #[widget(property = "set_colour")]
-    #[widget(default = Colour(Color::rgb8(33, 37, 41)))]
+    #[widget(default = ThemeToken("text"))]
     pub colour: Option<Var<Colour>>,
-    #[widget(property = "set_size", default = 14.0f32)]
+    #[widget(property = "set_size", default = ThemeToken("body"), range = 0.0..=500.0)]
     pub size: Option<Var<f32>>,
+    #[widget(property = "set_align", default = TextAlign::Start)]
+    pub align: Option<Var<TextAlign>>,
+    #[widget(property = "set_selectable")]
+    pub selectable: Option<Var<bool>>,
 }
@@ -0,0 +1,72 @@
+use std::ops::{Deref, DerefMut};
+
+use gui_custom::layout::LayoutConstraints;
+use gui_custom::widget::{
+    EventHandle, RenderHandle, ResizeHandle, UpdateHandle, Widget, WidgetEvent, WidgetID,
+};
+use gui_custom::{SceneBuilder, Size, ToComponent};
+
+/// Wraps widgets that use the `visible` layout property. A hidden widget keeps its [`WidgetID`]
+/// and state, but it is not laid out, rendered or sent any events.
+pub struct Visible<W> {
+    visible: bool,
+    widget: W,
+}
+
+impl<W> Visible<W> {
+    pub fn new(widget: W) -> Self {
+        Self {
+            visible: true,
+            widget,
+        }
+    }
+
+    pub fn set_visible(&mut self, visible: bool, handle: &mut UpdateHandle) {
+        if self.visible != visible {
+            self.visible = visible;
+            handle.resize();
+        }
+    }
+}
+
+/// Allows the properties of the wrapped widget to be set as if it was not wrapped.
+impl<W> Deref for Visible<W> {
+    type Target = W;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl<W> DerefMut for Visible<W> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
+
+impl<C: ToComponent, W: Widget<C>> Widget<C> for Visible<W> {
+    fn id(&self) -> WidgetID {
+        self.widget.id()
+    }
+
+    fn render(&mut self, scene: &mut SceneBuilder, handle: &mut RenderHandle<C>) {
+        if self.visible {
+            self.widget.render(scene, handle);
+        }
+    }
+
+    fn resize(&mut self, constraints: LayoutConstraints, handle: &mut ResizeHandle<C>) -> Size {
+        if self.visible {
+            self.widget.resize(constraints, handle)
+        } else {
+            handle.hide_widget(self.widget.id());
+            Size::ZERO
+        }
+    }
+
+    fn event(&mut self, event: WidgetEvent, handle: &mut EventHandle<C>) {
+        if self.visible {
+            self.widget.event(event, handle);
+        }
+    }
+}
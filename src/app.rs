@@ -0,0 +1,72 @@
+use crate::{run_with, Error, QuitPolicy};
+use gui_core::window::WindowConfig;
+use gui_core::{Size, ToComponent};
+
+/// Builds the window of an application. Settings that are set here are used instead of the ones
+/// in the `window` section of the component's layout.
+///
+/// ```ignore
+/// gui::App::new()
+///     .title("Apple Counter")
+///     .size((800.0, 400.0))
+///     .run(AppleCounter::default())
+///     .expect("failed to start the application");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct App {
+    pub(crate) config: WindowConfig,
+    pub(crate) quit_policy: QuitPolicy,
+}
+
+impl App {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.config.title = Some(title.into());
+        self
+    }
+
+    /// The initial size of the window.
+    pub fn size<S: Into<Size>>(mut self, size: S) -> Self {
+        self.config.size = Some(size.into());
+        self
+    }
+
+    pub fn min_size<S: Into<Size>>(mut self, size: S) -> Self {
+        self.config.min_size = Some(size.into());
+        self
+    }
+
+    pub fn max_size<S: Into<Size>>(mut self, size: S) -> Self {
+        self.config.max_size = Some(size.into());
+        self
+    }
+
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.config.resizable = Some(resizable);
+        self
+    }
+
+    /// Whether the background of the window is transparent where nothing is drawn.
+    pub fn transparent(mut self, transparent: bool) -> Self {
+        self.config.transparent = Some(transparent);
+        self
+    }
+
+    /// When the application quits if it has more than one window, which is when the last window
+    /// is closed by default.
+    pub fn quit_policy(mut self, quit_policy: QuitPolicy) -> Self {
+        self.quit_policy = quit_policy;
+        self
+    }
+
+    /// Shows `component` in a window with these settings, see [`run_with`].
+    pub fn run<T: ToComponent>(self, component: T) -> Result<(), Error>
+    where
+        <T as ToComponent>::Component: 'static,
+    {
+        run_with(component, self)
+    }
+}
@@ -0,0 +1,27 @@
+use std::fmt::{Display, Formatter};
+
+/// An error when starting the application or opening a window.
+#[derive(Debug)]
+pub enum Error {
+    /// The application or the window could not be created by the platform.
+    Platform(gui_core::glazier::Error),
+    /// The renderer of the window could not be created.
+    Renderer(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Platform(error) => write!(f, "{error}"),
+            Error::Renderer(error) => write!(f, "failed to create the renderer: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<gui_core::glazier::Error> for Error {
+    fn from(error: gui_core::glazier::Error) -> Self {
+        Error::Platform(error)
+    }
+}
@@ -0,0 +1,213 @@
+//! Runtime support for the `hot-reload` feature. Layout files are polled for changes, and when
+//! changed they are parsed again so that generated components can look up the new values of their
+//! statics and fluent text.
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use gui_build::hot_reload::ReloadedComponent;
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use unic_langid::langid;
+
+/// How often the layout files are checked for changes.
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+type StaticKey = (u32, Option<String>, String);
+
+struct ComponentEntry {
+    structure: String,
+    statics: HashMap<StaticKey, Box<dyn Any>>,
+    bundle: FluentBundle<FluentResource>,
+    /// Whether the compiled component has the same structure as the reloaded component.
+    valid: bool,
+    reported: bool,
+}
+
+impl ComponentEntry {
+    fn new(component: ReloadedComponent) -> Result<Self, String> {
+        let mut bundle = FluentBundle::new_concurrent(vec![langid!("en-GB")]);
+        let resource = FluentResource::try_new(component.ftl)
+            .map_err(|(_, e)| format!("Invalid fluent text: {e:?}"))?;
+        bundle
+            .add_resource(resource)
+            .map_err(|e| format!("Invalid fluent text: {e:?}"))?;
+        Ok(Self {
+            structure: component.structure,
+            statics: component
+                .statics
+                .into_iter()
+                .map(|s| ((s.widget_id, s.state, s.property.to_string()), s.value))
+                .collect(),
+            bundle,
+            valid: false,
+            reported: false,
+        })
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    /// Incremented every time a layout file is reloaded.
+    generation: u64,
+    /// The layout files being watched and when they were last modified.
+    files: HashMap<PathBuf, Option<SystemTime>>,
+    components: HashMap<String, ComponentEntry>,
+}
+
+thread_local! {
+    static REGISTRY: RefCell<Registry> = RefCell::default();
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Returns the first line that differs between the compiled and reloaded structure.
+fn structure_difference<'a>(compiled: &'a str, reloaded: &'a str) -> &'a str {
+    let mut compiled_lines = compiled.lines();
+    let mut reloaded_lines = reloaded.lines();
+    loop {
+        match (compiled_lines.next(), reloaded_lines.next()) {
+            (Some(c), Some(r)) if c == r => continue,
+            (_, Some(r)) => return r,
+            (Some(c), None) => return c,
+            (None, None) => return "",
+        }
+    }
+}
+
+/// Used by generated code to watch the layout file at `path`. Returns true if the layout file
+/// has been reloaded since the last call and the statics of the component should be reapplied.
+#[doc(hidden)]
+pub fn needs_update(path: &str, component: &str, structure: &str, generation: &mut u64) -> bool {
+    REGISTRY.with_borrow_mut(|registry| {
+        let path = PathBuf::from(path);
+        registry
+            .files
+            .entry(path.clone())
+            .or_insert_with(|| modified(&path));
+
+        if *generation == registry.generation {
+            return false;
+        }
+        *generation = registry.generation;
+
+        let Some(entry) = registry.components.get_mut(component) else {
+            eprintln!(
+                "Could not hot reload component {component} as it was removed from {}. Restart the application to apply this change.",
+                path.display()
+            );
+            return false;
+        };
+
+        entry.valid = entry.structure == structure;
+        if !entry.valid && !entry.reported {
+            entry.reported = true;
+            eprintln!(
+                "Could not hot reload component {component} as its structure has changed (`{}`). Restart the application to apply this change.",
+                structure_difference(structure, &entry.structure)
+            );
+        }
+        entry.valid
+    })
+}
+
+/// Used by generated code to get the hot reloaded value of a static, returning `default` if
+/// the static has not been reloaded.
+#[doc(hidden)]
+pub fn static_value<T: Clone + 'static>(
+    component: &str,
+    widget_id: u32,
+    state: Option<&str>,
+    property: &str,
+    default: T,
+) -> T {
+    REGISTRY.with_borrow(|registry| {
+        registry
+            .components
+            .get(component)
+            .filter(|c| c.valid)
+            .and_then(|c| {
+                c.statics
+                    .get(&(widget_id, state.map(String::from), property.to_string()))
+            })
+            .and_then(|v| v.downcast_ref::<T>())
+            .cloned()
+            .unwrap_or(default)
+    })
+}
+
+/// Used by generated code to get the hot reloaded fluent text of a message.
+#[doc(hidden)]
+pub fn get_message(component: &str, message: &str, args: Option<&FluentArgs>) -> Option<String> {
+    REGISTRY.with_borrow(|registry| {
+        let entry = registry.components.get(component).filter(|c| c.valid)?;
+        let pattern = entry.bundle.get_message(message)?.value()?;
+        let mut errors = vec![];
+        Some(
+            entry
+                .bundle
+                .format_pattern(pattern, args, &mut errors)
+                .into_owned(),
+        )
+    })
+}
+
+/// Reloads any layout file that has changed. Returns true if a layout file was reloaded.
+pub(crate) fn poll() -> bool {
+    let changed_files: Vec<PathBuf> = REGISTRY.with_borrow_mut(|registry| {
+        registry
+            .files
+            .iter_mut()
+            .filter_map(|(path, last_modified)| {
+                let new_modified = modified(path);
+                (new_modified != *last_modified).then(|| {
+                    *last_modified = new_modified;
+                    path.clone()
+                })
+            })
+            .collect()
+    });
+
+    let mut reloaded = false;
+    for path in changed_files {
+        let components = match gui_build::hot_reload::reload(&path) {
+            Ok(components) => components,
+            Err(e) => {
+                eprintln!("Could not hot reload {}: {e:#}", path.display());
+                continue;
+            }
+        };
+        let entries = components
+            .into_iter()
+            .map(|c| Ok((c.name.clone(), ComponentEntry::new(c)?)))
+            .collect::<Result<HashMap<_, _>, String>>();
+
+        match entries {
+            Ok(entries) => REGISTRY.with_borrow_mut(|registry| {
+                registry.components.extend(entries);
+                registry.generation += 1;
+                reloaded = true;
+            }),
+            Err(e) => eprintln!("Could not hot reload {}: {e}", path.display()),
+        }
+    }
+    reloaded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::structure_difference;
+
+    #[test]
+    fn find_structure_difference() {
+        assert_eq!(structure_difference("a\nb\nc", "a\nd\nc"), "d");
+        assert_eq!(structure_difference("a\nb", "a\nb\nc"), "c");
+        assert_eq!(structure_difference("a\nb\nc", "a\nb"), "c");
+        assert_eq!(structure_difference("a", "a"), "");
+    }
+}
@@ -1,17 +1,31 @@
+mod app;
+mod error;
+#[cfg(feature = "hot-reload")]
+#[doc(hidden)]
+pub mod hot_reload;
+mod list_popup;
+mod menu;
+mod overlay;
+mod signal;
 mod testing;
+pub mod theme;
+mod tooltip;
 mod update;
+pub mod windows;
 
-use gui_core::glazier::kurbo::{Affine, Rect, Size};
+use gui_core::glazier::kurbo::{Affine, Point, Rect, Size};
 use gui_core::glazier::{
-    Application, Cursor, FileDialogToken, FileInfo, IdleToken, KeyEvent, PointerEvent, Region,
-    Scalable, TimerToken, WinHandler, WindowBuilder, WindowHandle,
+    Application, Cursor, FileDialogToken, FileInfo, IdleToken, KbKey, KeyEvent, PointerEvent,
+    Region, Scalable, TimerToken, WinHandler, WindowHandle,
 };
-use gui_core::vello::peniko::Color;
+use gui_core::vello::peniko::{Brush, Mix, Stroke};
 use gui_core::vello::util::{RenderContext, RenderSurface};
 use gui_core::vello::{RenderParams, Renderer, RendererOptions, Scene, SceneFragment};
 pub use gui_core::CompHolder;
-use gui_core::{Component, SceneBuilder, ToComponent};
+use gui_core::{Colour, Component, SceneBuilder, ToComponent};
 use std::any::Any;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use tracing_subscriber::EnvFilter;
 
 pub use fluent_bundle::concurrent::FluentBundle;
@@ -25,41 +39,84 @@ pub use gui_derive::{type_registry, ToComponent};
 
 pub use gui_widget;
 
+pub use gui_core::drag::DragPayload;
+use gui_core::drag::FileDialogResult;
 pub use gui_core::glazier::PointerButton;
-
-use gui_core::widget::{Handle, RuntimeID, WidgetEvent, WidgetID};
-pub use gui_core::Update;
+use gui_core::menu::MenuItem;
+pub use gui_core::widget::PointerInfo;
+use list_popup::ListPopup;
+use menu::Popup;
+use overlay::Overlay;
+
+pub use app::App;
+pub use error::Error;
+use gui_core::widget::{Clipboard, Handle, RuntimeID, WidgetEvent, WidgetID};
+use gui_core::window::WindowConfig;
+pub use gui_core::{MenuHandler, ShortcutHandler, Update};
+pub use overlay::{show_dialog, show_overlay, Dialog};
+pub use signal::{Computed, Signal};
 pub use testing::TestHarness;
+use tooltip::{Tooltip, Tooltips};
 pub use update::Updateable;
 use wgpu::Maintain;
+pub use windows::{
+    close_window, main_window, open_window, window_sender, QuitPolicy, WindowId, WindowSender,
+};
 
 /// Default width of the window
 const WIDTH: usize = 1024;
 /// Default height of the window
 const HEIGHT: usize = 768;
-
-/// Entry point of the framework. Use this to create a window with the specified component.
+/// Longest time between two presses for them to be a double click.
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(500);
+/// How long the primary button needs to be held down for a long press.
+const LONG_PRESS_TIME: Duration = Duration::from_millis(500);
+/// How far the pointer can move while still counting as a double click or long press, before it
+/// starts a drag.
+const CLICK_DISTANCE: f64 = 4.0;
+
+/// Entry point of the framework. Use this to create a window with the specified component,
+/// configured by the `window` section of its layout.
+///
+/// With the `hot-reload` feature enabled, the layout file will be watched for changes which
+/// will then be applied to the window without needing to restart the application.
+///
+/// # Panics
+///
+/// If the application or its window could not be created, use [`run_with`] to handle the error.
 pub fn run<T: ToComponent>(component: T)
 where
     <T as ToComponent>::Component: 'static,
 {
-    tracing_subscriber::fmt()
+    run_with(component, App::new()).expect("failed to start the application")
+}
+
+/// Creates a window with the specified component like [`run`], using the settings of `app` over
+/// the `window` section of the component's layout.
+pub fn run_with<T: ToComponent>(component: T, app: App) -> Result<(), Error>
+where
+    <T as ToComponent>::Component: 'static,
+{
+    // Ignore the error if the application already set up its own subscriber.
+    let _ = tracing_subscriber::fmt()
         .with_env_filter(EnvFilter::from_default_env())
-        .init();
-    let app = Application::new().unwrap();
-    let window = WindowBuilder::new(app.clone())
-        .size((WIDTH as f64, HEIGHT as f64).into())
-        .handler(Box::new(WindowState::new(
-            component.to_component_holder(RuntimeID::next()),
-        )))
-        .build()
-        .unwrap();
+        .try_init();
+    let component = component.to_component_holder(RuntimeID::next());
+    let config = app.config.or(component.window_config());
+    let application = Application::new()?;
+    let state = WindowState::new(component, config.clone())?;
+    windows::set_main(state.id, app.quit_policy);
+    let window = windows::window_builder(application.clone(), &config)
+        .handler(Box::new(state))
+        .build()?;
     window.show();
-    app.run(None);
+    application.run(None);
+    Ok(())
 }
 
 /// Holds the structs needed to render a component to a window.
 struct WindowState<C: Component + 'static> {
+    id: WindowId,
     /// If this is set to the default value then a window has not been initialised,
     /// and we are most likely rendering the window through a test.
     handle: Handle,
@@ -69,12 +126,39 @@ struct WindowState<C: Component + 'static> {
     scene: Scene,
     size: Size,
     component: C,
+    /// When and where the last primary press that was not part of a double click happened.
+    last_press: Option<(Instant, Point)>,
+    /// The timer and the event of the primary press that is held down, which becomes a long press
+    /// if the pointer stays still or a drag if it moves.
+    press: Option<(TimerToken, PointerEvent)>,
+    /// Set when a press was taken by a popup, so that its release is not sent to the widgets
+    /// under the popup.
+    popup_press: bool,
+    /// Components drawn above the component of the window, with the topmost last.
+    overlays: Vec<Overlay>,
+    /// The context menu drawn over the component when there is no window to show it natively.
+    popup: Option<Popup>,
+    /// The list of options opened by a widget, drawn above the overlays.
+    option_list: Option<ListPopup>,
+    tooltips: Tooltips,
+    /// The component and handler of each native menu item, indexed by the item's command id.
+    menu_commands: Vec<(RuntimeID, u32)>,
+    /// The number of commands used by the menu bar, which are followed by the commands of the
+    /// last context menu.
+    menu_bar_commands: usize,
+    /// Used to keep the window within its maximum size, which is not supported by every platform.
+    window_config: WindowConfig,
+    #[cfg(feature = "hot-reload")]
+    hot_reload_timer: TimerToken,
 }
 
 impl<C: Component> WindowState<C> {
-    pub fn new(component: C) -> Self {
-        let render = RenderContext::new().unwrap();
-        Self {
+    pub fn new(mut component: C, window_config: WindowConfig) -> Result<Self, Error> {
+        let render = RenderContext::new().map_err(|e| Error::Renderer(e.to_string()))?;
+        let id = windows::next_id();
+        windows::register(id, (*component.get_comp_struct()).type_id());
+        Ok(Self {
+            id,
             handle: Default::default(),
             surface: None,
             renderer: None,
@@ -82,12 +166,27 @@ impl<C: Component> WindowState<C> {
             scene: Default::default(),
             component,
             size: Size::new(WIDTH as f64, HEIGHT as f64),
-        }
+            last_press: None,
+            press: None,
+            popup_press: false,
+            overlays: vec![],
+            popup: None,
+            option_list: None,
+            tooltips: Tooltips::default(),
+            menu_commands: vec![],
+            menu_bar_commands: 0,
+            window_config,
+            #[cfg(feature = "hot-reload")]
+            hot_reload_timer: TimerToken::INVALID,
+        })
     }
 
     fn resize(&mut self) {
         let max_size = self.dp_surface_size();
         self.handle.info.reset_positions();
+        // Drop targets and hidden widgets add themselves again when they are resized.
+        self.handle.info.clear_drop_targets();
+        self.handle.info.clear_hidden();
         let size = self
             .component
             .resize(LayoutConstraints::new_max(max_size), &mut self.handle);
@@ -96,6 +195,9 @@ impl<C: Component> WindowState<C> {
             Rect::from_center_size((max_size / 2.0).to_vec2().to_point(), size),
             &self.component,
         );
+        for overlay in &mut self.overlays {
+            overlay.resize(max_size);
+        }
     }
 
     fn dp_surface_size(&self) -> Size {
@@ -150,22 +252,67 @@ impl<C: Component> WindowState<C> {
                 timestamp_period: queue.get_timestamp_period(),
             };
             let render_params = RenderParams {
-                base_color: Color::WHITE,
+                base_color: theme::get_or_default::<Colour>("background").0,
                 width,
                 height,
             };
 
+            let size = self.dp_surface_size();
             let mut sb = SceneBuilder::for_scene(&mut self.scene);
             let mut fragment = SceneFragment::new();
             let mut component = SceneBuilder::for_fragment(&mut fragment);
             self.component.render(&mut component, &mut self.handle);
+            let component_origin = self.handle.info.get_parent_rect().origin().to_vec2();
             sb.append(
                 &fragment,
                 Some(
-                    Affine::translate(self.handle.info.get_parent_rect().origin().to_vec2())
+                    Affine::translate(component_origin)
                         .then_scale_non_uniform(scale.x(), scale.y()),
                 ),
             );
+            overlay::render(
+                &mut self.overlays,
+                &mut sb,
+                size,
+                Affine::scale_non_uniform(scale.x(), scale.y()),
+            );
+            // The drag preview is a translucent copy of the dragged widget, drawn over the
+            // component by drawing the component again clipped to the preview.
+            if let Some(drag) = &self.handle.info.drag {
+                let transform = Affine::scale_non_uniform(scale.x(), scale.y());
+                let source = self.handle.info.get_rect(drag.source.0, drag.source.1);
+                let preview = drag.preview_rect();
+                sb.push_layer(Mix::Normal, 0.7, transform, &preview);
+                sb.append(
+                    &fragment,
+                    Some(
+                        transform
+                            * Affine::translate(
+                                component_origin + (preview.origin() - source.origin()),
+                            ),
+                    ),
+                );
+                sb.pop_layer();
+                let colour = theme::get_or_default::<Colour>("primary")
+                    .0
+                    .with_alpha_factor(0.3);
+                sb.stroke(
+                    &Stroke::new(2.0),
+                    transform,
+                    &Brush::Solid(colour),
+                    None,
+                    &preview.to_rounded_rect(4.0),
+                );
+            }
+            if let Some(popup) = &self.popup {
+                popup.render(&mut sb, Affine::scale_non_uniform(scale.x(), scale.y()));
+            }
+            if let Some(list) = &self.option_list {
+                list.render(&mut sb, Affine::scale_non_uniform(scale.x(), scale.y()));
+            }
+            if let Some(tooltip) = &self.tooltips.shown {
+                tooltip.render(&mut sb, Affine::scale_non_uniform(scale.x(), scale.y()));
+            }
 
             self.renderer
                 .get_or_insert_with(|| Renderer::new(device, &renderer_options).unwrap())
@@ -189,17 +336,316 @@ impl<C: Component> WindowState<C> {
     fn propagate_component_event(&mut self, event: WidgetEvent) -> bool {
         self.component.propagate_event(event, &mut self.handle)
     }
+
+    /// Sends the event to the active widget, or to all widgets if none are active.
+    fn send_pointer_event(&mut self, event: WidgetEvent) -> bool {
+        if let Some(id) = self.handle.info.get_active_widget() {
+            self.send_component_event(id.0, id.1, event)
+        } else {
+            self.propagate_component_event(event)
+        }
+    }
+
+    /// Checks if a primary press completes a double click.
+    fn is_double_click(&mut self, event: &PointerEvent) -> bool {
+        let now = Instant::now();
+        let double_click = self.last_press.is_some_and(|(time, pos)| {
+            now - time <= DOUBLE_CLICK_TIME && pos.distance(event.pos) <= CLICK_DISTANCE
+        });
+        // A third press starts a new double click.
+        self.last_press = (!double_click).then_some((now, event.pos));
+        double_click
+    }
+
+    /// Moves the drag in progress to `pos`, sending drag events to the drop targets it moves over.
+    fn drag_over(&mut self, pos: Point) -> bool {
+        let target = self.handle.info.get_drop_target(pos);
+        let Some(drag) = self.handle.info.drag.as_mut() else {
+            return false;
+        };
+        drag.pos = pos;
+        let previous = std::mem::replace(&mut drag.target, target);
+        let event = drag.event();
+        // The preview follows the pointer.
+        self.handle.if_window(|w| w.invalidate());
+
+        let mut resize = false;
+        if previous != target {
+            if let Some(id) = previous {
+                resize |= self.send_component_event(id.0, id.1, WidgetEvent::DragLeave(&event));
+            }
+            if let Some(id) = target {
+                resize |= self.send_component_event(id.0, id.1, WidgetEvent::DragEnter(&event));
+            }
+        }
+        if let Some(id) = target {
+            resize |= self.send_component_event(id.0, id.1, WidgetEvent::DragOver(&event));
+        }
+        resize
+    }
+
+    /// Ends the drag in progress, dropping it on the target under the pointer.
+    fn drop(&mut self) -> bool {
+        let Some(drag) = self.handle.info.drag.take() else {
+            return false;
+        };
+        self.handle.if_window(|w| w.invalidate());
+        drag.target.is_some_and(|id| {
+            self.send_component_event(id.0, id.1, WidgetEvent::Drop(&drag.event()))
+        })
+    }
+
+    /// Sends the files chosen in a dialog to the widget that opened it.
+    fn file_dialog_result(&mut self, token: FileDialogToken, paths: Vec<PathBuf>) {
+        let Some(id) = self.handle.info.take_file_dialog(token) else {
+            return;
+        };
+        let result = FileDialogResult { token, paths };
+        let event_resize = self.send_component_event(id.0, id.1, WidgetEvent::FileDialog(&result));
+        let var_resize = self.component.update_vars(false, &mut self.handle);
+        if event_resize || var_resize {
+            self.resize();
+        }
+    }
+
+    /// Adds the overlays shown by handlers and removes the ones that were closed, and opens the
+    /// list of options requested by a widget. Called after every event that can run a handler.
+    fn after_event(&mut self) {
+        let request = self.handle.info.option_list.take().or_else(|| {
+            self.overlays
+                .iter_mut()
+                .find_map(|o| o.handle.info.option_list.take())
+        });
+        if let Some(list) = request {
+            let anchor = self.widget_rect(list.owner.0, list.owner.1);
+            let bounds = self.dp_surface_size();
+            self.option_list = Some(ListPopup::new(list, anchor, &mut self.handle.fcx, bounds));
+            self.handle.if_window(|w| w.invalidate());
+        }
+        let mut resize = false;
+        for mut overlay in overlay::take_pending() {
+            overlay.handle.window = self.handle.window.clone();
+            overlay.handle.clipboard = self.handle.clipboard.clone();
+            overlay.handle.file_dialogs = self.handle.file_dialogs;
+            overlay.update_vars(true);
+            self.overlays.push(overlay);
+            resize = true;
+        }
+        let root = &mut self.component;
+        let overlay_count = self.overlays.len();
+        self.overlays.retain_mut(|overlay| !overlay.close(root));
+        if self.overlays.len() != overlay_count {
+            self.component.update_vars(false, &mut self.handle);
+            resize = true;
+        }
+        if resize {
+            self.resize();
+            self.handle.if_window(|w| w.invalidate());
+        }
+        windows::update_others(self.id);
+    }
+
+    /// The topmost overlay receiving pointer events at `pos`.
+    fn overlay_at(&mut self, pos: Point) -> Option<&mut Overlay> {
+        self.overlays.iter_mut().rev().find(|o| o.captures(pos))
+    }
+
+    /// Sends a pointer event to the overlay at its position. Returns false if there is no overlay
+    /// there, so the event should be sent to the component instead.
+    fn overlay_pointer_event(&mut self, pos: Point, event: WidgetEvent) -> bool {
+        let Some(overlay) = self.overlay_at(pos) else {
+            return false;
+        };
+        let event_resize = match event {
+            WidgetEvent::PointerMove(event) => overlay.pointer_move(event),
+            event => overlay.pointer_event(event),
+        };
+        if overlay.update_vars(false) || event_resize {
+            self.resize();
+        }
+        true
+    }
+
+    /// The rect of a widget of the component or one of the overlays.
+    fn widget_rect(&self, runtime_id: RuntimeID, widget_id: WidgetID) -> Rect {
+        self.overlays
+            .iter()
+            .find_map(|o| o.widget_rect(runtime_id, widget_id))
+            .unwrap_or_else(|| self.handle.info.get_rect(runtime_id, widget_id))
+    }
+
+    /// Tracks the innermost widget with a tooltip at `pos`, which is under the pointer.
+    fn hover_tooltip(&mut self, pos: Option<Point>) {
+        let widget = pos.and_then(|pos| {
+            let (component, info): (&dyn Component, _) =
+                match self.overlays.iter().rev().find(|o| o.captures(pos)) {
+                    Some(overlay) => (&*overlay.component, &overlay.handle.info),
+                    None => (&self.component, &self.handle.info),
+                };
+            info.widgets_at(pos)
+                .into_iter()
+                .find(|&(runtime_id, widget_id)| component.tooltip(runtime_id, widget_id).is_some())
+        });
+        if self.tooltips.hover(widget, &mut self.handle) {
+            self.handle.if_window(|w| w.invalidate());
+        }
+    }
+
+    fn hide_tooltip(&mut self) {
+        if self.tooltips.hide() {
+            self.handle.if_window(|w| w.invalidate());
+        }
+    }
+
+    /// Shows the tooltip of the widget the pointer has rested over.
+    fn show_tooltip(&mut self) {
+        let Some((runtime_id, widget_id)) = self.tooltips.take_waiting() else {
+            return;
+        };
+        let text = match self
+            .overlays
+            .iter()
+            .find(|o| o.handle.info.has_runtime_id(runtime_id))
+        {
+            Some(overlay) => overlay.component.tooltip(runtime_id, widget_id),
+            None => self.component.tooltip(runtime_id, widget_id),
+        };
+        let Some(text) = text else {
+            return;
+        };
+        let anchor = self.widget_rect(runtime_id, widget_id);
+        let bounds = self.dp_surface_size();
+        self.tooltips.shown = Some(Tooltip::new(text, anchor, &mut self.handle.fcx, bounds));
+        self.handle.if_window(|w| w.invalidate());
+    }
+
+    /// Passes the messages sent to the window to its root component.
+    fn receive_messages(&mut self) {
+        // Handlers can send more messages to the window, which are received next time.
+        for letter in windows::take_letters(self.id) {
+            letter(self.component.get_comp_struct());
+        }
+    }
+
+    /// Runs the menu handler `id` of the component with `runtime_id`.
+    fn menu_command(&mut self, runtime_id: RuntimeID, id: u32) {
+        self.component.menu_command(runtime_id, id);
+        if self.component.update_vars(false, &mut self.handle) {
+            self.resize();
+        }
+        self.after_event();
+    }
+
+    /// Opens the context menu of the innermost widget at `pos` with one. Returns true if a menu
+    /// was opened.
+    fn open_context_menu(&mut self, pos: Point) -> bool {
+        let Some(menu) = self
+            .handle
+            .info
+            .widgets_at(pos)
+            .into_iter()
+            .find_map(|(runtime_id, widget_id)| self.component.context_menu(runtime_id, widget_id))
+        else {
+            return false;
+        };
+        if self.handle.window == WindowHandle::default() {
+            let bounds = self.dp_surface_size();
+            self.popup = Some(Popup::new(menu, pos, &mut self.handle.fcx, bounds));
+        } else {
+            self.menu_commands.truncate(self.menu_bar_commands);
+            let native = menu::native_menu(&menu, &mut self.menu_commands, true);
+            self.handle.window.show_context_menu(native, pos);
+        }
+        true
+    }
+
+    /// Selects the item of the popup at `pos`, closing the popup unless a submenu or separator
+    /// was pressed.
+    fn press_popup(&mut self, popup: Popup, pos: Point) {
+        self.handle.if_window(|w| w.invalidate());
+        let Some(index) = popup.item_at(pos) else {
+            return;
+        };
+        match &popup.menu.items[index] {
+            MenuItem::Item { runtime_id, id, .. } => self.menu_command(*runtime_id, *id),
+            MenuItem::Submenu { menu, .. } => {
+                let bounds = self.dp_surface_size();
+                let origin = popup.origin();
+                self.popup = Some(Popup::new(
+                    menu.clone(),
+                    origin,
+                    &mut self.handle.fcx,
+                    bounds,
+                ));
+            }
+            MenuItem::Separator => self.popup = Some(popup),
+        }
+    }
+
+    /// Sends the option chosen from a list to the widget that opened it.
+    fn choose_option(&mut self, list: ListPopup, index: usize) {
+        self.handle.if_window(|w| w.invalidate());
+        let (runtime_id, widget_id) = list.list.owner;
+        let event = WidgetEvent::OptionChosen(index);
+        match self
+            .overlays
+            .iter_mut()
+            .find(|o| o.handle.info.has_runtime_id(runtime_id))
+        {
+            Some(overlay) => {
+                if overlay.event(runtime_id, widget_id, event) | overlay.update_vars(false) {
+                    self.resize();
+                }
+            }
+            None => {
+                let event_resize = self.send_component_event(runtime_id, widget_id, event);
+                let var_resize = self.component.update_vars(false, &mut self.handle);
+                if event_resize || var_resize {
+                    self.resize();
+                }
+            }
+        }
+        self.after_event();
+    }
+
+    /// Sends the long press event of the press that is still held down.
+    fn long_press(&mut self) {
+        if let Some((_, event)) = self.press.take() {
+            let event_resize = self.send_pointer_event(WidgetEvent::LongPress(&event));
+            let var_resize = self.component.update_vars(false, &mut self.handle);
+            if event_resize || var_resize {
+                self.resize();
+            }
+        }
+    }
 }
 
 impl<C: Component + 'static> WinHandler for WindowState<C> {
     fn connect(&mut self, handle: &WindowHandle) {
         self.handle.window = handle.clone();
+        self.handle.clipboard = Clipboard::System;
+        windows::connected(self.id, handle);
+        self.receive_messages();
+        if let Some(menu) = self.component.menu_bar() {
+            let native = menu::native_menu(&menu, &mut self.menu_commands, false);
+            self.menu_bar_commands = self.menu_commands.len();
+            self.handle.window.set_menu(native);
+        }
         self.component.update_vars(true, &mut self.handle);
         self.resize();
         self.render();
+        #[cfg(feature = "hot-reload")]
+        {
+            self.hot_reload_timer = self.handle.window.request_timer(hot_reload::POLL_INTERVAL);
+        }
     }
 
     fn size(&mut self, size: Size) {
+        let clamped = self.window_config.clamp_size(size);
+        if clamped != size {
+            self.handle.window.set_size(clamped);
+        }
+        let size = clamped;
         if self.size != size {
             self.size = size;
             // MacOS hack as it does not correctly listen to widget redraws.
@@ -209,6 +655,7 @@ impl<C: Component + 'static> WinHandler for WindowState<C> {
     }
 
     fn prepare_paint(&mut self) {
+        self.receive_messages();
         if self.component.update_vars(false, &mut self.handle) {
             self.resize();
         }
@@ -218,48 +665,132 @@ impl<C: Component + 'static> WinHandler for WindowState<C> {
         self.render();
     }
 
-    fn command(&mut self, _id: u32) {}
+    fn command(&mut self, id: u32) {
+        if let Some(&(runtime_id, id)) = self.menu_commands.get(id as usize) {
+            self.menu_command(runtime_id, id);
+        }
+    }
 
-    fn save_as(&mut self, _token: FileDialogToken, file: Option<FileInfo>) {
-        println!("save file result: {file:?}");
+    fn save_as(&mut self, token: FileDialogToken, file: Option<FileInfo>) {
+        self.file_dialog_result(token, file.into_iter().map(|f| f.path).collect());
     }
 
-    fn open_file(&mut self, _token: FileDialogToken, file_info: Option<FileInfo>) {
-        println!("open file result: {file_info:?}");
+    fn open_file(&mut self, token: FileDialogToken, file_info: Option<FileInfo>) {
+        self.file_dialog_result(token, file_info.into_iter().map(|f| f.path).collect());
+    }
+
+    fn open_files(&mut self, token: FileDialogToken, files: Vec<FileInfo>) {
+        self.file_dialog_result(token, files.into_iter().map(|f| f.path).collect());
     }
 
     fn key_down(&mut self, event: &KeyEvent) -> bool {
-        println!("keydown: {event:?}");
-        false
+        self.hide_tooltip();
+        if self.popup.is_some() && event.key == KbKey::Escape {
+            self.popup = None;
+            self.handle.if_window(|w| w.invalidate());
+            return true;
+        }
+        // The list of options takes all key presses while it is open.
+        if let Some(list) = self.option_list.as_mut() {
+            match event.key {
+                KbKey::ArrowUp => {
+                    list.move_highlight(-1);
+                }
+                KbKey::ArrowDown => {
+                    list.move_highlight(1);
+                }
+                KbKey::Enter => {
+                    if let Some(index) = list.highlighted {
+                        let list = self.option_list.take().unwrap();
+                        self.choose_option(list, index);
+                    }
+                }
+                KbKey::Escape => self.option_list = None,
+                _ => {}
+            }
+            self.handle.if_window(|w| w.invalidate());
+            return true;
+        }
+        // Modal overlays take all key presses, without running shortcuts.
+        if let Some(overlay) = self.overlays.last_mut().filter(|o| o.modal) {
+            if overlay.key_down(event) | overlay.update_vars(false) {
+                self.resize();
+            }
+            self.after_event();
+            return true;
+        }
+        // Shortcuts take precedence over widgets, so a widget handling the same keys is not sent
+        // the key press.
+        let handled = self.component.shortcut(event);
+        let event_resize = !handled && self.propagate_component_event(WidgetEvent::KeyDown(event));
+        let var_resize = self.component.update_vars(false, &mut self.handle);
+        if event_resize || var_resize {
+            self.resize();
+        }
+        self.after_event();
+        handled
     }
 
     fn key_up(&mut self, event: &KeyEvent) {
         println!("keyup: {event:?}");
     }
 
+    fn pointer_leave(&mut self) {
+        self.hover_tooltip(None);
+    }
+
     fn wheel(&mut self, event: &PointerEvent) {
+        if let Some(list) = self.option_list.as_mut().filter(|l| l.contains(event.pos)) {
+            if list.scroll(event.wheel_delta.y.signum() as isize) {
+                self.handle.if_window(|w| w.invalidate());
+            }
+            return;
+        }
         println!("wheel {event:?}");
     }
 
     fn pointer_move(&mut self, event: &PointerEvent) {
+        // Widgets under the popup are not hovered.
+        if let Some(popup) = self.popup.as_mut().filter(|p| p.contains(event.pos)) {
+            if popup.hover(event.pos) {
+                self.handle.if_window(|w| w.invalidate());
+            }
+            self.hover_tooltip(None);
+            return;
+        }
+        if let Some(list) = self.option_list.as_mut().filter(|l| l.contains(event.pos)) {
+            if list.hover(event.pos) {
+                self.handle.if_window(|w| w.invalidate());
+            }
+            self.hover_tooltip(None);
+            return;
+        }
+        self.hover_tooltip(Some(event.pos));
         if self.handle.window != WindowHandle::default() {
             self.handle.window.set_cursor(&Cursor::Arrow);
         }
+        if self.overlay_pointer_event(event.pos, WidgetEvent::PointerMove(event)) {
+            return;
+        }
         let mouse_point = event.pos;
         let un_hovered_widgets = self.handle.info.remove_un_hovered(mouse_point);
 
         let mut resize = false;
+        if let Some((_, down)) = self
+            .press
+            .take_if(|(_, down)| down.pos.distance(mouse_point) > CLICK_DISTANCE)
+        {
+            resize |= self.send_pointer_event(WidgetEvent::DragStart(&down));
+        }
+        resize |= self.drag_over(mouse_point);
+
         for id in un_hovered_widgets.into_iter() {
-            if self.send_component_event(id.0, id.1, WidgetEvent::HoverChange) {
+            if self.send_component_event(id.0, id.1, WidgetEvent::HoverChange(event)) {
                 resize = true;
             }
         }
 
-        let event_resize = if let Some(id) = self.handle.info.get_active_widget() {
-            self.send_component_event(id.0, id.1, WidgetEvent::PointerMove(event))
-        } else {
-            self.propagate_component_event(WidgetEvent::PointerMove(event))
-        };
+        let event_resize = self.send_pointer_event(WidgetEvent::PointerMove(event));
         let var_resize = self.component.update_vars(false, &mut self.handle);
 
         if event_resize || var_resize || resize {
@@ -268,26 +799,83 @@ impl<C: Component + 'static> WinHandler for WindowState<C> {
     }
 
     fn pointer_down(&mut self, event: &PointerEvent) {
-        let event_resize = self.propagate_component_event(WidgetEvent::PointerDown(event));
+        self.hide_tooltip();
+        if let Some(popup) = self.popup.take() {
+            self.popup_press = true;
+            self.press_popup(popup, event.pos);
+            return;
+        }
+        // A press outside the list of options closes it without choosing one.
+        if let Some(list) = self.option_list.take() {
+            self.popup_press = true;
+            self.handle.if_window(|w| w.invalidate());
+            if let Some(index) = list.option_at(event.pos) {
+                self.choose_option(list, index);
+            }
+            return;
+        }
+        if self.overlay_pointer_event(event.pos, WidgetEvent::PointerDown(event)) {
+            self.after_event();
+            return;
+        }
+        let mut event_resize = self.propagate_component_event(WidgetEvent::PointerDown(event));
+        if event.button == PointerButton::Secondary {
+            self.open_context_menu(event.pos);
+        }
+        if event.button == PointerButton::Primary {
+            if self.is_double_click(event) {
+                event_resize |= self.send_pointer_event(WidgetEvent::DoubleClick(event));
+            }
+            let mut token = TimerToken::INVALID;
+            self.handle
+                .if_window(|w| token = w.request_timer(LONG_PRESS_TIME));
+            self.press = Some((token, event.clone()));
+        }
         let var_resize = self.component.update_vars(false, &mut self.handle);
         if event_resize || var_resize {
             self.resize();
         }
+        self.after_event();
     }
 
     fn pointer_up(&mut self, event: &PointerEvent) {
-        let event_resize = if let Some(id) = self.handle.info.get_active_widget() {
-            self.send_component_event(id.0, id.1, WidgetEvent::PointerUp(event))
-        } else {
-            self.propagate_component_event(WidgetEvent::PointerUp(event))
-        };
+        if std::mem::take(&mut self.popup_press) {
+            return;
+        }
+        if self.overlay_pointer_event(event.pos, WidgetEvent::PointerUp(event)) {
+            self.after_event();
+            return;
+        }
+        let mut event_resize = false;
+        if event.button == PointerButton::Primary {
+            self.press = None;
+            event_resize |= self.drop();
+        }
+        event_resize |= self.send_pointer_event(WidgetEvent::PointerUp(event));
         let var_resize = self.component.update_vars(false, &mut self.handle);
         if event_resize || var_resize {
             self.resize();
         }
+        self.after_event();
     }
 
     fn timer(&mut self, id: TimerToken) {
+        #[cfg(feature = "hot-reload")]
+        if id == self.hot_reload_timer {
+            if hot_reload::poll() {
+                self.handle.window.invalidate();
+            }
+            self.hot_reload_timer = self.handle.window.request_timer(hot_reload::POLL_INTERVAL);
+            return;
+        }
+        if self.press.as_ref().is_some_and(|(token, _)| *token == id) {
+            self.long_press();
+            return;
+        }
+        if self.tooltips.is_timer(id) {
+            self.show_tooltip();
+            return;
+        }
         println!("timer fired: {id:?}");
     }
 
@@ -304,10 +892,16 @@ impl<C: Component + 'static> WinHandler for WindowState<C> {
     }
 
     fn destroy(&mut self) {
-        Application::global().quit();
+        windows::destroyed(self.id);
     }
 
-    fn idle(&mut self, _: IdleToken) {}
+    fn idle(&mut self, token: IdleToken) {
+        if token == windows::OPEN_WINDOWS {
+            if let Err(error) = windows::create_pending() {
+                eprintln!("Could not open a window: {error}");
+            }
+        }
+    }
 
     fn as_any(&mut self) -> &mut dyn Any {
         self
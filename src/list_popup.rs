@@ -0,0 +1,243 @@
+use crate::menu::layout_text;
+use gui_core::common::text::{render_text, ParleyBrush};
+use gui_core::glazier::kurbo::{Affine, Point, Rect, Size};
+use gui_core::menu::OptionList;
+use gui_core::parley::layout::Layout;
+use gui_core::theme;
+use gui_core::vello::peniko::{Brush, Fill, Stroke};
+use gui_core::{Colour, FontContext, SceneBuilder};
+
+const ITEM_HEIGHT: f64 = 24.0;
+const PADDING: f64 = 8.0;
+/// The most options shown at once, after which the list scrolls.
+const MAX_VISIBLE: usize = 8;
+const SCROLLBAR_WIDTH: f64 = 4.0;
+
+/// A list of options opened by a widget, drawn by the window above the overlays next to the
+/// widget.
+pub(crate) struct ListPopup {
+    pub list: OptionList,
+    rect: Rect,
+    layouts: Vec<Layout<ParleyBrush>>,
+    pub highlighted: Option<usize>,
+    /// The index of the first visible option.
+    first: usize,
+}
+
+impl ListPopup {
+    /// Lays out the list below `anchor`, or above it if there is no room below, at least as wide
+    /// as `anchor`.
+    pub fn new(list: OptionList, anchor: Rect, fcx: &mut FontContext, bounds: Size) -> Self {
+        let layouts: Vec<_> = list
+            .options
+            .iter()
+            .map(|option| layout_text(option, fcx))
+            .collect();
+
+        let width = layouts
+            .iter()
+            .map(|layout| layout.width() as f64 + PADDING * 2.0)
+            .fold(anchor.width(), f64::max);
+        let height = list.options.len().min(MAX_VISIBLE) as f64 * ITEM_HEIGHT + PADDING;
+        let y = if anchor.y1 + height > bounds.height && anchor.y0 >= height {
+            anchor.y0 - height
+        } else {
+            anchor.y1
+        };
+        let origin = Point::new(anchor.x0.min(bounds.width - width).max(0.0), y);
+
+        // The selected index comes from the widget, which may not have clamped it to the options.
+        let mut popup = Self {
+            highlighted: list
+                .selected
+                .filter(|&selected| selected < list.options.len()),
+            list,
+            rect: Rect::from_origin_size(origin, (width, height)),
+            layouts,
+            first: 0,
+        };
+        if let Some(selected) = popup.highlighted {
+            popup.scroll_to(selected);
+        }
+        popup
+    }
+
+    fn visible(&self) -> usize {
+        self.list.options.len().min(MAX_VISIBLE)
+    }
+
+    pub fn contains(&self, pos: Point) -> bool {
+        self.rect.contains(pos)
+    }
+
+    /// The index of the option at `pos`, which is `None` if it is outside the list.
+    pub fn option_at(&self, pos: Point) -> Option<usize> {
+        if !self.contains(pos) {
+            return None;
+        }
+        let row = ((pos.y - self.rect.y0 - PADDING / 2.0) / ITEM_HEIGHT).floor();
+        (row >= 0.0 && (row as usize) < self.visible()).then(|| self.first + row as usize)
+    }
+
+    /// The global rect of an option, scrolling the list so that it is visible.
+    pub fn option_rect(&mut self, index: usize) -> Rect {
+        self.scroll_to(index);
+        let y = self.rect.y0 + PADDING / 2.0 + (index - self.first) as f64 * ITEM_HEIGHT;
+        Rect::new(self.rect.x0, y, self.rect.x1, y + ITEM_HEIGHT)
+    }
+
+    /// Scrolls the list as little as possible so that the option at `index` is visible.
+    fn scroll_to(&mut self, index: usize) {
+        if index < self.first {
+            self.first = index;
+        } else if index >= self.first + self.visible() {
+            self.first = index + 1 - self.visible();
+        }
+    }
+
+    /// Scrolls the list by a number of options. Returns true if it was scrolled.
+    pub fn scroll(&mut self, options: isize) -> bool {
+        let last = self.list.options.len() - self.visible();
+        let first = self.first.saturating_add_signed(options).min(last);
+        std::mem::replace(&mut self.first, first) != first
+    }
+
+    /// Highlights the option under the pointer. Returns true if the highlighted option changed.
+    pub fn hover(&mut self, pos: Point) -> bool {
+        let Some(hovered) = self.option_at(pos) else {
+            return false;
+        };
+        self.highlighted.replace(hovered) != Some(hovered)
+    }
+
+    /// Moves the highlight by `offset` options, starting from the first or last option if none
+    /// are highlighted. Returns true if the highlighted option changed.
+    pub fn move_highlight(&mut self, offset: isize) -> bool {
+        let Some(last) = self.list.options.len().checked_sub(1) else {
+            return false;
+        };
+        let highlighted = match self.highlighted {
+            Some(index) => index.saturating_add_signed(offset).min(last),
+            None if offset < 0 => last,
+            None => 0,
+        };
+        self.scroll_to(highlighted);
+        self.highlighted.replace(highlighted) != Some(highlighted)
+    }
+
+    pub fn render(&self, scene: &mut SceneBuilder, transform: Affine) {
+        let rect = self.rect.to_rounded_rect(4.0);
+        scene.fill(
+            Fill::NonZero,
+            transform,
+            &Brush::Solid(theme::get_or_default::<Colour>("surface").0),
+            None,
+            &rect,
+        );
+        scene.stroke(
+            &Stroke::new(1.0),
+            transform,
+            &Brush::Solid(theme::get_or_default::<Colour>("border").0),
+            None,
+            &rect,
+        );
+
+        let top = self.rect.y0 + PADDING / 2.0;
+        for (row, index) in (self.first..self.first + self.visible()).enumerate() {
+            let y = top + row as f64 * ITEM_HEIGHT;
+            if self.highlighted == Some(index) {
+                let colour = theme::get_or_default::<Colour>("primary")
+                    .0
+                    .with_alpha_factor(0.2);
+                scene.fill(
+                    Fill::NonZero,
+                    transform,
+                    &Brush::Solid(colour),
+                    None,
+                    &Rect::new(self.rect.x0, y, self.rect.x1, y + ITEM_HEIGHT),
+                );
+            }
+            let layout = &self.layouts[index];
+            let offset = (ITEM_HEIGHT - layout.height() as f64) / 2.0;
+            let origin = Point::new(self.rect.x0 + PADDING, y + offset);
+            render_text(
+                scene,
+                transform * Affine::translate(origin.to_vec2()),
+                layout,
+            );
+        }
+
+        // The scrollbar shows which part of a long list is visible.
+        let count = self.list.options.len();
+        if count > MAX_VISIBLE {
+            let track = self.rect.height() - PADDING;
+            let y = top + track * self.first as f64 / count as f64;
+            let height = track * MAX_VISIBLE as f64 / count as f64;
+            let x = self.rect.x1 - SCROLLBAR_WIDTH - 2.0;
+            scene.fill(
+                Fill::NonZero,
+                transform,
+                &Brush::Solid(theme::get_or_default::<Colour>("border").0),
+                None,
+                &Rect::new(x, y, x + SCROLLBAR_WIDTH, y + height).to_rounded_rect(2.0),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ListPopup, MAX_VISIBLE};
+    use gui_core::glazier::kurbo::{Rect, Size};
+    use gui_core::menu::OptionList;
+    use gui_core::widget::{RuntimeID, WidgetID};
+    use gui_core::FontContext;
+
+    fn popup(count: usize, selected: Option<usize>) -> ListPopup {
+        let list = OptionList {
+            owner: (RuntimeID::new(0), WidgetID::new(0)),
+            options: (0..count).map(|i| format!("Option {i}")).collect(),
+            selected,
+        };
+        let anchor = Rect::new(10.0, 10.0, 110.0, 30.0);
+        ListPopup::new(
+            list,
+            anchor,
+            &mut FontContext::new(),
+            Size::new(400.0, 400.0),
+        )
+    }
+
+    #[test]
+    fn scrolls_long_lists() {
+        let mut popup = popup(20, Some(15));
+        let rect = popup.option_rect(15);
+        assert_eq!(popup.option_at(rect.center()), Some(15));
+        // The selected option is the last one visible.
+        assert_eq!(popup.first, 15 + 1 - MAX_VISIBLE);
+
+        assert!(popup.move_highlight(4));
+        assert_eq!(popup.highlighted, Some(19));
+        assert!(!popup.move_highlight(1));
+        assert!(!popup.scroll(1));
+        assert!(popup.scroll(-100));
+        assert_eq!(popup.option_at(popup.option_rect(0).center()), Some(0));
+    }
+
+    #[test]
+    fn highlights_from_the_ends() {
+        let mut popup = popup(3, None);
+        assert!(popup.move_highlight(-1));
+        assert_eq!(popup.highlighted, Some(2));
+        assert!(!popup.scroll(1));
+    }
+
+    #[test]
+    fn ignores_selection_past_the_end() {
+        let mut popup = popup(3, Some(5));
+        assert_eq!(popup.highlighted, None);
+        assert_eq!(popup.first, 0);
+        assert!(popup.move_highlight(1));
+        assert_eq!(popup.highlighted, Some(0));
+    }
+}
@@ -0,0 +1,198 @@
+use gui_core::common::text::{render_text, ParleyBrush};
+use gui_core::glazier::kurbo::{Affine, Line, Point, Rect, Size};
+use gui_core::menu::{Menu, MenuItem};
+use gui_core::parley::layout::{Alignment, Layout};
+use gui_core::parley::style::StyleProperty;
+use gui_core::parley::LayoutContext;
+use gui_core::theme;
+use gui_core::vello::peniko::{Brush, Fill, Stroke};
+use gui_core::widget::RuntimeID;
+use gui_core::{Colour, FontContext, SceneBuilder};
+
+const ITEM_HEIGHT: f64 = 24.0;
+const SEPARATOR_HEIGHT: f64 = 9.0;
+const PADDING: f64 = 8.0;
+const TEXT_SIZE: f32 = 14.0;
+
+/// Builds a native menu. The component and handler of each item is pushed to `commands`, so the
+/// index of an item in `commands` is the id of its command.
+pub(crate) fn native_menu(
+    menu: &Menu,
+    commands: &mut Vec<(RuntimeID, u32)>,
+    popup: bool,
+) -> gui_core::glazier::Menu {
+    let mut native = if popup {
+        gui_core::glazier::Menu::new_for_popup()
+    } else {
+        gui_core::glazier::Menu::new()
+    };
+    for item in &menu.items {
+        match item {
+            MenuItem::Item {
+                runtime_id,
+                id,
+                text,
+            } => {
+                native.add_item(commands.len() as u32, text, None, None, true);
+                commands.push((*runtime_id, *id));
+            }
+            MenuItem::Submenu { text, menu } => {
+                native.add_dropdown(native_menu(menu, commands, popup), text, true)
+            }
+            MenuItem::Separator => native.add_separator(),
+        }
+    }
+    native
+}
+
+/// Lays out `text` on one line in the style of menus, which is also used by tooltips.
+pub(crate) fn layout_text(text: &str, fcx: &mut FontContext) -> Layout<ParleyBrush> {
+    let colour = theme::get_or_default::<Colour>("text").0;
+    let mut lcx = LayoutContext::new();
+    let mut layout_builder = lcx.ranged_builder(fcx, text, 1.0);
+    layout_builder.push_default(&StyleProperty::FontSize(TEXT_SIZE));
+    layout_builder.push_default(&StyleProperty::Brush(ParleyBrush(Brush::Solid(colour))));
+    let mut layout = layout_builder.build();
+    layout.break_all_lines(None, Alignment::Start);
+    layout
+}
+
+/// A menu drawn over the component, used for context menus when there is no window to show a
+/// native menu, such as in a [`TestHarness`](crate::TestHarness).
+pub(crate) struct Popup {
+    pub menu: Menu,
+    rect: Rect,
+    /// The global rect of each item, with the layout of its text.
+    items: Vec<(Rect, Option<Layout<ParleyBrush>>)>,
+    hovered: Option<usize>,
+}
+
+impl Popup {
+    /// Lays out the menu at `pos`, moving it so that it fits within `bounds` if possible.
+    pub fn new(menu: Menu, pos: Point, fcx: &mut FontContext, bounds: Size) -> Self {
+        let layouts: Vec<_> = menu
+            .items
+            .iter()
+            .map(|item| Some(layout_text(item.text()?, fcx)))
+            .collect();
+
+        let width = layouts
+            .iter()
+            .flatten()
+            .map(|layout| layout.width() as f64)
+            .fold(0.0, f64::max)
+            + PADDING * 2.0;
+        let height = menu
+            .items
+            .iter()
+            .map(|item| match item {
+                MenuItem::Separator => SEPARATOR_HEIGHT,
+                _ => ITEM_HEIGHT,
+            })
+            .sum::<f64>()
+            + PADDING;
+        let origin = Point::new(
+            pos.x.min(bounds.width - width).max(0.0),
+            pos.y.min(bounds.height - height).max(0.0),
+        );
+        let rect = Rect::from_origin_size(origin, (width, height));
+
+        let mut y = rect.y0 + PADDING / 2.0;
+        let items = menu
+            .items
+            .iter()
+            .zip(layouts)
+            .map(|(item, layout)| {
+                let height = match item {
+                    MenuItem::Separator => SEPARATOR_HEIGHT,
+                    _ => ITEM_HEIGHT,
+                };
+                let item_rect = Rect::new(rect.x0, y, rect.x1, y + height);
+                y += height;
+                (item_rect, layout)
+            })
+            .collect();
+
+        Self {
+            menu,
+            rect,
+            items,
+            hovered: None,
+        }
+    }
+
+    pub fn origin(&self) -> Point {
+        self.rect.origin()
+    }
+
+    /// The index of the item at `pos`, which is `None` if it is outside the popup.
+    pub fn item_at(&self, pos: Point) -> Option<usize> {
+        self.items.iter().position(|(rect, _)| rect.contains(pos))
+    }
+
+    /// The global rect of the first item with `text`.
+    pub fn item_rect(&self, text: &str) -> Option<Rect> {
+        let index = self
+            .menu
+            .items
+            .iter()
+            .position(|i| i.text() == Some(text))?;
+        Some(self.items[index].0)
+    }
+
+    pub fn contains(&self, pos: Point) -> bool {
+        self.rect.contains(pos)
+    }
+
+    /// Highlights the item under the pointer. Returns true if the highlighted item changed.
+    pub fn hover(&mut self, pos: Point) -> bool {
+        let hovered = self
+            .item_at(pos)
+            .filter(|i| !matches!(self.menu.items[*i], MenuItem::Separator));
+        std::mem::replace(&mut self.hovered, hovered) != hovered
+    }
+
+    pub fn render(&self, scene: &mut SceneBuilder, transform: Affine) {
+        let rect = self.rect.to_rounded_rect(4.0);
+        let border = Brush::Solid(theme::get_or_default::<Colour>("border").0);
+        scene.fill(
+            Fill::NonZero,
+            transform,
+            &Brush::Solid(theme::get_or_default::<Colour>("surface").0),
+            None,
+            &rect,
+        );
+        scene.stroke(&Stroke::new(1.0), transform, &border, None, &rect);
+
+        for (index, (item_rect, layout)) in self.items.iter().enumerate() {
+            if self.hovered == Some(index) {
+                let colour = theme::get_or_default::<Colour>("primary")
+                    .0
+                    .with_alpha_factor(0.2);
+                scene.fill(
+                    Fill::NonZero,
+                    transform,
+                    &Brush::Solid(colour),
+                    None,
+                    item_rect,
+                );
+            }
+            match layout {
+                Some(layout) => {
+                    let offset = (ITEM_HEIGHT - layout.height() as f64) / 2.0;
+                    let origin = Point::new(item_rect.x0 + PADDING, item_rect.y0 + offset);
+                    render_text(
+                        scene,
+                        transform * Affine::translate(origin.to_vec2()),
+                        layout,
+                    );
+                }
+                None => {
+                    let y = item_rect.center().y;
+                    let line = Line::new((item_rect.x0 + PADDING, y), (item_rect.x1 - PADDING, y));
+                    scene.stroke(&Stroke::new(1.0), transform, &border, None, &line);
+                }
+            }
+        }
+    }
+}
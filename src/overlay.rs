@@ -0,0 +1,254 @@
+//! Components drawn above the component of a window. Handlers can show a dialog with
+//! [`show_dialog`], which blocks input to the window until it is closed, or a non-modal overlay
+//! with [`show_overlay`].
+
+use gui_core::glazier::kurbo::{Affine, Point, Rect, Size};
+use gui_core::glazier::{KeyEvent, PointerEvent};
+use gui_core::theme;
+use gui_core::vello::peniko::{Brush, Fill, Stroke};
+use gui_core::vello::SceneFragment;
+use gui_core::widget::{Handle, RuntimeID, WidgetEvent, WidgetID};
+use gui_core::{Colour, Component, LayoutConstraints, OnMessage, SceneBuilder, ToComponent};
+use std::any::{type_name, Any};
+use std::cell::RefCell;
+
+/// Implemented by components shown with [`show_dialog`] or [`show_overlay`], which are closed
+/// once they have a result.
+pub trait Dialog: ToComponent {
+    type Result;
+    /// Checked after every event, the dialog is closed when this returns a result.
+    fn take_result(&mut self) -> Option<Self::Result>;
+}
+
+/// Passes the result of a dialog to the component that showed it, which is found from the
+/// component of the window. Returns true if the dialog has a result.
+type CloseFn = Box<dyn FnMut(&mut dyn Any, &mut dyn Component) -> bool>;
+
+pub(crate) struct Overlay {
+    pub component: Box<dyn Component>,
+    /// Each overlay positions its widgets separately from the window.
+    pub handle: Handle,
+    /// Modal overlays receive all input until they are closed.
+    pub modal: bool,
+    close: CloseFn,
+}
+
+thread_local! {
+    /// Overlays shown while handling an event, which are added to the window that handled it.
+    static PENDING: RefCell<Vec<Overlay>> = const { RefCell::new(vec![]) };
+}
+
+/// Shows `dialog` above the component of the window, blocking input to the window until the
+/// dialog has a result. The result is sent as a message to the component whose handler showed the
+/// dialog, which must be a `T`, using `to_message`. If it is not, or it has been removed, the
+/// result is dropped with an error.
+///
+/// ```ignore
+/// gui::show_dialog::<Editor, _>(Confirm::new("Delete the file?"), Message::ConfirmDelete);
+/// ```
+pub fn show_dialog<T, D>(dialog: D, to_message: impl FnOnce(D::Result) -> T::Message + 'static)
+where
+    T: OnMessage + 'static,
+    D: Dialog + 'static,
+    <D as ToComponent>::Component: 'static,
+{
+    push::<T, D>(dialog, to_message, true)
+}
+
+/// Shows `dialog` above the component of the window like [`show_dialog`], but only receives
+/// the input over it so the rest of the window can still be used.
+pub fn show_overlay<T, D>(dialog: D, to_message: impl FnOnce(D::Result) -> T::Message + 'static)
+where
+    T: OnMessage + 'static,
+    D: Dialog + 'static,
+    <D as ToComponent>::Component: 'static,
+{
+    push::<T, D>(dialog, to_message, false)
+}
+
+fn push<T, D>(dialog: D, to_message: impl FnOnce(D::Result) -> T::Message + 'static, modal: bool)
+where
+    T: OnMessage + 'static,
+    D: Dialog + 'static,
+    <D as ToComponent>::Component: 'static,
+{
+    // Dialogs shown outside of a handler send their result to the root component.
+    let caller = RuntimeID::running();
+    let mut to_message = Some(to_message);
+    let close = move |dialog: &mut dyn Any, root: &mut dyn Component| {
+        let dialog = dialog
+            .downcast_mut::<D>()
+            .expect("the component of the overlay is a D");
+        let Some(result) = dialog.take_result() else {
+            return false;
+        };
+        let Some(to_message) = to_message.take() else {
+            return true;
+        };
+        let mut message = Some(to_message(result));
+        let mut send = |component: &mut dyn Any| match component.downcast_mut::<T>() {
+            Some(component) => component.on_message(message.take().expect("sent once")),
+            None => eprintln!(
+                "Could not send the result of dialog {} to {}, as it was shown by a component of \
+                another type",
+                type_name::<D>(),
+                type_name::<T>()
+            ),
+        };
+        let caller = caller.unwrap_or(root.id());
+        if !root.with_comp_struct(caller, &mut send) {
+            eprintln!(
+                "Could not send the result of dialog {} to the component that showed it, as it is \
+                no longer in the window",
+                type_name::<D>()
+            );
+        }
+        true
+    };
+    let overlay = Overlay {
+        component: Box::new(dialog.to_component_holder(RuntimeID::next())),
+        handle: Handle::default(),
+        modal,
+        close: Box::new(close),
+    };
+    PENDING.with_borrow_mut(|pending| pending.push(overlay));
+}
+
+/// Renders the overlays above the component of a window of `size` on a surface, dimming what is
+/// below a modal overlay.
+pub(crate) fn render(
+    overlays: &mut [Overlay],
+    scene: &mut SceneBuilder,
+    size: Size,
+    transform: Affine,
+) {
+    for overlay in overlays {
+        if overlay.modal {
+            let colour = theme::get_or_default::<Colour>("text")
+                .0
+                .with_alpha_factor(0.3);
+            scene.fill(
+                Fill::NonZero,
+                transform,
+                &Brush::Solid(colour),
+                None,
+                &size.to_rect(),
+            );
+        }
+        let surface = overlay.rect().to_rounded_rect(8.0);
+        scene.fill(
+            Fill::NonZero,
+            transform,
+            &Brush::Solid(theme::get_or_default::<Colour>("surface").0),
+            None,
+            &surface,
+        );
+        scene.stroke(
+            &Stroke::new(1.0),
+            transform,
+            &Brush::Solid(theme::get_or_default::<Colour>("border").0),
+            None,
+            &surface,
+        );
+        let mut fragment = SceneFragment::new();
+        let mut component = SceneBuilder::for_fragment(&mut fragment);
+        overlay
+            .component
+            .render(&mut component, &mut overlay.handle);
+        scene.append(
+            &fragment,
+            Some(transform * Affine::translate(overlay.rect().origin().to_vec2())),
+        );
+    }
+}
+
+/// Takes the overlays shown since this was last called.
+pub(crate) fn take_pending() -> Vec<Overlay> {
+    PENDING.with_borrow_mut(std::mem::take)
+}
+
+impl Overlay {
+    /// The rect of the overlay in the window.
+    pub fn rect(&self) -> Rect {
+        self.handle.info.get_parent_rect()
+    }
+
+    /// Whether the overlay receives pointer events at `pos`.
+    pub fn captures(&self, pos: Point) -> bool {
+        self.modal || self.rect().contains(pos)
+    }
+
+    /// Lays out the component in the centre of the window.
+    pub fn resize(&mut self, window_size: Size) {
+        self.handle.info.reset_positions();
+        self.handle.info.clear_drop_targets();
+        self.handle.info.clear_hidden();
+        let size = self
+            .component
+            .resize(LayoutConstraints::new_max(window_size), &mut self.handle);
+        self.handle.info.convert_to_global_positions(
+            Rect::from_center_size((window_size / 2.0).to_vec2().to_point(), size),
+            &*self.component,
+        );
+    }
+
+    /// Updates the variables of the component, returning true if it needs to be resized.
+    pub fn update_vars(&mut self, force_update: bool) -> bool {
+        self.component.update_vars(force_update, &mut self.handle)
+    }
+
+    /// Sends a pointer event to the active widget of the overlay, or to all its widgets if none
+    /// are active.
+    pub fn pointer_event(&mut self, event: WidgetEvent) -> bool {
+        match self.handle.info.get_active_widget() {
+            Some((runtime_id, widget_id)) => {
+                self.component
+                    .event(runtime_id, widget_id, event, &mut self.handle)
+            }
+            None => self.component.propagate_event(event, &mut self.handle),
+        }
+    }
+
+    pub fn pointer_move(&mut self, event: &PointerEvent) -> bool {
+        let mut resize = false;
+        for (runtime_id, widget_id) in self.handle.info.remove_un_hovered(event.pos) {
+            resize |= self.component.event(
+                runtime_id,
+                widget_id,
+                WidgetEvent::HoverChange(event),
+                &mut self.handle,
+            );
+        }
+        resize | self.pointer_event(WidgetEvent::PointerMove(event))
+    }
+
+    /// Sends an event to a widget of the overlay.
+    pub fn event(
+        &mut self,
+        runtime_id: RuntimeID,
+        widget_id: WidgetID,
+        event: WidgetEvent,
+    ) -> bool {
+        self.component
+            .event(runtime_id, widget_id, event, &mut self.handle)
+    }
+
+    pub fn key_down(&mut self, event: &KeyEvent) -> bool {
+        self.component
+            .propagate_event(WidgetEvent::KeyDown(event), &mut self.handle)
+    }
+
+    /// Passes the result of the dialog to the component in `root` that showed it if it has one.
+    /// Returns true if the overlay should be closed.
+    pub fn close(&mut self, root: &mut dyn Component) -> bool {
+        (self.close)(self.component.get_comp_struct(), root)
+    }
+
+    /// The rect of a widget of the overlay, or `None` if the widget is not in the overlay.
+    pub fn widget_rect(&self, runtime_id: RuntimeID, widget_id: WidgetID) -> Option<Rect> {
+        self.handle
+            .info
+            .has_runtime_id(runtime_id)
+            .then(|| self.handle.info.get_rect(runtime_id, widget_id))
+    }
+}
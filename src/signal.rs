@@ -0,0 +1,225 @@
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+thread_local! {
+    /// The dependencies read by each [`Computed`] that is currently being evaluated.
+    static TRACKING: RefCell<Vec<Vec<Dependency>>> = const { RefCell::new(vec![]) };
+}
+
+/// A [`Signal`] read while evaluating a [`Computed`], with the version that was read.
+#[derive(Clone)]
+struct Dependency {
+    version: Rc<Cell<u64>>,
+    read: u64,
+}
+
+impl Dependency {
+    fn is_updated(&self) -> bool {
+        self.version.get() != self.read
+    }
+}
+
+struct SignalInner<T> {
+    value: RefCell<T>,
+    /// Incremented every time the value is changed.
+    version: Rc<Cell<u64>>,
+}
+
+/// Shared value that records when it is read by a [`Computed`], so that the [`Computed`] is
+/// updated when the value changes. Cloning a signal shares the value, so clones can be moved into
+/// the functions of [`Computed`] variables.
+///
+/// Like [`Updateable`](crate::Updateable), signals can be used as fields of components for
+/// variables of the same name.
+pub struct Signal<T> {
+    inner: Rc<SignalInner<T>>,
+    /// The version last seen by `reset`, used as the field of a component.
+    seen: u64,
+}
+
+impl<T> Signal<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: Rc::new(SignalInner {
+                value: RefCell::new(value),
+                version: Rc::new(Cell::new(0)),
+            }),
+            seen: 0,
+        }
+    }
+
+    /// Runs `f` with a reference to the value, tracking the read if a [`Computed`] is being
+    /// evaluated.
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        self.track();
+        f(&self.inner.value.borrow())
+    }
+
+    /// Changes the value using `f`, updating any [`Computed`] that read it.
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        f(&mut self.inner.value.borrow_mut());
+        self.inner.version.set(self.inner.version.get() + 1);
+    }
+
+    pub fn is_updated(&self) -> bool {
+        self.inner.version.get() != self.seen
+    }
+
+    pub fn reset(&mut self) {
+        self.seen = self.inner.version.get();
+    }
+
+    fn track(&self) {
+        TRACKING.with_borrow_mut(|tracking| {
+            if let Some(dependencies) = tracking.last_mut() {
+                dependencies.push(Dependency {
+                    version: self.inner.version.clone(),
+                    read: self.inner.version.get(),
+                });
+            }
+        });
+    }
+}
+
+impl<T: Clone> Signal<T> {
+    pub fn value(&self) -> T {
+        self.with(T::clone)
+    }
+}
+
+impl<T: PartialEq> Signal<T> {
+    /// Set the value to `value`, updating any [`Computed`] that read it if the value has changed.
+    pub fn set_value(&self, value: T) {
+        if *self.inner.value.borrow() != value {
+            self.update(|v| *v = value);
+        }
+    }
+}
+
+impl<T> Clone for Signal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            seen: self.seen,
+        }
+    }
+}
+
+impl<T: Default> Default for Signal<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: Debug> Debug for Signal<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Signal")
+            .field(&self.inner.value.borrow())
+            .finish()
+    }
+}
+
+/// Value calculated from [`Signal`]s. The signals read by the function are recorded every time it
+/// is run, and the value is updated when any of them change, so the dependencies of a variable do
+/// not need to be listed by hand. Use this as the field of a component for a variable of the same
+/// name.
+pub struct Computed<T> {
+    compute: Box<dyn Fn() -> T>,
+    /// `None` until the value has been calculated.
+    dependencies: RefCell<Option<Vec<Dependency>>>,
+}
+
+impl<T> Computed<T> {
+    pub fn new(compute: impl Fn() -> T + 'static) -> Self {
+        Self {
+            compute: Box::new(compute),
+            dependencies: RefCell::new(None),
+        }
+    }
+
+    pub fn is_updated(&self) -> bool {
+        self.dependencies
+            .borrow()
+            .as_ref()
+            .is_none_or(|d| d.iter().any(Dependency::is_updated))
+    }
+
+    /// Calculates the value, recording the signals that were read.
+    pub fn value(&self) -> T {
+        TRACKING.with_borrow_mut(|tracking| tracking.push(vec![]));
+        let value = (self.compute)();
+        let dependencies = TRACKING.with_borrow_mut(|tracking| {
+            let dependencies = tracking.pop().expect("tracking was started");
+            // A computed value read by another depends on the same signals.
+            if let Some(parent) = tracking.last_mut() {
+                parent.extend(dependencies.iter().cloned());
+            }
+            dependencies
+        });
+        *self.dependencies.borrow_mut() = Some(dependencies);
+        value
+    }
+
+    /// The dependencies are reset when the value is calculated.
+    pub fn reset(&mut self) {}
+}
+
+impl<T> Debug for Computed<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Computed").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Computed, Signal};
+
+    #[test]
+    fn signal_updated() {
+        let mut signal = Signal::new(5);
+        assert!(!signal.is_updated());
+        signal.set_value(5);
+        assert!(!signal.is_updated());
+        signal.set_value(10);
+        assert!(signal.is_updated());
+        assert_eq!(signal.value(), 10);
+        signal.reset();
+        assert!(!signal.is_updated());
+    }
+
+    #[test]
+    fn computed_tracks_reads() {
+        let count = Signal::new(0);
+        let other = Signal::new(0);
+        let disabled = Computed::new({
+            let count = count.clone();
+            move || count.value() == 0
+        });
+        assert!(disabled.is_updated());
+        assert!(disabled.value());
+        assert!(!disabled.is_updated());
+
+        other.set_value(1);
+        assert!(!disabled.is_updated());
+        count.update(|c| *c += 1);
+        assert!(disabled.is_updated());
+        assert!(!disabled.value());
+        assert!(!disabled.is_updated());
+    }
+
+    #[test]
+    fn nested_computed() {
+        let count = Signal::new(1);
+        let double = Computed::new({
+            let count = count.clone();
+            move || count.value() * 2
+        });
+        let text = Computed::new(move || format!("{}", double.value()));
+        assert_eq!(text.value(), "2");
+        count.set_value(2);
+        assert!(text.is_updated());
+        assert_eq!(text.value(), "4");
+    }
+}
@@ -1,10 +1,12 @@
 mod messages;
 mod render;
 
-use crate::WindowState;
+use crate::{windows, WindowState};
 use gui_core::glazier::kurbo::Rect;
-use gui_core::glazier::{PointerButton, PointerEvent, WinHandler};
-use gui_core::widget::{RuntimeID, WidgetID};
+use gui_core::glazier::{KbKey, KeyEvent, Modifiers, PointerButton, PointerEvent, WinHandler};
+use gui_core::menu::{Menu, MenuItem};
+use gui_core::parse::shortcut::KeyChord;
+use gui_core::widget::{FileDialogs, RuntimeID, WidgetID};
 use gui_core::{Component, Point, Size, ToComponent};
 use image::io::Reader as ImageReader;
 use image::{ImageBuffer, Pixel, Rgba};
@@ -67,19 +69,33 @@ where
 
 impl<T: ToComponent> TestHarness<T> {
     pub fn new<S: Into<Size>>(component: T, size: S) -> Self {
+        let window_state = WindowState::new(
+            component.to_component_holder(RuntimeID::next()),
+            Default::default(),
+        )
+        .expect("failed to create the renderer");
+        // Windows opened by the component can message it with `main_window`.
+        windows::set_main(window_state.id, Default::default());
+        Self::from_window_state(window_state, size.into())
+    }
+
+    fn from_window_state(mut window_state: WindowState<T::Component>, size: Size) -> Self {
+        // Dialogs are completed with `choose_files` instead of being shown.
+        window_state.handle.file_dialogs = FileDialogs::Simulated;
         let mut harness = Self {
-            window_state: WindowState::new(component.to_component_holder(RuntimeID::next())),
+            window_state,
             report: TestReport::default(),
             image_buffer: vec![],
             last_mouse_pos: None,
             phantom: PhantomData,
         };
-        harness.init(size.into());
+        harness.init(size);
         harness
     }
 
     fn init(&mut self, size: Size) {
         self.window_state.size = size;
+        self.window_state.receive_messages();
         self.window_state
             .component
             .update_vars(true, &mut self.window_state.handle);
@@ -168,18 +184,25 @@ impl<T: ToComponent> TestHarness<T> {
         }
     }
 
+    /// Finds a widget by name, looking in the topmost overlay first.
     pub fn get_id(&self, name: &str) -> Option<(RuntimeID, WidgetID)> {
-        self.window_state.component.get_id(name)
+        self.window_state
+            .overlays
+            .iter()
+            .rev()
+            .find_map(|o| o.component.get_id(name))
+            .or_else(|| self.window_state.component.get_id(name))
+    }
+
+    /// The number of overlays and dialogs that are shown.
+    pub fn overlay_count(&self) -> usize {
+        self.window_state.overlays.len()
     }
 
     pub fn get_local_rect(&self, runtime_id: RuntimeID, widget_id: WidgetID) -> Rect {
         Rect::from_origin_size(
             (0.0, 0.0),
-            self.window_state
-                .handle
-                .info
-                .get_rect(runtime_id, widget_id)
-                .size(),
+            self.window_state.widget_rect(runtime_id, widget_id).size(),
         )
     }
 
@@ -191,9 +214,7 @@ impl<T: ToComponent> TestHarness<T> {
     ) -> Point {
         (self
             .window_state
-            .handle
-            .info
-            .get_rect(runtime_id, widget_id)
+            .widget_rect(runtime_id, widget_id)
             .origin()
             .to_vec2()
             + local_pos.to_vec2())
@@ -219,7 +240,7 @@ impl<T: ToComponent> TestHarness<T> {
                 self.last_mouse_pos
                     .unwrap_or_else(|| self.window_state.size.to_rect().center())
             },
-            |id| self.window_state.handle.info.get_rect(id.0, id.1).center(),
+            |id| self.window_state.widget_rect(id.0, id.1).center(),
         );
         let mut pointer_event = PointerEvent {
             pos,
@@ -245,7 +266,7 @@ impl<T: ToComponent> TestHarness<T> {
                 self.last_mouse_pos
                     .unwrap_or_else(|| self.window_state.size.to_rect().center())
             },
-            |id| self.window_state.handle.info.get_rect(id.0, id.1).center(),
+            |id| self.window_state.widget_rect(id.0, id.1).center(),
         );
         let mut pointer_event = PointerEvent {
             pos,
@@ -261,6 +282,162 @@ impl<T: ToComponent> TestHarness<T> {
         self.window_state.pointer_up(&pointer_event);
     }
 
+    /// Presses and releases the primary button after holding it down long enough to be a long
+    /// press.
+    pub fn simulate_long_press(&mut self, local_pos: Option<(RuntimeID, WidgetID)>) {
+        self.simulate_pointer_down(PointerButton::Primary, local_pos);
+        self.window_state.long_press();
+        self.simulate_pointer_up(PointerButton::Primary, local_pos);
+    }
+
+    /// Moves the pointer over a widget and keeps it there long enough for a tooltip to be shown.
+    pub fn simulate_hover(&mut self, id: (RuntimeID, WidgetID)) {
+        self.simulate_pointer_move(id.0, id.1, None);
+        self.window_state.show_tooltip();
+    }
+
+    /// The text of the tooltip that is shown.
+    pub fn get_tooltip(&self) -> Option<&str> {
+        self.window_state
+            .tooltips
+            .shown
+            .as_ref()
+            .map(|t| t.text.as_str())
+    }
+
+    /// Presses the primary button on the `from` widget and releases it over the `to` widget.
+    pub fn simulate_drag(&mut self, from: (RuntimeID, WidgetID), to: (RuntimeID, WidgetID)) {
+        self.simulate_pointer_down(PointerButton::Primary, Some(from));
+        self.simulate_pointer_up(PointerButton::Primary, Some(to));
+    }
+
+    /// Closes the file dialog that was opened last as if `paths` were chosen. Use an empty `paths`
+    /// to cancel the dialog.
+    pub fn choose_files<P: Into<PathBuf>>(&mut self, paths: impl IntoIterator<Item = P>) {
+        let token = self
+            .window_state
+            .handle
+            .info
+            .last_file_dialog()
+            .expect("a file dialog is open");
+        let paths = paths.into_iter().map(Into::into).collect();
+        self.window_state.file_dialog_result(token, paths);
+    }
+
+    pub fn simulate_key_down(&mut self, event: &KeyEvent) {
+        self.window_state.key_down(event);
+    }
+
+    /// Presses a key chord written as in a `shortcuts` declaration, such as `Ctrl+S`.
+    pub fn simulate_shortcut(&mut self, keys: &str) {
+        let chord: KeyChord = keys.parse().expect("a valid key chord");
+        let command = if cfg!(target_os = "macos") {
+            Modifiers::META
+        } else {
+            Modifiers::CONTROL
+        };
+        let mut mods = Modifiers::empty();
+        mods.set(Modifiers::CONTROL, chord.ctrl);
+        mods.set(Modifiers::SHIFT, chord.shift);
+        mods.set(Modifiers::ALT, chord.alt);
+        mods.set(Modifiers::META, chord.meta);
+        if chord.command {
+            mods.insert(command);
+        }
+        let key = match chord.key.parse() {
+            Ok(key) if chord.key.chars().count() > 1 => key,
+            _ => KbKey::Character(chord.key),
+        };
+        self.simulate_key_down(&KeyEvent {
+            key,
+            mods,
+            ..Default::default()
+        });
+    }
+
+    /// The context menu that is open, which is drawn over the component while testing.
+    pub fn get_popup(&self) -> Option<&Menu> {
+        self.window_state.popup.as_ref().map(|p| &p.menu)
+    }
+
+    /// Clicks the item of the open context menu with `text`.
+    pub fn select_popup_item(&mut self, text: &str) {
+        let pos = self
+            .window_state
+            .popup
+            .as_ref()
+            .expect("a context menu is open")
+            .item_rect(text)
+            .unwrap_or_else(|| panic!("context menu has an item {text}"))
+            .center();
+        let pointer_event = PointerEvent {
+            pos,
+            button: PointerButton::Primary,
+            ..PointerEvent::default()
+        };
+        self.last_mouse_pos = Some(pos);
+        self.window_state.pointer_move(&pointer_event);
+        self.window_state.pointer_down(&pointer_event);
+        self.window_state.pointer_up(&pointer_event);
+    }
+
+    /// The options of the list opened by a widget such as a `Select`.
+    pub fn get_option_list(&self) -> Option<&[String]> {
+        self.window_state
+            .option_list
+            .as_ref()
+            .map(|l| l.list.options.as_slice())
+    }
+
+    /// Clicks the option of the open list of options with `text`, scrolling to it if needed.
+    pub fn select_option(&mut self, text: &str) {
+        let list = self
+            .window_state
+            .option_list
+            .as_mut()
+            .expect("a list of options is open");
+        let index = list
+            .list
+            .options
+            .iter()
+            .position(|o| o == text)
+            .unwrap_or_else(|| panic!("list of options has an option {text}"));
+        let pos = list.option_rect(index).center();
+        let pointer_event = PointerEvent {
+            pos,
+            button: PointerButton::Primary,
+            ..PointerEvent::default()
+        };
+        self.last_mouse_pos = Some(pos);
+        self.window_state.pointer_move(&pointer_event);
+        self.window_state.pointer_down(&pointer_event);
+        self.window_state.pointer_up(&pointer_event);
+    }
+
+    /// Selects the item of the menu bar at the end of `path`, such as `["File", "Open"]`.
+    pub fn select_menu_item(&mut self, path: &[&str]) {
+        let menu = self
+            .window_state
+            .component
+            .menu_bar()
+            .expect("the component has a menu bar");
+        match menu.find(path) {
+            Some(MenuItem::Item { runtime_id, id, .. }) => {
+                self.window_state.menu_command(*runtime_id, *id)
+            }
+            _ => panic!("menu bar has an item at {path:?}"),
+        }
+    }
+
+    /// The text in the clipboard, which is only stored in memory while testing.
+    pub fn get_clipboard(&self) -> Option<String> {
+        self.window_state.handle.get_clipboard()
+    }
+
+    pub fn set_clipboard(&mut self, text: &str) {
+        self.window_state.handle.set_clipboard(text)
+    }
+
     pub fn simulate_pointer_move(
         &mut self,
         runtime_id: RuntimeID,
@@ -281,6 +458,35 @@ impl<T: ToComponent + 'static> TestHarness<T>
 where
     <T as ToComponent>::Component: 'static,
 {
+    /// Tests the first window with a component of type `T` that was opened with
+    /// [`open_window`](crate::open_window), as windows are not created while testing.
+    pub fn from_opened_window<S: Into<Size>>(size: S) -> Option<Self> {
+        let window_state = windows::take_pending::<T>()?;
+        Some(Self::from_window_state(window_state, size.into()))
+    }
+
+    /// The component of the topmost overlay or dialog of type `D`.
+    pub fn get_dialog<D: 'static>(&mut self) -> Option<&mut D> {
+        self.window_state
+            .overlays
+            .iter_mut()
+            .rev()
+            .find_map(|o| o.component.get_comp_struct().downcast_mut::<D>())
+    }
+
+    /// Passes the messages sent with a [`WindowSender`](crate::WindowSender) to the component,
+    /// which happens before the window is painted when running.
+    pub fn receive_messages(&mut self) {
+        self.window_state.receive_messages();
+        if self
+            .window_state
+            .component
+            .update_vars(false, &mut self.window_state.handle)
+        {
+            self.window_state.resize();
+        }
+    }
+
     pub fn get_component(&mut self) -> &mut T {
         self.window_state
             .component
@@ -1,8 +1,8 @@
-use crate::TestHarness;
+use crate::{overlay, TestHarness};
 use gui_core::glazier::kurbo::Affine;
-use gui_core::vello::peniko::Color;
+use gui_core::theme;
 use gui_core::vello::{RenderParams, Renderer, RendererOptions, SceneFragment};
-use gui_core::{Component, SceneBuilder, ToComponent};
+use gui_core::{Colour, Component, SceneBuilder, ToComponent};
 use std::default::Default;
 use wgpu::{
     Buffer, BufferAddress, BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Device,
@@ -74,6 +74,21 @@ impl<T: ToComponent> TestHarness<T> {
                     .to_vec2(),
             )),
         );
+        overlay::render(
+            &mut self.window_state.overlays,
+            &mut sb,
+            self.window_state.size,
+            Affine::IDENTITY,
+        );
+        if let Some(popup) = &self.window_state.popup {
+            popup.render(&mut sb, Affine::IDENTITY);
+        }
+        if let Some(list) = &self.window_state.option_list {
+            list.render(&mut sb, Affine::IDENTITY);
+        }
+        if let Some(tooltip) = &self.window_state.tooltips.shown {
+            tooltip.render(&mut sb, Affine::IDENTITY);
+        }
 
         self.window_state
             .renderer
@@ -179,7 +194,7 @@ impl<T: ToComponent> TestHarness<T> {
         let texture_view = texture.create_view(&Default::default());
 
         let render_params = RenderParams {
-            base_color: Color::WHITE,
+            base_color: theme::get_or_default::<Colour>("background").0,
             width,
             height,
         };
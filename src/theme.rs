@@ -0,0 +1,11 @@
+//! App-wide themes, see [`gui_core::theme`]. Switch the theme with [`set_theme`] from this module
+//! so that every window is repainted with it.
+
+use crate::windows;
+pub use gui_core::theme::*;
+
+/// Switches the theme of every component, repainting every open window with the new theme.
+pub fn set_theme(theme: Theme) {
+    gui_core::theme::set_theme(theme);
+    windows::invalidate_all();
+}
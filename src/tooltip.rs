@@ -0,0 +1,120 @@
+use crate::menu::layout_text;
+use gui_core::common::text::{render_text, ParleyBrush};
+use gui_core::glazier::kurbo::{Affine, Point, Rect, Size};
+use gui_core::glazier::TimerToken;
+use gui_core::parley::layout::Layout;
+use gui_core::theme;
+use gui_core::vello::peniko::{Brush, Fill, Stroke};
+use gui_core::widget::{Handle, RuntimeID, WidgetID};
+use gui_core::{Colour, FontContext, SceneBuilder};
+use std::time::Duration;
+
+/// How long the pointer rests over a widget before its tooltip is shown.
+const DELAY: Duration = Duration::from_millis(700);
+/// The space between a tooltip and its widget.
+const GAP: f64 = 4.0;
+const PADDING: f64 = 6.0;
+
+/// The text of a tooltip, drawn above the overlays of the window next to its widget.
+pub(crate) struct Tooltip {
+    pub text: String,
+    rect: Rect,
+    layout: Layout<ParleyBrush>,
+}
+
+impl Tooltip {
+    /// Lays out the tooltip below `anchor`, or above it if there is no room below, keeping it
+    /// within `bounds` if possible.
+    pub fn new(text: String, anchor: Rect, fcx: &mut FontContext, bounds: Size) -> Self {
+        let layout = layout_text(&text, fcx);
+        let size = Size::new(
+            layout.width() as f64 + PADDING * 2.0,
+            layout.height() as f64 + PADDING * 2.0,
+        );
+        let below = anchor.y1 + GAP;
+        let y = if below + size.height > bounds.height {
+            anchor.y0 - GAP - size.height
+        } else {
+            below
+        };
+        let origin = Point::new(
+            anchor.x0.min(bounds.width - size.width).max(0.0),
+            y.max(0.0),
+        );
+        Self {
+            text,
+            rect: Rect::from_origin_size(origin, size),
+            layout,
+        }
+    }
+
+    pub fn render(&self, scene: &mut SceneBuilder, transform: Affine) {
+        let rect = self.rect.to_rounded_rect(4.0);
+        scene.fill(
+            Fill::NonZero,
+            transform,
+            &Brush::Solid(theme::get_or_default::<Colour>("surface").0),
+            None,
+            &rect,
+        );
+        scene.stroke(
+            &Stroke::new(1.0),
+            transform,
+            &Brush::Solid(theme::get_or_default::<Colour>("border").0),
+            None,
+            &rect,
+        );
+        let origin = self.rect.origin() + (PADDING, PADDING);
+        render_text(
+            scene,
+            transform * Affine::translate(origin.to_vec2()),
+            &self.layout,
+        );
+    }
+}
+
+/// Tracks the widget with a tooltip under the pointer, showing its tooltip once the pointer has
+/// rested over it for long enough.
+#[derive(Default)]
+pub(crate) struct Tooltips {
+    /// The innermost widget with a tooltip under the pointer.
+    widget: Option<(RuntimeID, WidgetID)>,
+    /// The timer that shows the tooltip of `widget`, which is removed once it has been shown or
+    /// hidden.
+    timer: Option<TimerToken>,
+    pub shown: Option<Tooltip>,
+}
+
+impl Tooltips {
+    /// Moves the pointer over `widget`, starting the timer of its tooltip if it is a different
+    /// widget. Returns true if a tooltip was hidden.
+    pub fn hover(&mut self, widget: Option<(RuntimeID, WidgetID)>, handle: &mut Handle) -> bool {
+        if widget == self.widget {
+            return false;
+        }
+        self.widget = widget;
+        self.timer = widget.map(|_| {
+            let mut token = TimerToken::INVALID;
+            handle.if_window(|w| token = w.request_timer(DELAY));
+            token
+        });
+        self.shown.take().is_some()
+    }
+
+    /// Hides the tooltip until the pointer moves over another widget. Returns true if a tooltip
+    /// was hidden.
+    pub fn hide(&mut self) -> bool {
+        self.timer = None;
+        self.shown.take().is_some()
+    }
+
+    pub fn is_timer(&self, token: TimerToken) -> bool {
+        self.timer == Some(token)
+    }
+
+    /// The widget whose tooltip should be shown now, if it is still waiting to be shown.
+    pub fn take_waiting(&mut self) -> Option<(RuntimeID, WidgetID)> {
+        self.timer.take()?;
+        self.widget
+    }
+}
@@ -0,0 +1,371 @@
+//! Applications with more than one window. Handlers can open a window with [`open_window`],
+//! which shows another root component and returns a [`WindowSender`] to send it messages.
+//! Windows can send messages back with [`main_window`] or [`window_sender`].
+
+use crate::{App, Error, WindowState, HEIGHT, WIDTH};
+use gui_core::glazier::{Application, IdleToken, WinHandler, WindowBuilder, WindowHandle};
+use gui_core::widget::RuntimeID;
+use gui_core::window::WindowConfig;
+use gui_core::{OnMessage, Size, ToComponent};
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// The idle token used to create the windows opened while handling an event.
+pub(crate) const OPEN_WINDOWS: IdleToken = IdleToken::new(1);
+
+/// Identifies a window of the application.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct WindowId(u64);
+
+/// When the application quits, set with [`App::quit_policy`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum QuitPolicy {
+    /// Quit once every window has been closed.
+    #[default]
+    LastWindowClosed,
+    /// Quit when the window created by [`run`](crate::run) is closed, closing the other windows.
+    MainWindowClosed,
+}
+
+/// A window opened by a handler, which is created once the current event has been handled.
+pub(crate) struct PendingWindow {
+    id: WindowId,
+    config: WindowConfig,
+    /// The `WindowState` of the window, which can be taken by a `TestHarness`.
+    state: Box<dyn Any>,
+    into_handler: fn(Box<dyn Any>) -> Box<dyn WinHandler>,
+}
+
+/// A message sent to a window, which passes it to the root component of the window.
+type Letter = Box<dyn FnOnce(&mut dyn Any)>;
+
+/// The messages sent to a window that it has not received yet.
+struct Mailbox {
+    /// The type of the root component of the window.
+    root: TypeId,
+    letters: Vec<Letter>,
+}
+
+#[derive(Default)]
+struct Windows {
+    next_id: u64,
+    /// The windows that have been created and not yet destroyed.
+    open: Vec<(WindowId, WindowHandle)>,
+    pending: Vec<PendingWindow>,
+    /// The mailbox of each window that has not been closed, including pending windows.
+    mailboxes: HashMap<WindowId, Mailbox>,
+    main: Option<WindowId>,
+    quit_policy: QuitPolicy,
+    quitting: bool,
+}
+
+thread_local! {
+    static WINDOWS: RefCell<Windows> = RefCell::new(Windows::default());
+}
+
+pub(crate) fn next_id() -> WindowId {
+    WINDOWS.with_borrow_mut(|windows| {
+        windows.next_id += 1;
+        WindowId(windows.next_id)
+    })
+}
+
+/// Gives the window a mailbox, so that it can be sent messages for its root component of type
+/// `root`.
+pub(crate) fn register(id: WindowId, root: TypeId) {
+    WINDOWS.with_borrow_mut(|windows| {
+        windows.mailboxes.insert(
+            id,
+            Mailbox {
+                root,
+                letters: vec![],
+            },
+        )
+    });
+}
+
+/// Takes the messages sent to the window since this was last called.
+pub(crate) fn take_letters(id: WindowId) -> Vec<Letter> {
+    WINDOWS.with_borrow_mut(|windows| {
+        windows
+            .mailboxes
+            .get_mut(&id)
+            .map(|mailbox| std::mem::take(&mut mailbox.letters))
+            .unwrap_or_default()
+    })
+}
+
+pub(crate) fn set_main(id: WindowId, quit_policy: QuitPolicy) {
+    WINDOWS.with_borrow_mut(|windows| {
+        windows.main = Some(id);
+        windows.quit_policy = quit_policy;
+    })
+}
+
+/// Creates the builder of a window using `config`.
+pub(crate) fn window_builder(app: Application, config: &WindowConfig) -> WindowBuilder {
+    let size = config.clamp_size(
+        config
+            .size
+            .unwrap_or(Size::new(WIDTH as f64, HEIGHT as f64)),
+    );
+    let mut builder = WindowBuilder::new(app).size(size);
+    if let Some(title) = &config.title {
+        builder = builder.title(title.clone());
+    }
+    if let Some(min_size) = config.min_size {
+        builder = builder.min_size(min_size);
+    }
+    if let Some(resizable) = config.resizable {
+        builder = builder.resizable(resizable);
+    }
+    if let Some(transparent) = config.transparent {
+        builder = builder.transparent(transparent);
+    }
+    builder
+}
+
+/// Opens a window showing `component`, using the settings of `app` over the `window` section of
+/// the component's layout. The window is created once the current event has been handled, or once
+/// the main window is shown if it is called before then. The quit policy of `app` is ignored, as
+/// it is set by the main window. Returns an error if the renderer of the window cannot be created.
+pub fn open_window<T>(component: T, app: App) -> Result<WindowSender<T::Message>, Error>
+where
+    T: ToComponent + OnMessage + 'static,
+    <T as ToComponent>::Component: 'static,
+    T::Message: 'static,
+{
+    let component = component.to_component_holder(RuntimeID::next());
+    let config = app.config.or(component.window_config());
+    let state = WindowState::new(component, config.clone())?;
+    let id = state.id;
+
+    let window = PendingWindow {
+        id,
+        config,
+        state: Box::new(state),
+        into_handler: |state| {
+            state
+                .downcast::<WindowState<T::Component>>()
+                .expect("the state of the window is a WindowState")
+        },
+    };
+    let waker = WINDOWS.with_borrow_mut(|windows| {
+        windows.pending.push(window);
+        windows.open.first().map(|(_, handle)| handle.clone())
+    });
+    if let Some(mut idle) = waker.and_then(|handle| handle.get_idle_handle()) {
+        idle.schedule_idle(OPEN_WINDOWS);
+    }
+    Ok(WindowSender::new::<T>(id))
+}
+
+/// Sends messages to the window `id` if its root component is a `T`, which can be a window opened
+/// with [`open_window`] or the main window. Returns `None` if the window has been closed.
+pub fn window_sender<T>(id: WindowId) -> Option<WindowSender<T::Message>>
+where
+    T: OnMessage + 'static,
+    T::Message: 'static,
+{
+    let root = WINDOWS.with_borrow(|windows| windows.mailboxes.get(&id).map(|m| m.root))?;
+    (root == TypeId::of::<T>()).then(|| WindowSender::new::<T>(id))
+}
+
+/// Sends messages to the window created by [`run`](crate::run) if its root component is a `T`,
+/// so that the windows opened by it can send messages back.
+pub fn main_window<T>() -> Option<WindowSender<T::Message>>
+where
+    T: OnMessage + 'static,
+    T::Message: 'static,
+{
+    let id = WINDOWS.with_borrow(|windows| windows.main)?;
+    window_sender::<T>(id)
+}
+
+/// Closes the window, or stops it from being created if it has not been created yet.
+pub fn close_window(id: WindowId) {
+    let handle = WINDOWS.with_borrow_mut(|windows| {
+        if let Some(index) = windows.pending.iter().position(|w| w.id == id) {
+            windows.pending.remove(index);
+            windows.mailboxes.remove(&id);
+        }
+        windows
+            .open
+            .iter()
+            .find(|(open, _)| *open == id)
+            .map(|(_, handle)| handle.clone())
+    });
+    if let Some(handle) = handle {
+        handle.close();
+    }
+}
+
+/// Creates the windows opened since this was last called.
+pub(crate) fn create_pending() -> Result<(), Error> {
+    let pending = WINDOWS.with_borrow_mut(|windows| std::mem::take(&mut windows.pending));
+    for window in pending {
+        let handler = (window.into_handler)(window.state);
+        let handle = window_builder(Application::global(), &window.config)
+            .handler(handler)
+            .build()?;
+        handle.show();
+    }
+    Ok(())
+}
+
+/// Takes the first window opened with a component of type `T` that has not been created, so
+/// that it can be tested.
+pub(crate) fn take_pending<T: ToComponent + 'static>() -> Option<WindowState<T::Component>>
+where
+    <T as ToComponent>::Component: 'static,
+{
+    WINDOWS.with_borrow_mut(|windows| {
+        let index = windows
+            .pending
+            .iter()
+            .position(|w| w.state.is::<WindowState<T::Component>>())?;
+        let window = windows.pending.remove(index);
+        window.state.downcast().ok().map(|state| *state)
+    })
+}
+
+pub(crate) fn connected(id: WindowId, handle: &WindowHandle) {
+    let pending = WINDOWS.with_borrow_mut(|windows| {
+        windows.open.push((id, handle.clone()));
+        !windows.pending.is_empty()
+    });
+    // Windows opened before any window was connected are created once one is.
+    if let Some(mut idle) = handle.get_idle_handle().filter(|_| pending) {
+        idle.schedule_idle(OPEN_WINDOWS);
+    }
+}
+
+/// Asks the other windows to update, as handlers can change `Signal`s that they use.
+pub(crate) fn update_others(id: WindowId) {
+    invalidate_where(|open| open != id);
+}
+
+/// Asks every window to repaint, such as after switching the theme.
+pub(crate) fn invalidate_all() {
+    invalidate_where(|_| true);
+}
+
+fn invalidate_where(filter: impl Fn(WindowId) -> bool) {
+    let handles: Vec<WindowHandle> = WINDOWS.with_borrow(|windows| {
+        windows
+            .open
+            .iter()
+            .filter(|(open, _)| filter(*open))
+            .map(|(_, handle)| handle.clone())
+            .collect()
+    });
+    for handle in handles {
+        handle.invalidate();
+    }
+}
+
+/// Removes the window, quitting the application if required by the quit policy.
+pub(crate) fn destroyed(id: WindowId) {
+    let to_close = WINDOWS.with_borrow_mut(|windows| {
+        windows.open.retain(|(open, _)| *open != id);
+        windows.mailboxes.remove(&id);
+        let quit = match windows.quit_policy {
+            QuitPolicy::LastWindowClosed => windows.open.is_empty(),
+            QuitPolicy::MainWindowClosed => windows.main == Some(id) || windows.open.is_empty(),
+        };
+        if !quit || windows.quitting {
+            return None;
+        }
+        windows.quitting = true;
+        windows.pending.clear();
+        Some(std::mem::take(&mut windows.open))
+    });
+    if let Some(to_close) = to_close {
+        // Closing a window destroys it, so the windows must not be borrowed.
+        for (_, handle) in to_close {
+            handle.close();
+        }
+        Application::global().quit();
+    }
+}
+
+fn is_open(id: WindowId) -> bool {
+    WINDOWS.with_borrow(|windows| windows.mailboxes.contains_key(&id))
+}
+
+/// Passes a message to the root component of a window, which is a `T` as checked when creating
+/// the sender.
+fn deliver<T: OnMessage + 'static>(comp_struct: &mut dyn Any, message: T::Message) {
+    comp_struct
+        .downcast_mut::<T>()
+        .expect("the root component of the window is a T")
+        .on_message(message);
+}
+
+fn wake(id: WindowId) {
+    let handle = WINDOWS.with_borrow(|windows| {
+        windows
+            .open
+            .iter()
+            .find(|(open, _)| *open == id)
+            .map(|(_, handle)| handle.clone())
+    });
+    if let Some(handle) = handle {
+        handle.invalidate();
+    }
+}
+
+/// Sends messages to the root component of a window, which receives them with [`OnMessage`]
+/// before the window is next painted. Clones send to the same window, so they can be given to
+/// other windows to message each other.
+pub struct WindowSender<M> {
+    id: WindowId,
+    deliver: fn(&mut dyn Any, M),
+}
+
+impl<M> Clone for WindowSender<M> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            deliver: self.deliver,
+        }
+    }
+}
+
+impl<M: 'static> WindowSender<M> {
+    fn new<T: OnMessage<Message = M> + 'static>(id: WindowId) -> Self {
+        Self {
+            id,
+            deliver: deliver::<T>,
+        }
+    }
+
+    pub fn id(&self) -> WindowId {
+        self.id
+    }
+
+    /// Sends `message` to the window. Messages sent to a closed window are dropped.
+    pub fn send(&self, message: M) {
+        let deliver = self.deliver;
+        let letter: Letter = Box::new(move |comp_struct| deliver(comp_struct, message));
+        let sent = WINDOWS.with_borrow_mut(|windows| match windows.mailboxes.get_mut(&self.id) {
+            Some(mailbox) => {
+                mailbox.letters.push(letter);
+                true
+            }
+            None => false,
+        });
+        if sent {
+            wake(self.id);
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        is_open(self.id)
+    }
+
+    pub fn close(&self) {
+        close_window(self.id)
+    }
+}